@@ -0,0 +1,70 @@
+//! Keystroke throughput of the metrics pipeline.
+//!
+//! Run with `cargo bench --bench metrics_throughput`. Each case is timed several
+//! times and the best run is reported as keystrokes per second, so later
+//! optimizations of `record_keystroke` and the per-keystroke recalculation can be
+//! compared against the baseline below.
+//!
+//! Baseline (release build, single thread). The recalculation case is far slower
+//! than recording alone and falls further behind as a session grows, because every
+//! call re-sums the WPM histogram's rolling 10s/60s windows over all their samples:
+//!
+//! | case                                 | keystrokes/sec |
+//! |--------------------------------------|----------------|
+//! | record_keystroke (88,000 keys)       |    ~10,000,000 |
+//! | record + calculate_metrics (8,800)   |       ~100,000 |
+//! | SpringKeys::feed_str (2,199 keys)    |         ~7,000 |
+
+use spring_keys::{SpringKeys, TypingMetrics};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 5;
+const PANGRAM: &str = "the quick brown fox jumps over the lazy dog ";
+
+/// Time `run` (which types `keystrokes` keys) and print the best keystrokes/sec
+fn bench(name: &str, keystrokes: usize, mut run: impl FnMut()) {
+    let best = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or(Duration::ZERO);
+    let rate = keystrokes as f64 / best.as_secs_f64().max(f64::EPSILON);
+    println!("{:<30} {:>12.0} keystrokes/sec ({} keys in {:?})", name, rate, keystrokes, best);
+}
+
+fn main() {
+    let text: String = PANGRAM.repeat(2_000);
+    let chars: Vec<char> = text.chars().collect();
+
+    bench("record_keystroke", chars.len(), || {
+        let mut metrics = TypingMetrics::new();
+        for (i, &c) in chars.iter().enumerate() {
+            metrics.record_keystroke(c, c, i);
+        }
+        black_box(&metrics);
+    });
+
+    // Recalculating on every key is much slower, so use a tenth of the text
+    let short = &chars[..chars.len() / 10];
+    bench("record + calculate_metrics", short.len(), || {
+        let mut metrics = TypingMetrics::new();
+        for (i, &c) in short.iter().enumerate() {
+            metrics.record_keystroke(c, c, i);
+            metrics.calculate_overall_metrics();
+        }
+        black_box(&metrics);
+    });
+
+    // The full key-event path: input processing, validation and metrics
+    let quote = PANGRAM.repeat(50);
+    let quote = quote.trim_end();
+    let mut app = SpringKeys::new();
+    bench("SpringKeys::feed_str", quote.len(), || {
+        app.start_typing_session(Some(quote.to_string()));
+        black_box(app.feed_str(quote));
+    });
+}
@@ -1,9 +1,9 @@
 use crossterm::{
-    terminal::{enable_raw_mode, disable_raw_mode, Clear, ClearType},
+    terminal::{self, enable_raw_mode, disable_raw_mode, Clear, ClearType},
     cursor::{Hide, Show},
     ExecutableCommand,
 };
-use spring_keys::{TypingMetrics, ui::heatmap};
+use spring_keys::{TypingMetrics, ui::{caps, heatmap}};
 use std::io::{self, stdout};
 
 fn main() -> io::Result<()> {
@@ -20,7 +20,8 @@ fn main() -> io::Result<()> {
     metrics.simulate_demo_data();
 
     // Draw the unified keyboard heatmap
-    heatmap::draw_unified_keyboard_heatmap(&mut stdout, &metrics, 2)?;
+    let (width, _) = terminal::size()?;
+    heatmap::draw_unified_keyboard_heatmap(&mut stdout, &metrics, 2, width, caps::detect())?;
 
     // Wait for user input
     let mut input = String::new();
@@ -0,0 +1,108 @@
+//! `spring-keys check`: load the config, quote files and stats directory without starting
+//! a session, as a smoke test for packagers and CI
+
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use crate::config::Config;
+use crate::quotes::Quote;
+
+/// What `run_checks` found
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CheckReport {
+    /// How the config file loaded: "ok", "missing (defaults are used)" or the parse error
+    pub config: String,
+    /// JSON files found in the quotes directory
+    pub quote_files: usize,
+    /// Quotes that parsed, across all files
+    pub quotes: usize,
+    pub stats_dir_writable: bool,
+    /// One line per problem; the check passes when this is empty
+    pub problems: Vec<String>,
+}
+
+impl CheckReport {
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Check the config at `config_path`, every quote file in `quotes_dir` and that files can
+/// be created in `stats_dir`. Nothing is written apart from a probe file in `stats_dir`.
+pub fn run_checks(config_path: &Path, quotes_dir: &Path, stats_dir: &Path) -> CheckReport {
+    let mut report = CheckReport::default();
+
+    // Parse the config directly: `Config::load` would write a migrated copy back
+    report.config = match fs::read_to_string(config_path) {
+        Ok(content) => match toml::from_str::<Config>(&content) {
+            Ok(_) => "ok".to_string(),
+            Err(e) => {
+                report.problems.push(format!("{}: {}", config_path.display(), e.message()));
+                "invalid".to_string()
+            }
+        },
+        Err(_) => "missing (defaults are used)".to_string(),
+    };
+
+    check_quote_files(quotes_dir, &mut report);
+
+    report.stats_dir_writable = match probe_writable(stats_dir) {
+        Ok(()) => true,
+        Err(e) => {
+            report.problems.push(format!("{}: not writable: {}", stats_dir.display(), e));
+            false
+        }
+    };
+    report
+}
+
+fn check_quote_files(dir: &Path, report: &mut CheckReport) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            report.problems.push(format!("{}: {}", dir.display(), e));
+            return;
+        }
+    };
+    let mut paths: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        report.quote_files += 1;
+        let quotes = match fs::read_to_string(&path).map(|content| serde_json::from_str::<Vec<Quote>>(&content)) {
+            Ok(Ok(quotes)) => quotes,
+            Ok(Err(e)) => {
+                report.problems.push(format!("{}: invalid quote file: {}", path.display(), e));
+                continue;
+            }
+            Err(e) => {
+                report.problems.push(format!("{}: {}", path.display(), e));
+                continue;
+            }
+        };
+        if quotes.is_empty() {
+            report.problems.push(format!("{}: no quotes", path.display()));
+        }
+        for (i, quote) in quotes.iter().enumerate() {
+            if quote.text.trim().is_empty() {
+                report.problems.push(format!("{}: quote {} has no text", path.display(), i + 1));
+            }
+        }
+        report.quotes += quotes.len();
+    }
+
+    if report.quote_files == 0 {
+        report.problems.push(format!("{}: no quote files", dir.display()));
+    }
+}
+
+fn probe_writable(dir: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let probe = dir.join(".spring-keys-check");
+    fs::write(&probe, b"")?;
+    fs::remove_file(&probe)
+}
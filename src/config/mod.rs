@@ -1,14 +1,25 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
+use log::{info, warn};
+use crate::core::metrics::WpmMode;
+use crate::quotes::QuoteDifficulty;
 
 pub const DEFAULT_CONFIG_FILE: &str = "springkeys.toml";
+/// Session files kept in the stats directory unless configured otherwise
+pub const DEFAULT_MAX_SESSIONS: usize = 1000;
+/// Default for `Preferences::key_decay`
+pub const DEFAULT_KEY_DECAY: f64 = 0.9;
 
-/// Main configuration structure for the application
+/// Main configuration structure for the application.
+/// Missing fields fall back to their defaults so older config files still load.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
-    /// Application version
+    /// Version of the application that last wrote this file (empty if unknown)
+    #[serde(default)]
     pub version: String,
     /// User preferences
     pub preferences: Preferences,
@@ -18,6 +29,7 @@ pub struct Config {
 
 /// User preferences
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Preferences {
     /// User name
     pub username: String,
@@ -25,10 +37,47 @@ pub struct Preferences {
     pub difficulty: DifficultyLevel,
     /// Sound effects enable/disable
     pub sound_enabled: bool,
+    /// Only complete a quote when the typed text exactly equals it
+    pub require_exact_completion: bool,
+    /// Strict drills: refuse wrong characters instead of letting errors into the input
+    pub stop_on_error: bool,
+    /// Target words per minute for a completed quote
+    pub goal_wpm: Option<f64>,
+    /// Target accuracy percentage for a completed quote
+    pub goal_accuracy: Option<f64>,
+    /// Index of the current lesson in the curriculum used by `learn`
+    pub lesson_stage: usize,
+    /// How WPM counts words: fixed characters per word or actual words
+    pub wpm_mode: WpmMode,
+    /// Count every backspace as an error and take points off the score
+    pub backspace_penalty: bool,
+    /// Take letter case only from SHIFT and the terminal, ignoring caps lock key events
+    pub respect_shift_only: bool,
+    /// In practice mode, skip quotes with characters the keyboard can't type (e.g. Kanji)
+    pub typeable_quotes_only: bool,
+    /// End the session and show the screensaver after this many seconds without a key; off when unset
+    pub inactivity_timeout_secs: Option<u64>,
+    /// Show the results card this many milliseconds after a quote before moving on by
+    /// itself; 0 keeps it up until a key is pressed
+    pub completion_pause_ms: u64,
+    /// Most session files kept in the stats directory before the oldest are deleted
+    pub max_sessions: usize,
+    /// In sentence mode, skip the spaces after a finished sentence instead of typing them
+    pub sentence_skip_spaces: bool,
+    /// Bookmarked quotes, by `quotes::quote_hash` of their text
+    pub favorite_quotes: Vec<String>,
+    /// `quotes::LOCKED_CATEGORIES` the user has unlocked; they stay unlocked even if accuracy drops later
+    pub unlocked_categories: Vec<String>,
+    /// Weight a session's key timings keep in the all-time key averages for every session
+    /// after it, from 0 (only the latest session counts) to 1 (all count equally)
+    pub key_decay: f64,
+    /// Quote difficulty served at each user level; levels left out use `default_difficulty_map`
+    pub difficulty_map: HashMap<DifficultyLevel, QuoteDifficulty>,
 }
 
 /// UI settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct UiSettings {
     /// UI theme
     pub theme: String,
@@ -40,10 +89,68 @@ pub struct UiSettings {
     pub show_accuracy: bool,
     /// Show error highlighting
     pub highlight_errors: bool,
+    /// Hide typed input behind placeholders to train typing without looking
+    pub blind_mode: bool,
+    /// How WPM and accuracy numbers are printed
+    pub display_format: DisplayFormat,
+    /// Which screen sections are drawn while typing
+    pub render_mode: RenderMode,
+    /// How the input cursor is drawn
+    pub cursor_style: CursorStyle,
+    /// Draw on the alternate screen; when off the UI draws inline and the last frame stays in scrollback
+    pub alternate_screen: bool,
+}
+
+/// Look of the input cursor
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CursorStyle {
+    /// Highlighted cell with ▼/▲ markers above and below
+    #[default]
+    Block,
+    /// Underlined character without a highlight
+    Underline,
+    /// Bold character between vertical bars above and below
+    Bar,
+    /// Square brackets above and below the character
+    Bracket,
+}
+
+/// How much of the typing screen to draw
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderMode {
+    /// Heatmap, finger stats, category indicators and both stat lines
+    #[default]
+    Full,
+    /// Only the quote, the input and one WPM/accuracy line
+    Minimal,
+    /// The minimal layout with a one-line-per-row Braille heatmap under it; `Full`
+    /// falls back to this in terminals too short for the full heatmap
+    Compact,
+}
+
+/// Number formatting for displayed metrics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayFormat {
+    /// Digits after the decimal point
+    pub precision: usize,
+    /// Group the integer part in thousands, e.g. 1,234.5
+    pub thousands_separator: bool,
+}
+
+impl Default for DisplayFormat {
+    fn default() -> Self {
+        Self {
+            precision: 1,
+            thousands_separator: false,
+        }
+    }
 }
 
 /// Difficulty levels
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum DifficultyLevel {
     /// Beginner level
@@ -72,6 +179,23 @@ impl Default for Preferences {
             username: "Captain Typebeard".to_string(),
             difficulty: DifficultyLevel::Beginner,
             sound_enabled: true,
+            require_exact_completion: false,
+            stop_on_error: false,
+            goal_wpm: None,
+            goal_accuracy: None,
+            lesson_stage: 0,
+            wpm_mode: WpmMode::default(),
+            backspace_penalty: false,
+            respect_shift_only: false,
+            typeable_quotes_only: true,
+            inactivity_timeout_secs: None,
+            completion_pause_ms: 0,
+            max_sessions: DEFAULT_MAX_SESSIONS,
+            sentence_skip_spaces: true,
+            favorite_quotes: Vec::new(),
+            unlocked_categories: Vec::new(),
+            key_decay: DEFAULT_KEY_DECAY,
+            difficulty_map: default_difficulty_map(),
         }
     }
 }
@@ -84,21 +208,69 @@ impl Default for UiSettings {
             show_wpm: true,
             show_accuracy: true,
             highlight_errors: true,
+            blind_mode: false,
+            display_format: DisplayFormat::default(),
+            render_mode: RenderMode::default(),
+            cursor_style: CursorStyle::default(),
+            alternate_screen: true,
         }
     }
 }
 
+/// Beginners get easy quotes, intermediates medium ones, and advanced and expert typists hard ones
+pub fn default_difficulty_map() -> HashMap<DifficultyLevel, QuoteDifficulty> {
+    HashMap::from([
+        (DifficultyLevel::Beginner, QuoteDifficulty::Easy),
+        (DifficultyLevel::Intermediate, QuoteDifficulty::Medium),
+        (DifficultyLevel::Advanced, QuoteDifficulty::Hard),
+        (DifficultyLevel::Expert, QuoteDifficulty::Hard),
+    ])
+}
+
 impl Config {
+    /// Quote difficulty to serve a user at `level`, from `difficulty_map`
+    pub fn quote_difficulty_for(&self, level: DifficultyLevel) -> QuoteDifficulty {
+        match self.preferences.difficulty_map.get(&level) {
+            Some(difficulty) => *difficulty,
+            None => default_difficulty_map()[&level],
+        }
+    }
+
     /// Load configuration from the specified file path
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
         let content = fs::read_to_string(&path)
             .map_err(|e| ConfigError::IoError(e, path.as_ref().to_path_buf()))?;
         
-        let config: Config = toml::from_str(&content)
+        let mut config: Config = toml::from_str(&content)
             .map_err(|e| ConfigError::ParseError(e))?;
+
+        // Write migrated configs back so the file picks up new fields
+        if config.migrate() {
+            if let Err(e) = config.save(&path) {
+                warn!("Failed to save migrated config: {}", e);
+            }
+        }
         
         Ok(config)
     }
+
+    /// Bring a config written by another version up to date.
+    /// Missing fields are already defaulted during parsing; this stamps the current
+    /// version and returns whether anything changed.
+    pub fn migrate(&mut self) -> bool {
+        let current = env!("CARGO_PKG_VERSION");
+        if self.version == current {
+            return false;
+        }
+
+        info!(
+            "Migrating config from version {} to {}",
+            if self.version.is_empty() { "unknown" } else { &self.version },
+            current
+        );
+        self.version = current.to_string();
+        true
+    }
     
     /// Save configuration to the specified file path
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ConfigError> {
@@ -121,6 +293,43 @@ impl Config {
     }
 }
 
+/// Settings given on the command line, which win over the config file, including
+/// after it's reloaded. `None` leaves the file's setting alone.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigOverrides {
+    /// `--difficulty`
+    pub difficulty: Option<DifficultyLevel>,
+    /// `--max-sessions`
+    pub max_sessions: Option<usize>,
+    /// `--minimal`
+    pub render_mode: Option<RenderMode>,
+    /// `--blind`
+    pub blind_mode: Option<bool>,
+    /// `--no-altscreen`
+    pub alternate_screen: Option<bool>,
+}
+
+impl ConfigOverrides {
+    /// Write the overridden settings into `config`
+    pub fn apply(&self, config: &mut Config) {
+        if let Some(difficulty) = self.difficulty {
+            config.preferences.difficulty = difficulty;
+        }
+        if let Some(max) = self.max_sessions {
+            config.preferences.max_sessions = max;
+        }
+        if let Some(mode) = self.render_mode {
+            config.ui.render_mode = mode;
+        }
+        if let Some(blind) = self.blind_mode {
+            config.ui.blind_mode = blind;
+        }
+        if let Some(alternate) = self.alternate_screen {
+            config.ui.alternate_screen = alternate;
+        }
+    }
+}
+
 /// Configuration error types
 #[derive(Debug, thiserror::Error)]
 pub enum ConfigError {
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// Outcome of comparing a completed session against the user's goals.
+/// Deltas are actual minus goal, and `None` when that goal isn't set.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GoalResult {
+    /// Every goal that is set was reached
+    Met { wpm_delta: Option<f64>, accuracy_delta: Option<f64> },
+    /// At least one goal was not reached
+    Missed { wpm_delta: Option<f64>, accuracy_delta: Option<f64> },
+}
+
+impl GoalResult {
+    /// Compare a session's WPM and accuracy with the goals, returning `None` if no goals are set
+    pub fn evaluate(goal_wpm: Option<f64>, goal_accuracy: Option<f64>, wpm: f64, accuracy: f64) -> Option<Self> {
+        if goal_wpm.is_none() && goal_accuracy.is_none() {
+            return None;
+        }
+
+        let wpm_delta = goal_wpm.map(|goal| wpm - goal);
+        let accuracy_delta = goal_accuracy.map(|goal| accuracy - goal);
+        let met = wpm_delta.is_none_or(|d| d >= 0.0) && accuracy_delta.is_none_or(|d| d >= 0.0);
+
+        Some(if met {
+            GoalResult::Met { wpm_delta, accuracy_delta }
+        } else {
+            GoalResult::Missed { wpm_delta, accuracy_delta }
+        })
+    }
+
+    pub fn is_met(&self) -> bool {
+        matches!(self, GoalResult::Met { .. })
+    }
+}
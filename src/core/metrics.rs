@@ -1,10 +1,63 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
 use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
 use chrono::Utc;
 use std::fs;
 use std::path::PathBuf;
 use crate::core::histogram::HistogramStats;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Default cap on recorded inter-keystroke intervals (longer pauses are reading time, not typing)
+pub const DEFAULT_MAX_RECORDED_INTERVAL_MS: f64 = 2000.0;
+
+/// Keystrokes needed before WPM is steady enough to count towards `peak_wpm`
+pub const PEAK_WPM_MIN_KEYSTROKES: usize = 10;
+
+/// How correctly typed text is turned into a word count for WPM
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WpmMode {
+    /// Every N correct characters count as one word (the usual standard is 5)
+    CharsPerWord(u8),
+    /// Count real words: each correctly typed space ends one
+    ActualWords,
+}
+
+impl Default for WpmMode {
+    fn default() -> Self {
+        WpmMode::CharsPerWord(5)
+    }
+}
+
+impl WpmMode {
+    /// Words typed, given the correct character and correct space counts
+    pub fn words(&self, correct_chars: usize, correct_spaces: usize) -> f64 {
+        match self {
+            WpmMode::CharsPerWord(n) => correct_chars as f64 / (*n).max(1) as f64,
+            WpmMode::ActualWords => correct_spaces as f64,
+        }
+    }
+}
+
+/// Physical keyboard layout that quotes are typed on
+#[derive(Debug, Clone, Copy, Default, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub enum KeyboardLayout {
+    /// US QWERTY
+    #[default]
+    Qwerty,
+}
+
+impl KeyboardLayout {
+    /// Whether `c` can be typed on this layout, with or without SHIFT
+    pub fn has_key(&self, c: char) -> bool {
+        match self {
+            KeyboardLayout::Qwerty => matches!(c, ' ' | '\t' | '\n') || KeyboardRow::for_key(c).is_some(),
+        }
+    }
+}
 
 /// Represents keyboard rows for metrics tracking
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Serialize, Deserialize)]
@@ -15,6 +68,72 @@ pub enum KeyboardRow {
     Bottom,
 }
 
+impl KeyboardRow {
+    /// Every row from the number row down
+    pub const ALL: [KeyboardRow; 4] = [
+        KeyboardRow::Number,
+        KeyboardRow::Top,
+        KeyboardRow::Home,
+        KeyboardRow::Bottom,
+    ];
+
+    /// Row of the key that types `c` on a US QWERTY keyboard, if it's on the main keyboard
+    pub fn for_key(c: char) -> Option<KeyboardRow> {
+        let row = match unshifted(c) {
+            '`' | '1'..='9' | '0' | '-' | '=' => KeyboardRow::Number,
+            'q' | 'w' | 'e' | 'r' | 't' | 'y' | 'u' | 'i' | 'o' | 'p' | '[' | ']' | '\\' => KeyboardRow::Top,
+            'a' | 's' | 'd' | 'f' | 'g' | 'h' | 'j' | 'k' | 'l' | ';' | '\'' => KeyboardRow::Home,
+            'z' | 'x' | 'c' | 'v' | 'b' | 'n' | 'm' | ',' | '.' | '/' => KeyboardRow::Bottom,
+            _ => return None,
+        };
+        Some(row)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyboardRow::Number => "number",
+            KeyboardRow::Top => "top",
+            KeyboardRow::Home => "home",
+            KeyboardRow::Bottom => "bottom",
+        }
+    }
+}
+
+/// The unshifted key that types `c` on a US QWERTY layout; shifted symbols share a key with their base
+fn unshifted(c: char) -> char {
+    match c {
+        '~' => '`', '!' => '1', '@' => '2', '#' => '3', '$' => '4', '%' => '5',
+        '^' => '6', '&' => '7', '*' => '8', '(' => '9', ')' => '0', '_' => '-',
+        '+' => '=', '{' => '[', '}' => ']', '|' => '\\', ':' => ';', '"' => '\'',
+        '<' => ',', '>' => '.', '?' => '/',
+        c => c.to_ascii_lowercase(),
+    }
+}
+
+impl fmt::Display for KeyboardRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for KeyboardRow {
+    type Err = ParseNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter()
+            .find(|row| row.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseNameError { kind: "keyboard row", name: s.to_string() })
+    }
+}
+
+/// Error for a finger or row name that doesn't match any variant
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unknown {kind} '{name}'")]
+pub struct ParseNameError {
+    pub kind: &'static str,
+    pub name: String,
+}
+
 /// Represents finger used for typing
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Finger {
@@ -30,6 +149,70 @@ pub enum Finger {
     RightPinky,
 }
 
+impl Finger {
+    /// Every finger from left pinky to right pinky
+    pub const ALL: [Finger; 10] = [
+        Finger::LeftPinky,
+        Finger::LeftRing,
+        Finger::LeftMiddle,
+        Finger::LeftIndex,
+        Finger::LeftThumb,
+        Finger::RightThumb,
+        Finger::RightIndex,
+        Finger::RightMiddle,
+        Finger::RightRing,
+        Finger::RightPinky,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Finger::LeftPinky => "left-pinky",
+            Finger::LeftRing => "left-ring",
+            Finger::LeftMiddle => "left-middle",
+            Finger::LeftIndex => "left-index",
+            Finger::LeftThumb => "left-thumb",
+            Finger::RightThumb => "right-thumb",
+            Finger::RightIndex => "right-index",
+            Finger::RightMiddle => "right-middle",
+            Finger::RightRing => "right-ring",
+            Finger::RightPinky => "right-pinky",
+        }
+    }
+
+    /// Finger that types `c` in standard QWERTY touch typing, if it's on the main keyboard
+    pub fn for_key(c: char) -> Option<Finger> {
+        let finger = match unshifted(c) {
+            '`' | '1' | 'q' | 'a' | 'z' => Finger::LeftPinky,
+            '2' | 'w' | 's' | 'x' => Finger::LeftRing,
+            '3' | 'e' | 'd' | 'c' => Finger::LeftMiddle,
+            '4' | '5' | 'r' | 't' | 'f' | 'g' | 'v' | 'b' => Finger::LeftIndex,
+            '6' | '7' | 'y' | 'u' | 'h' | 'j' | 'n' | 'm' => Finger::RightIndex,
+            '8' | 'i' | 'k' | ',' => Finger::RightMiddle,
+            '9' | 'o' | 'l' | '.' => Finger::RightRing,
+            '0' | '-' | '=' | 'p' | '[' | ']' | '\\' | ';' | '\'' | '/' => Finger::RightPinky,
+            ' ' => Finger::RightThumb,
+            _ => return None,
+        };
+        Some(finger)
+    }
+}
+
+impl fmt::Display for Finger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Finger {
+    type Err = ParseNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter()
+            .find(|finger| finger.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseNameError { kind: "finger", name: s.to_string() })
+    }
+}
+
 /// Represents a typing error
 #[derive(Debug, Clone, Serialize)]
 pub struct TypingError {
@@ -62,14 +245,97 @@ impl<'de> Deserialize<'de> for TypingError {
     }
 }
 
+/// Characters of the quote shown either side of a mistake in `mistake_report`
+const MISTAKE_CONTEXT_CHARS: usize = 10;
+
+/// One mistake of a finished quote, for the review after a session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MistakeEntry {
+    /// Position in the quote, counted in grapheme clusters like `TypingSession::current_position`
+    pub position: usize,
+    pub expected: char,
+    pub actual: char,
+    /// Word of the quote the mistake was made in
+    pub expected_word: String,
+    /// Stretch of the quote around the mistake
+    pub context: String,
+}
+
+/// Flat, serializable view of the live metrics, e.g. for an IPC or HTTP layer, so the
+/// wire format doesn't follow every change to `TypingMetrics`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    pub wpm: f64,
+    pub accuracy: f64,
+    pub keystrokes: usize,
+    pub errors: usize,
+    /// Share of the current quote typed correctly, from 0.0 to 1.0
+    pub progress: f64,
+    pub peak_wpm: f64,
+}
+
 /// Extended statistics for tracking performance over time
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExtendedStats {
     pub current: f64,
+    /// Mean of the values recorded in the last 10 seconds
     pub avg_10s: f64,
+    /// Mean of the values recorded in the last 60 seconds
     pub avg_60s: f64,
     pub fastest: f64,
     pub slowest: f64,
+    /// Mean of every value ever recorded
+    #[serde(default)]
+    pub mean: f64,
+    /// Number of values folded into `mean`
+    #[serde(default)]
+    pub count: u64,
+    /// Mean with values from older sessions decayed away, see `update_aged`
+    #[serde(default)]
+    pub recent_mean: f64,
+    /// Total decayed weight behind `recent_mean`; 0 until `update_aged` is first used
+    #[serde(default)]
+    pub recent_weight: f64,
+    /// Session the newest value in `recent_mean` came from
+    #[serde(default)]
+    pub recent_session: u64,
+    /// Timestamped values from the last minute, newest last, for the windowed averages
+    #[serde(skip)]
+    samples: VecDeque<(Instant, f64)>,
+}
+
+/// Per-key entry in an exported heat map
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct HeatmapEntry {
+    /// Average time to type the key (in milliseconds)
+    pub avg_ms: f64,
+    /// Number of recorded hits, when tracked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hits: Option<usize>,
+}
+
+/// Serialize heat map entries as pretty JSON, sorted by key
+pub fn heatmap_entries_to_json(entries: &BTreeMap<char, HeatmapEntry>) -> String {
+    serde_json::to_string_pretty(entries).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Per-key difference between two sets of average timings (negative = faster)
+pub fn diff_key_averages(current: &HashMap<char, f64>, baseline: &HashMap<char, f64>) -> HashMap<char, f64> {
+    current
+        .iter()
+        .filter_map(|(key, avg)| baseline.get(key).map(|base| (*key, avg - base)))
+        .collect()
+}
+
+/// Read per-key average timings from either serialized `TypingMetrics` (geometric
+/// averages) or a `heatmap export` file
+pub fn key_averages_from_json(json: &str) -> Option<HashMap<char, f64>> {
+    if let Ok(metrics) = serde_json::from_str::<TypingMetrics>(json) {
+        return Some(metrics.get_key_geometric_averages());
+    }
+    serde_json::from_str::<BTreeMap<char, HeatmapEntry>>(json)
+        .ok()
+        .map(|entries| entries.into_iter().map(|(key, entry)| (key, entry.avg_ms)).collect())
 }
 
 /// Per-character typing statistics
@@ -111,6 +377,8 @@ pub struct TypingMetrics {
     pub correct_keystrokes: usize,
     #[serde(skip)]
     pub errors: Vec<TypingError>,
+    /// (expected, actual, position) of every error, kept in the saved stats unlike `errors`
+    pub error_summary: Vec<(char, char, usize)>,
     pub wpm: f64,
     pub accuracy: f64,
     pub key_timings: HashMap<char, Vec<f64>>,
@@ -127,6 +395,26 @@ pub struct TypingMetrics {
     pub last_keystroke_time: Option<Instant>,
     pub key_histogram: HistogramStats,
     pub wpm_histogram: HistogramStats,
+    /// Intervals above this many milliseconds are not recorded as key timings
+    #[serde(skip)]
+    pub max_recorded_interval_ms: f64,
+    /// (elapsed_seconds, wpm) samples taken each time the metrics are recalculated
+    #[serde(skip)]
+    pub wpm_samples: Vec<(f64, f64)>,
+    /// Number of keystrokes typed by each finger
+    pub finger_counts: HashMap<Finger, usize>,
+    /// Correctly typed spaces, i.e. completed words for `WpmMode::ActualWords`
+    pub correct_spaces: usize,
+    /// How `wpm` turns typed characters into words
+    #[serde(skip)]
+    pub wpm_mode: WpmMode,
+    /// Highest WPM reached once `PEAK_WPM_MIN_KEYSTROKES` were typed
+    pub peak_wpm: f64,
+    /// Seconds into the session when `peak_wpm` was reached
+    pub peak_wpm_at: f64,
+    /// Quote the positions in `errors` refer to, for `mistake_report`
+    #[serde(skip)]
+    pub quote_text: String,
 }
 
 impl<'de> Deserialize<'de> for TypingMetrics {
@@ -138,6 +426,8 @@ impl<'de> Deserialize<'de> for TypingMetrics {
         struct Helper {
             keystrokes: usize,
             correct_keystrokes: usize,
+            #[serde(default)]
+            error_summary: Vec<(char, char, usize)>,
             wpm: f64,
             accuracy: f64,
             key_timings: HashMap<char, Vec<f64>>,
@@ -152,6 +442,14 @@ impl<'de> Deserialize<'de> for TypingMetrics {
             bottom_row_metrics: CategoryMetrics,
             key_histogram: HistogramStats,
             wpm_histogram: HistogramStats,
+            #[serde(default)]
+            finger_counts: HashMap<Finger, usize>,
+            #[serde(default)]
+            correct_spaces: usize,
+            #[serde(default)]
+            peak_wpm: f64,
+            #[serde(default)]
+            peak_wpm_at: f64,
         }
 
         let helper = Helper::deserialize(deserializer)?;
@@ -161,6 +459,7 @@ impl<'de> Deserialize<'de> for TypingMetrics {
             keystrokes: helper.keystrokes,
             correct_keystrokes: helper.correct_keystrokes,
             errors: Vec::new(),
+            error_summary: helper.error_summary,
             wpm: helper.wpm,
             accuracy: helper.accuracy,
             key_timings: helper.key_timings,
@@ -176,6 +475,14 @@ impl<'de> Deserialize<'de> for TypingMetrics {
             last_keystroke_time: None,
             key_histogram: helper.key_histogram,
             wpm_histogram: helper.wpm_histogram,
+            max_recorded_interval_ms: DEFAULT_MAX_RECORDED_INTERVAL_MS,
+            wpm_samples: Vec::new(),
+            finger_counts: helper.finger_counts,
+            correct_spaces: helper.correct_spaces,
+            wpm_mode: WpmMode::default(),
+            peak_wpm: helper.peak_wpm,
+            peak_wpm_at: helper.peak_wpm_at,
+            quote_text: String::new(),
         })
     }
 }
@@ -188,6 +495,7 @@ impl TypingMetrics {
             keystrokes: 0,
             correct_keystrokes: 0,
             errors: Vec::new(),
+            error_summary: Vec::new(),
             wpm: 0.0,
             accuracy: 0.0,
             key_timings: HashMap::new(),
@@ -203,24 +511,23 @@ impl TypingMetrics {
             last_keystroke_time: None,
             key_histogram: HistogramStats::new(),
             wpm_histogram: HistogramStats::new(),
+            max_recorded_interval_ms: DEFAULT_MAX_RECORDED_INTERVAL_MS,
+            wpm_samples: Vec::new(),
+            finger_counts: HashMap::new(),
+            correct_spaces: 0,
+            wpm_mode: WpmMode::default(),
+            peak_wpm: 0.0,
+            peak_wpm_at: 0.0,
+            quote_text: String::new(),
         };
 
         // Initialize finger stats
-        for finger in [
-            Finger::LeftPinky, Finger::LeftRing, Finger::LeftMiddle, Finger::LeftIndex,
-            Finger::LeftThumb, Finger::RightThumb,
-            Finger::RightIndex, Finger::RightMiddle, Finger::RightRing, Finger::RightPinky,
-        ] {
+        for finger in Finger::ALL {
             metrics.finger_stats.insert(finger, ExtendedStats::new());
         }
 
         // Initialize row stats
-        for row in [
-            KeyboardRow::Number,
-            KeyboardRow::Top,
-            KeyboardRow::Home,
-            KeyboardRow::Bottom,
-        ] {
+        for row in KeyboardRow::ALL {
             metrics.row_stats.insert(row, ExtendedStats::new());
         }
 
@@ -231,6 +538,9 @@ impl TypingMetrics {
         self.keystrokes += 1;
         if c == expected {
             self.correct_keystrokes += 1;
+            if c == ' ' {
+                self.correct_spaces += 1;
+            }
         } else {
             self.errors.push(TypingError {
                 expected,
@@ -241,26 +551,194 @@ impl TypingMetrics {
             *self.key_errors.entry(c).or_insert(0) += 1;
         }
 
+        if let Some(finger) = Finger::for_key(c) {
+            *self.finger_counts.entry(finger).or_insert(0) += 1;
+        }
+
         let now = Instant::now();
-        if let Some(last_time) = self.last_keystroke_time {
-            let time_ms = now.duration_since(last_time).as_millis() as f64;
+        // Skip long pauses (e.g. reading a new quote) so they don't skew the speed range
+        let interval_ms = self.last_keystroke_time
+            .map(|last_time| now.duration_since(last_time).as_millis() as f64)
+            .filter(|&time_ms| time_ms <= self.max_recorded_interval_ms);
+        if let Some(time_ms) = interval_ms {
             self.key_timings.entry(c).or_insert_with(Vec::new).push(time_ms);
             self.key_histogram.add_value(time_ms);
         }
         self.last_keystroke_time = Some(now);
+
+        // Credit the row of the key that should have been typed; untimed keys (the first one,
+        // or one after a long pause) would count as 0ms, so they're left out
+        if let (Some(row), Some(time_ms)) = (KeyboardRow::for_key(expected), interval_ms) {
+            self.row_metrics_mut(row).update(time_ms as u64, c == expected);
+            self.row_stats.entry(row).or_insert_with(ExtendedStats::new).update(time_ms, now);
+        }
     }
 
     pub fn calculate_overall_metrics(&mut self) {
+        // `errors` isn't saved, so metrics loaded from disk keep the summary they came with
+        if !self.errors.is_empty() {
+            self.error_summary = self.errors.iter().map(|e| (e.expected, e.actual, e.position)).collect();
+        }
+        let elapsed = self.recompute_rates();
+        self.wpm_histogram.add_value(self.wpm);
+        if elapsed > 0.0 {
+            self.wpm_samples.push((elapsed, self.wpm));
+        }
+        if self.keystrokes >= PEAK_WPM_MIN_KEYSTROKES && self.wpm > self.peak_wpm {
+            self.peak_wpm = self.wpm;
+            self.peak_wpm_at = elapsed;
+        }
+    }
+
+    /// How often each (expected, actual) substitution happened, from `error_summary`
+    pub fn confusion_matrix(&self) -> HashMap<(char, char), usize> {
+        let mut matrix = HashMap::new();
+        for &(expected, actual, _) in &self.error_summary {
+            *matrix.entry((expected, actual)).or_insert(0) += 1;
+        }
+        matrix
+    }
+
+    /// The `n` most frequent substitutions, most frequent first (ties in key order)
+    pub fn top_confusions(&self, n: usize) -> Vec<((char, char), usize)> {
+        let mut confusions: Vec<_> = self.confusion_matrix().into_iter().collect();
+        confusions.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        confusions.truncate(n);
+        confusions
+    }
+
+    /// The headline numbers as a `MetricsSnapshot`, with `position` characters of
+    /// `quote_text` typed (see `TypingSession::snapshot`)
+    pub fn snapshot(&self, position: usize) -> MetricsSnapshot {
+        let quote_len = self.quote_text.graphemes(true).count();
+        MetricsSnapshot {
+            wpm: self.wpm,
+            accuracy: self.accuracy,
+            keystrokes: self.keystrokes,
+            errors: self.keystrokes.saturating_sub(self.correct_keystrokes),
+            progress: if quote_len > 0 { (position as f64 / quote_len as f64).min(1.0) } else { 0.0 },
+            peak_wpm: self.peak_wpm,
+        }
+    }
+
+    /// Every mistake in `errors`, in the order made, with the word and stretch of
+    /// `quote_text` it was made in
+    pub fn mistake_report(&self) -> Vec<MistakeEntry> {
+        let graphemes: Vec<&str> = self.quote_text.graphemes(true).collect();
+        let is_space = |g: &&str| g.chars().all(char::is_whitespace);
+        self.errors
+            .iter()
+            .map(|error| {
+                let at = error.position.min(graphemes.len());
+                let word_start = graphemes[..at].iter().rposition(is_space).map_or(0, |i| i + 1);
+                let word_end = graphemes[at..].iter().position(is_space).map_or(graphemes.len(), |i| at + i);
+                let context_start = at.saturating_sub(MISTAKE_CONTEXT_CHARS);
+                let context_end = (at + MISTAKE_CONTEXT_CHARS + 1).min(graphemes.len());
+                MistakeEntry {
+                    position: error.position,
+                    expected: error.expected,
+                    actual: error.actual,
+                    expected_word: graphemes[word_start..word_end].concat(),
+                    context: graphemes[context_start..context_end].concat(),
+                }
+            })
+            .collect()
+    }
+
+    /// Recompute WPM and accuracy from the totals, returning the elapsed seconds
+    fn recompute_rates(&mut self) -> f64 {
         let elapsed = self.current_time.duration_since(self.start_time).as_secs_f64();
         let minutes = elapsed / 60.0;
-        let words = self.correct_keystrokes as f64 / 5.0;
+        let words = self.wpm_mode.words(self.correct_keystrokes, self.correct_spaces);
         self.wpm = if minutes > 0.0 { words / minutes } else { 0.0 };
         self.accuracy = if self.keystrokes > 0 {
             (self.correct_keystrokes as f64 / self.keystrokes as f64) * 100.0
         } else {
             0.0
         };
-        self.wpm_histogram.add_value(self.wpm);
+        elapsed
+    }
+
+    /// Fold another session's metrics into this one, e.g. to total a multi-quote test.
+    /// Counts and timings are summed, and WPM/accuracy are recomputed from the totals.
+    pub fn merge(&mut self, other: &TypingMetrics) {
+        let own_elapsed = self.current_time.duration_since(self.start_time);
+        let other_elapsed = other.current_time.duration_since(other.start_time);
+
+        self.keystrokes += other.keystrokes;
+        self.correct_keystrokes += other.correct_keystrokes;
+        self.correct_spaces += other.correct_spaces;
+        self.errors.extend(other.errors.iter().cloned());
+        self.error_summary.extend(other.error_summary.iter().copied());
+
+        for (key, timings) in &other.key_timings {
+            self.key_timings.entry(*key).or_default().extend(timings.iter().copied());
+            for timing in timings {
+                self.key_histogram.add_value(*timing);
+            }
+        }
+        for (key, count) in &other.key_errors {
+            *self.key_errors.entry(*key).or_insert(0) += count;
+        }
+        for (finger, count) in &other.finger_counts {
+            *self.finger_counts.entry(*finger).or_insert(0) += count;
+        }
+        for (key, theirs) in &other.char_metrics {
+            let ours = self.char_metrics.entry(*key).or_insert_with(|| CharacterMetrics::new(theirs.row, theirs.finger));
+            ours.count += theirs.count;
+            ours.errors += theirs.errors;
+            ours.total_time_ms += theirs.total_time_ms;
+            ours.avg_time_ms = if ours.count > 0 { ours.total_time_ms / ours.count as f64 } else { 0.0 };
+        }
+
+        // The other session's samples continue on from where this one ended
+        let offset = own_elapsed.as_secs_f64();
+        if other.peak_wpm > self.peak_wpm {
+            self.peak_wpm = other.peak_wpm;
+            self.peak_wpm_at = other.peak_wpm_at + offset;
+        }
+        self.wpm_samples.extend(other.wpm_samples.iter().map(|(t, wpm)| (t + offset, *wpm)));
+
+        // Stretch the time span to cover both sessions
+        let total = own_elapsed + other_elapsed;
+        self.start_time = self.current_time.checked_sub(total).unwrap_or(self.start_time);
+        self.recompute_rates();
+    }
+
+    /// Render the WPM samples as a sparkline of exactly `width` block characters
+    pub fn render_sparkline(&self, width: usize) -> String {
+        const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+        if self.wpm_samples.is_empty() {
+            return " ".repeat(width);
+        }
+
+        // Average the samples falling into each column
+        let count = self.wpm_samples.len();
+        let columns: Vec<f64> = (0..width)
+            .map(|col| {
+                let start = col * count / width;
+                let end = ((col + 1) * count / width).max(start + 1).min(count);
+                let bucket = &self.wpm_samples[start..end];
+                bucket.iter().map(|(_, wpm)| wpm).sum::<f64>() / bucket.len() as f64
+            })
+            .collect();
+
+        let min = columns.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = columns.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let range = max - min;
+
+        columns
+            .iter()
+            .map(|wpm| {
+                if range <= 0.0 {
+                    BLOCKS[0]
+                } else {
+                    let level = ((wpm - min) / range * (BLOCKS.len() - 1) as f64).round() as usize;
+                    BLOCKS[level.min(BLOCKS.len() - 1)]
+                }
+            })
+            .collect()
     }
 
     pub fn get_heat_map(&self) -> HashMap<char, f64> {
@@ -274,6 +752,40 @@ impl TypingMetrics {
         heat_map
     }
 
+    /// Coefficient of variation of all inter-keystroke intervals (standard deviation over mean).
+    /// Lower is steadier: touch typists keep an even rhythm, hunt-and-peck typing comes in bursts.
+    /// Returns 0.0 until there are at least two intervals.
+    pub fn rhythm_consistency(&self) -> f64 {
+        let intervals: Vec<f64> = self.key_timings.values().flatten().copied().collect();
+        if intervals.len() < 2 {
+            return 0.0;
+        }
+        let count = intervals.len() as f64;
+        let mean = intervals.iter().sum::<f64>() / count;
+        if mean <= 0.0 {
+            return 0.0;
+        }
+        let variance = intervals.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / count;
+        variance.sqrt() / mean
+    }
+
+    /// Per-key average timings and hit counts, sorted by key
+    pub fn heatmap_entries(&self) -> BTreeMap<char, HeatmapEntry> {
+        self.key_timings
+            .iter()
+            .filter(|(_, timings)| !timings.is_empty())
+            .map(|(key, timings)| {
+                let avg_ms = timings.iter().sum::<f64>() / timings.len() as f64;
+                (*key, HeatmapEntry { avg_ms, hits: Some(timings.len()) })
+            })
+            .collect()
+    }
+
+    /// Export the live heat map as JSON for external visualizations
+    pub fn heatmap_to_json(&self) -> String {
+        heatmap_entries_to_json(&self.heatmap_entries())
+    }
+
     pub fn get_key_geometric_averages(&self) -> HashMap<char, f64> {
         let mut averages = HashMap::new();
         for (key, timings) in &self.key_timings {
@@ -286,28 +798,112 @@ impl TypingMetrics {
         averages
     }
 
+    /// Per-key change in geometric average timing against a baseline session, in
+    /// milliseconds (negative = faster). Only keys typed in both sessions are included.
+    pub fn diff_heatmap(&self, baseline: &TypingMetrics) -> HashMap<char, f64> {
+        diff_key_averages(&self.get_key_geometric_averages(), &baseline.get_key_geometric_averages())
+    }
+
+    /// Fraction of keystrokes typed by each finger (sums to 1.0 when anything was typed)
+    pub fn finger_load(&self) -> HashMap<Finger, f64> {
+        let total: usize = self.finger_counts.values().sum();
+        if total == 0 {
+            return HashMap::new();
+        }
+        self.finger_counts
+            .iter()
+            .map(|(finger, count)| (*finger, *count as f64 / total as f64))
+            .collect()
+    }
+
+    /// Keystroke counts and timings for one keyboard row
+    pub fn row_metrics(&self, row: KeyboardRow) -> &CategoryMetrics {
+        match row {
+            KeyboardRow::Number => &self.number_metrics,
+            KeyboardRow::Top => &self.top_row_metrics,
+            KeyboardRow::Home => &self.home_row_metrics,
+            KeyboardRow::Bottom => &self.bottom_row_metrics,
+        }
+    }
+
+    fn row_metrics_mut(&mut self, row: KeyboardRow) -> &mut CategoryMetrics {
+        match row {
+            KeyboardRow::Number => &mut self.number_metrics,
+            KeyboardRow::Top => &mut self.top_row_metrics,
+            KeyboardRow::Home => &mut self.home_row_metrics,
+            KeyboardRow::Bottom => &mut self.bottom_row_metrics,
+        }
+    }
+
+    /// Speed (WPM from the average correct-key interval) and accuracy for each row typed so far
+    pub fn row_summary(&self) -> HashMap<KeyboardRow, (f64, f64)> {
+        KeyboardRow::ALL
+            .iter()
+            .map(|&row| (row, self.row_metrics(row)))
+            .filter(|(_, metrics)| metrics.count > 0)
+            .map(|(row, metrics)| {
+                let wpm = if metrics.avg_time_ms > 0.0 { 60_000.0 / metrics.avg_time_ms / 5.0 } else { 0.0 };
+                (row, (wpm, metrics.accuracy()))
+            })
+            .collect()
+    }
+
     pub fn finger_performance(&self) -> &HashMap<Finger, ExtendedStats> {
         &self.finger_stats
     }
 
+    /// Fill in demo timings for the heatmap, seeded with `DEMO_SEED` so every run looks the same
     pub fn simulate_demo_data(&mut self) {
-        // Simulate some typing data for visualization
-        for finger in self.finger_stats.values_mut() {
-            finger.current = 180.0;
-            finger.avg_10s = 200.0;
-            finger.avg_60s = 220.0;
-            finger.fastest = 150.0;
-            finger.slowest = 250.0;
+        self.simulate_demo_data_seeded(DEMO_SEED);
+    }
+
+    /// Fill in varied demo timings: the home row is fastest, reaches to other rows and the
+    /// weaker fingers are slower, capitals pay for the shift, and `seed` adds a little jitter.
+    pub fn simulate_demo_data_seeded(&mut self, seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        for (finger, stats) in self.finger_stats.iter_mut() {
+            let base = 150.0 + demo_finger_penalty_ms(*finger);
+            stats.current = base + rng.gen_range(-10.0..10.0);
+            stats.avg_10s = base + 10.0;
+            stats.avg_60s = base + 20.0;
+            stats.fastest = base - 30.0;
+            stats.slowest = base + 70.0;
         }
 
-        // Simulate some key timings
         for c in ('a'..='z').chain('A'..='Z').chain('0'..='9') {
-            self.key_timings.insert(c, vec![180.0, 200.0, 220.0]);
+            let row_ms = match KeyboardRow::for_key(c) {
+                Some(KeyboardRow::Home) => 150.0,
+                Some(KeyboardRow::Top) => 185.0,
+                Some(KeyboardRow::Bottom) => 205.0,
+                Some(KeyboardRow::Number) | None => 240.0,
+            };
+            let finger_ms = Finger::for_key(c).map_or(0.0, demo_finger_penalty_ms);
+            let shift_ms = if c.is_ascii_uppercase() { 35.0 } else { 0.0 };
+            let base = row_ms + finger_ms + shift_ms;
+            let timings = (0..3).map(|_| base + rng.gen_range(-15.0..15.0)).collect();
+            self.key_timings.insert(c, timings);
         }
     }
 }
 
+/// Seed used by `TypingMetrics::simulate_demo_data`
+pub const DEMO_SEED: u64 = 0x5EED_CAFE;
+
+/// Extra milliseconds a finger takes over the index fingers in the demo data
+fn demo_finger_penalty_ms(finger: Finger) -> f64 {
+    match finger {
+        Finger::LeftIndex | Finger::RightIndex | Finger::LeftThumb | Finger::RightThumb => 0.0,
+        Finger::LeftMiddle | Finger::RightMiddle => 10.0,
+        Finger::LeftRing | Finger::RightRing => 30.0,
+        Finger::LeftPinky | Finger::RightPinky => 55.0,
+    }
+}
+
 impl ExtendedStats {
+    /// Most samples kept for the windowed averages; older ones drop off first
+    pub const MAX_SAMPLES: usize = 512;
+
     pub fn new() -> Self {
         Self {
             current: 0.0,
@@ -315,25 +911,64 @@ impl ExtendedStats {
             avg_60s: 0.0,
             fastest: f64::INFINITY,
             slowest: 0.0,
+            mean: 0.0,
+            count: 0,
+            recent_mean: 0.0,
+            recent_weight: 0.0,
+            recent_session: 0,
+            samples: VecDeque::new(),
         }
     }
 
-    pub fn update(&mut self, value: f64, _now: Instant) {
+    /// Record `value` as seen at `now` and recompute the 10s and 60s windowed averages
+    pub fn update(&mut self, value: f64, now: Instant) {
         self.current = value;
         self.fastest = self.fastest.min(value);
         self.slowest = self.slowest.max(value);
-        
-        // Simple moving averages
-        if self.avg_10s == 0.0 {
-            self.avg_10s = value;
+        self.count += 1;
+        self.mean += (value - self.mean) / self.count as f64;
+
+        if self.samples.len() == Self::MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((now, value));
+        while self.samples.front().is_some_and(|(at, _)| now.saturating_duration_since(*at) > Duration::from_secs(60)) {
+            self.samples.pop_front();
+        }
+
+        self.avg_10s = self.window_average(now, Duration::from_secs(10));
+        self.avg_60s = self.window_average(now, Duration::from_secs(60));
+    }
+
+    /// Like `update`, and also fold `value`, recorded in `session`, into `recent_mean`. Earlier
+    /// values keep `decay` (0-1) of their weight for every session since theirs, so values
+    /// from the same session count equally and newer sessions count for more.
+    pub fn update_aged(&mut self, value: f64, now: Instant, session: u64, decay: f64) {
+        if self.recent_weight == 0.0 && self.count > 0 {
+            // Values recorded before decay was tracked carry over at full weight
+            self.recent_mean = self.mean;
+            self.recent_weight = self.count as f64;
         } else {
-            self.avg_10s = 0.9 * self.avg_10s + 0.1 * value;
+            let age = session.saturating_sub(self.recent_session).min(i32::MAX as u64) as i32;
+            self.recent_weight *= decay.clamp(0.0, 1.0).powi(age);
         }
-        
-        if self.avg_60s == 0.0 {
-            self.avg_60s = value;
+        self.update(value, now);
+        self.recent_session = self.recent_session.max(session);
+        self.recent_weight += 1.0;
+        self.recent_mean += (value - self.recent_mean) / self.recent_weight;
+    }
+
+    /// Mean of the samples no older than `window` at `now`
+    fn window_average(&self, now: Instant, window: Duration) -> f64 {
+        let recent: Vec<f64> = self.samples
+            .iter()
+            .filter(|(at, _)| now.saturating_duration_since(*at) <= window)
+            .map(|(_, value)| *value)
+            .collect();
+        if recent.is_empty() {
+            0.0
         } else {
-            self.avg_60s = 0.98 * self.avg_60s + 0.02 * value;
+            recent.iter().sum::<f64>() / recent.len() as f64
         }
     }
 }
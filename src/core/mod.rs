@@ -1,20 +1,47 @@
 use std::time::{SystemTime, Instant};
 use serde::{Serialize, Deserialize};
+use unicode_segmentation::UnicodeSegmentation;
 
 pub mod state;
 pub mod metrics;
 pub mod histogram;
 pub mod stats;
+pub mod similarity;
+pub mod goals;
+pub mod scoring;
 
-use metrics::TypingMetrics;
+use metrics::{MetricsSnapshot, TypingMetrics};
 
 #[derive(Debug, Clone)]
 pub struct TypingSession {
     pub start_time: Instant,
     pub metrics: TypingMetrics,
+    /// Metrics of the current quote alone; `metrics` keeps counting across every quote loaded
+    pub quote_metrics: TypingMetrics,
     pub quote_text: String,
+    /// Grapheme clusters of the quote typed correctly so far, so an accented letter or
+    /// a multi-codepoint emoji counts as one position
     pub current_position: usize,
+    /// Chars typed so far of the grapheme at `current_position`, when it takes several
+    pub grapheme_progress: usize,
     pub is_complete: bool,
+    /// Points for the current quote, see `scoring`
+    pub score: u64,
+    /// Consecutive correct keystrokes; any error resets it
+    pub combo: usize,
+    /// Graphemes of the quote that have already earned points, so text typed again after
+    /// erasing it or a `reset` earns nothing
+    scored_through: usize,
+    /// Backspaces counted against the session by the backspace penalty
+    pub backspace_penalties: usize,
+    /// Word being typed in word-by-word mode, as an index into `words`
+    pub word_index: usize,
+    /// When the current word came up, for its WPM
+    pub word_started: Instant,
+    /// WPM of each word of this quote completed in word-by-word mode
+    pub word_wpms: Vec<f64>,
+    /// Char index where the sentence being typed in sentence mode begins
+    pub sentence_start: usize,
 }
 
 impl Serialize for TypingSession {
@@ -73,12 +100,24 @@ impl<'de> Deserialize<'de> for TypingSession {
                     }
                 }
 
+                let metrics: TypingMetrics = metrics.ok_or_else(|| serde::de::Error::missing_field("metrics"))?;
                 Ok(TypingSession {
                     start_time: Instant::now(),
-                    metrics: metrics.ok_or_else(|| serde::de::Error::missing_field("metrics"))?,
+                    // Per-quote metrics aren't saved, so the session's stand in for them
+                    quote_metrics: metrics.clone(),
+                    metrics,
                     quote_text: quote_text.ok_or_else(|| serde::de::Error::missing_field("quote_text"))?,
                     current_position: current_position.ok_or_else(|| serde::de::Error::missing_field("current_position"))?,
+                    grapheme_progress: 0,
                     is_complete: is_complete.ok_or_else(|| serde::de::Error::missing_field("is_complete"))?,
+                    score: 0,
+                    combo: 0,
+                    scored_through: 0,
+                    backspace_penalties: 0,
+                    word_index: 0,
+                    word_started: Instant::now(),
+                    word_wpms: Vec::new(),
+                    sentence_start: 0,
                 })
             }
         }
@@ -88,6 +127,32 @@ impl<'de> Deserialize<'de> for TypingSession {
     }
 }
 
+/// Index of the grapheme cluster of `text` that char `char_index` falls in, or the
+/// number of graphemes if it's past the end
+fn grapheme_at_char(text: &str, char_index: usize) -> usize {
+    let mut chars = 0;
+    text.graphemes(true)
+        .position(|grapheme| {
+            chars += grapheme.chars().count();
+            chars > char_index
+        })
+        .unwrap_or_else(|| text.graphemes(true).count())
+}
+
+/// Char index where the `index`th whitespace-separated word of `text` begins
+fn word_start(text: &str, index: usize) -> Option<usize> {
+    let mut previous_is_space = true;
+    text.chars()
+        .enumerate()
+        .filter(|&(_, c)| {
+            let starts_word = previous_is_space && !c.is_whitespace();
+            previous_is_space = c.is_whitespace();
+            starts_word
+        })
+        .map(|(i, _)| i)
+        .nth(index)
+}
+
 #[derive(Debug, Clone)]
 pub struct GameState {
     pub last_update: SystemTime,
@@ -238,39 +303,138 @@ impl<'de> Deserialize<'de> for TypingError {
 
 impl TypingSession {
     pub fn new(text: String) -> Self {
-        // TODO: investigate quote initialization flow
-        let first_sentence_end = find_next_sentence_end(&text, 0);
+        let mut metrics = TypingMetrics::new();
+        metrics.quote_text = text.clone();
         Self {
-            quote_text: text.clone(),
+            quote_text: text,
             start_time: Instant::now(),
-            metrics: TypingMetrics::new(),
+            quote_metrics: metrics.clone(),
+            metrics,
             current_position: 0,
+            grapheme_progress: 0,
             is_complete: false,
+            score: 0,
+            combo: 0,
+            scored_through: 0,
+            backspace_penalties: 0,
+            word_index: 0,
+            word_started: Instant::now(),
+            word_wpms: Vec::new(),
+            sentence_start: 0,
         }
     }
 
     pub fn load_new_quote(&mut self, text: String) {
-        // Update text and keep existing metrics
+        // Update text and keep existing metrics; the pacer and quote metrics restart with each quote
+        self.metrics.quote_text = text.clone();
+        self.quote_metrics = TypingMetrics::new();
+        self.quote_metrics.quote_text = text.clone();
+        self.quote_metrics.wpm_mode = self.metrics.wpm_mode;
         self.quote_text = text;
         self.current_position = 0;
+        self.grapheme_progress = 0;
         self.is_complete = false;
+        self.start_time = Instant::now();
+        self.score = 0;
+        self.combo = 0;
+        self.scored_through = 0;
+        self.word_index = 0;
+        self.word_started = Instant::now();
+        self.word_wpms.clear();
+        self.sentence_start = 0;
+    }
+
+    /// Live metrics with progress through the quote taken from `current_position`
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        self.metrics.snapshot(self.current_position)
+    }
+
+    /// The quote split on whitespace, as word-by-word mode presents it
+    pub fn words(&self) -> Vec<&str> {
+        self.quote_text.split_whitespace().collect()
+    }
+
+    /// Word waiting to be typed in word-by-word mode, or `None` once every word is done
+    pub fn current_word(&self) -> Option<&str> {
+        self.quote_text.split_whitespace().nth(self.word_index)
+    }
+
+    /// Check a word typed in word-by-word mode. A match records the word's WPM and moves
+    /// on to the next word; anything else leaves the session where it was.
+    pub fn submit_word(&mut self, typed: &str) -> bool {
+        let Some(word) = self.current_word() else {
+            return false;
+        };
+        if typed != word {
+            self.combo = 0;
+            return false;
+        }
+
+        let minutes = self.word_started.elapsed().as_secs_f64() / 60.0;
+        let words = word.chars().count() as f64 / 5.0;
+        self.word_wpms.push(if minutes > 0.0 { words / minutes } else { 0.0 });
+        self.word_index += 1;
+        self.word_started = Instant::now();
+
+        // Keystrokes are checked against the quote, so skip to where the next word starts
+        let next_word = word_start(&self.quote_text, self.word_index).unwrap_or_else(|| self.quote_text.chars().count());
+        self.current_position = grapheme_at_char(&self.quote_text, next_word);
+        self.grapheme_progress = 0;
+        true
+    }
+
+    /// How many characters of the quote a typist at `goal_wpm` would have typed by now,
+    /// counting five characters per word from when the quote was loaded
+    pub fn pacer_position(&self, goal_wpm: f64) -> usize {
+        let minutes = self.start_time.elapsed().as_secs_f64() / 60.0;
+        let chars = (goal_wpm.max(0.0) * 5.0 * minutes) as usize;
+        chars.min(self.quote_text.chars().count())
     }
     
     pub fn record_keystroke(&mut self, c: char) {
         // TODO: investigate quote completion detection and validation
-        let expected_char = self.quote_text.chars().nth(self.current_position).unwrap_or(' ');
+        let total = self.quote_text.graphemes(true).count();
+        let expected_grapheme = self.quote_text.graphemes(true).nth(self.current_position).unwrap_or(" ");
+        let expected_char = expected_grapheme.chars().nth(self.grapheme_progress).unwrap_or(' ');
         self.metrics.record_keystroke(c, expected_char, self.current_position);
-        
-        // Increment position if the character matches and we're not past the end
-        if c == expected_char && self.current_position < self.quote_text.len() {
-            self.current_position += 1;
-            
-            // Check if quote is complete
-            if self.current_position == self.quote_text.len() {
-                println!("🎉 Quote completed! Moving to next quote...");
-                self.is_complete = true;
-            }
+        self.quote_metrics.record_keystroke(c, expected_char, self.current_position);
+
+        if c != expected_char {
+            self.combo = 0;
+            return;
         }
+        // Nothing is left to type past the end
+        if self.current_position >= total {
+            return;
+        }
+        self.combo += 1;
+
+        // Advance once every char of the expected grapheme is typed
+        self.grapheme_progress += 1;
+        if self.grapheme_progress < expected_grapheme.chars().count() {
+            return;
+        }
+        self.grapheme_progress = 0;
+        self.current_position += 1;
+
+        // Streaks of correct keys score at a growing multiplier, but only on text that
+        // hasn't scored yet
+        if self.current_position > self.scored_through {
+            self.scored_through = self.current_position;
+            self.score += scoring::POINTS_PER_KEY * scoring::combo_multiplier(self.combo);
+        }
+
+        // Check if quote is complete
+        if self.current_position == total {
+            println!("🎉 Quote completed! Moving to next quote...");
+            self.is_complete = true;
+        }
+    }
+
+    /// Count `count` penalized backspaces and take their points off the score
+    pub fn penalize_backspaces(&mut self, count: usize) {
+        self.backspace_penalties += count;
+        self.score = self.score.saturating_sub(scoring::BACKSPACE_PENALTY_POINTS * count as u64);
     }
 
     pub fn get_averages(&self) -> (f64, f64) {
@@ -284,19 +448,39 @@ impl TypingSession {
     pub fn reset(&mut self) {
         // Only reset position and completion status, keep metrics
         self.current_position = 0;
+        self.grapheme_progress = 0;
         self.is_complete = false;
     }
 
     pub fn calculate_metrics(&mut self) {
         self.metrics.current_time = Instant::now();
         self.metrics.calculate_overall_metrics();
+        self.quote_metrics.current_time = self.metrics.current_time;
+        self.quote_metrics.calculate_overall_metrics();
+    }
+
+    /// Sentence waiting to be typed in sentence mode, or `None` once the quote is done
+    pub fn current_sentence(&self) -> Option<&str> {
+        let total = self.quote_text.chars().count();
+        if self.sentence_start >= total {
+            return None;
+        }
+        let end = find_next_sentence_end(&self.quote_text, self.sentence_start);
+        let byte_at = |index: usize| self.quote_text.char_indices().nth(index).map_or(self.quote_text.len(), |(i, _)| i);
+        Some(&self.quote_text[byte_at(self.sentence_start)..byte_at(end)])
     }
 
-    pub fn advance_sentence(&mut self) {
-        if self.current_position < self.quote_text.len() {
-            let next_end = find_next_sentence_end(&self.quote_text, self.current_position);
-            self.current_position = next_end;
+    /// Move on to the sentence after the current one in sentence mode. With `skip_spaces`
+    /// the whitespace between sentences is skipped rather than typed as part of the next.
+    pub fn advance_sentence(&mut self, skip_spaces: bool) {
+        let total = self.quote_text.chars().count();
+        let mut next = find_next_sentence_end(&self.quote_text, self.sentence_start);
+        if skip_spaces {
+            next += self.quote_text.chars().skip(next).take_while(|c| c.is_whitespace()).count();
         }
+        self.sentence_start = next.min(total);
+        self.current_position = grapheme_at_char(&self.quote_text, self.sentence_start);
+        self.grapheme_progress = 0;
     }
 
     pub fn process_input(&mut self, input: char) -> Result<(), String> {
@@ -305,29 +489,63 @@ impl TypingSession {
     }
 }
 
-fn find_next_sentence_end(text: &str, start: usize) -> usize {
+/// Words ending in '.' that don't end a sentence
+const ABBREVIATIONS: [&str; 16] = [
+    "mr", "mrs", "ms", "dr", "prof", "sr", "jr", "st", "vs", "etc", "e.g", "i.e", "no", "mt", "ft", "approx",
+];
+
+/// Char index just past the sentence that starts at char `start`, including its closing
+/// quotes and brackets, or the length of `text` if it never ends. A run of `.`, `!`, `?`
+/// or `…` ends a sentence only outside a quotation and when it's followed by whitespace
+/// and then anything but a lowercase letter, so ellipses mid-thought ("wait... what"),
+/// abbreviations and initials don't split it.
+pub fn find_next_sentence_end(text: &str, start: usize) -> usize {
+    let chars: Vec<char> = text.chars().collect();
+    let is_terminator = |c: char| matches!(c, '.' | '!' | '?' | '…');
     let mut in_quote = false;
-    let mut last_char = None;
+    let mut i = start;
 
-    for (i, c) in text[start..].chars().enumerate() {
+    while i < chars.len() {
+        let c = chars[i];
         match c {
-            '"' | '"' | '"' => in_quote = !in_quote,
-            '.' | '!' | '?' => {
-                // Check if this is part of an ellipsis
-                if c == '.' && last_char == Some('.') {
-                    last_char = Some(c);
-                    continue;
+            '"' => in_quote = !in_quote,
+            '\u{201C}' => in_quote = true,
+            '\u{201D}' => in_quote = false,
+            _ if is_terminator(c) => {
+                let run_start = i;
+                while i + 1 < chars.len() && is_terminator(chars[i + 1]) {
+                    i += 1;
                 }
-                // If we're not in a quote and this is a sentence end, return position
-                if !in_quote {
-                    return start + i + 1;
+                let single_dot = c == '.' && i == run_start;
+                // Closing quotes and brackets stay with the sentence they close
+                while i + 1 < chars.len() && matches!(chars[i + 1], '"' | '\u{201D}' | '\'' | '\u{2019}' | ')' | ']') {
+                    i += 1;
+                    match chars[i] {
+                        '"' => in_quote = !in_quote,
+                        '\u{201D}' => in_quote = false,
+                        _ => {}
+                    }
+                }
+                let end = i + 1;
+                let next = chars[end..].iter().find(|c| !c.is_whitespace());
+                let followed_by_space = chars.get(end).is_none_or(|c| c.is_whitespace());
+                let abbreviation = single_dot && is_abbreviation(&chars[..run_start]);
+                if !in_quote && followed_by_space && !abbreviation && next.is_none_or(|c| !c.is_lowercase()) {
+                    return end;
                 }
             }
             _ => {}
         }
-        last_char = Some(c);
+        i += 1;
     }
-    
-    // If no sentence end found, return end of text
-    text.len()
-} 
\ No newline at end of file
+
+    chars.len()
+}
+
+/// Whether the word just before a '.' at the end of `before` is an abbreviation or an initial
+fn is_abbreviation(before: &[char]) -> bool {
+    let word_start = before.iter().rposition(|c| c.is_whitespace() || *c == '(').map_or(0, |i| i + 1);
+    let word: String = before[word_start..].iter().collect::<String>().to_lowercase();
+    let is_initial = word.chars().count() == 1 && word.chars().all(|c| c.is_alphabetic());
+    is_initial || ABBREVIATIONS.contains(&word.as_str())
+}
@@ -0,0 +1,23 @@
+/// Points for each correct keystroke before the combo multiplier
+pub const POINTS_PER_KEY: u64 = 10;
+/// Consecutive correct keystrokes needed to raise the multiplier by one
+pub const COMBO_STEP: usize = 10;
+/// The multiplier stops growing here however long the streak gets
+pub const MAX_COMBO_MULTIPLIER: u64 = 8;
+
+/// Points taken off for each backspace when the backspace penalty is on
+pub const BACKSPACE_PENALTY_POINTS: u64 = 50;
+
+/// Score multiplier for a streak of `combo` correct keystrokes: x1, then +1 every `COMBO_STEP`
+pub fn combo_multiplier(combo: usize) -> u64 {
+    (1 + (combo / COMBO_STEP) as u64).min(MAX_COMBO_MULTIPLIER)
+}
+
+/// Quotes are grouped by length so high scores compare like with like
+pub fn length_bucket(quote_len: usize) -> &'static str {
+    match quote_len {
+        0..=49 => "short",
+        50..=149 => "medium",
+        _ => "long",
+    }
+}
@@ -0,0 +1,97 @@
+use std::fs;
+use std::path::Path;
+use log::warn;
+
+/// Largest edit distance at which two words count as similar
+pub const MAX_SIMILAR_DISTANCE: usize = 2;
+
+/// Built-in groups of similar-looking words, used when no word list is available
+pub const SIMILARITY_GROUPS: &[&[&str]] = &[
+    &["doggy", "foggy", "boggy", "soggy"],
+    &["tappy", "happy", "nappy", "sappy"],
+    &["flippy", "floppy", "sloppy", "choppy"],
+    &["twerpy", "twirly", "twisty"],
+];
+
+/// Number of single-character insertions, deletions or substitutions between two words
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            current[j + 1] = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Cluster words so that every word is within `max_distance` edits of another word in its group
+pub fn group_by_edit_distance(words: &[String], max_distance: usize) -> Vec<Vec<String>> {
+    let mut groups: Vec<Vec<String>> = Vec::new();
+
+    for word in words {
+        // A word can bridge several existing groups, so merge all of them
+        let (related, mut rest): (Vec<_>, Vec<_>) = groups.into_iter().partition(|group| {
+            group.iter().any(|member| levenshtein(member, word) <= max_distance)
+        });
+
+        let mut merged: Vec<String> = related.into_iter().flatten().collect();
+        merged.push(word.clone());
+        rest.push(merged);
+        groups = rest;
+    }
+
+    groups
+}
+
+/// The built-in similarity groups as owned strings
+pub fn default_groups() -> Vec<Vec<String>> {
+    SIMILARITY_GROUPS
+        .iter()
+        .map(|group| group.iter().map(|w| w.to_string()).collect())
+        .collect()
+}
+
+/// Build similarity groups from a newline-separated word list, dropping words with no
+/// similar partner. Falls back to the built-in groups if the file can't be read or
+/// yields no groups.
+pub fn load_word_groups<P: AsRef<Path>>(path: P) -> Vec<Vec<String>> {
+    let path = path.as_ref();
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            warn!("Failed to read word list {}: {}, using built-in groups", path.display(), e);
+            return default_groups();
+        }
+    };
+
+    let mut words: Vec<String> = content
+        .lines()
+        .map(|line| line.trim().to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect();
+    words.sort();
+    words.dedup();
+
+    let groups: Vec<Vec<String>> = group_by_edit_distance(&words, MAX_SIMILAR_DISTANCE)
+        .into_iter()
+        .filter(|group| group.len() > 1)
+        .collect();
+
+    if groups.is_empty() {
+        warn!("Word list {} has no similar words, using built-in groups", path.display());
+        default_groups()
+    } else {
+        groups
+    }
+}
@@ -1,7 +1,59 @@
+use std::fmt;
+use std::str::FromStr;
+use super::metrics::ParseNameError;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GameType {
     Practice,
     Consume,
+    /// Work through the lesson curriculum
+    Learn,
+    /// Type the quote one word at a time, pressing Enter after each word
+    WordByWord,
+    /// Type long passages one sentence at a time, moving on as each sentence is finished
+    SentenceBySentence,
+    /// Type short quotes from memory, with ones typed poorly coming back sooner
+    Memorize,
+}
+
+impl GameType {
+    /// Every mode, in the order `--help` lists their commands
+    pub const ALL: [GameType; 6] = [
+        GameType::Practice,
+        GameType::WordByWord,
+        GameType::SentenceBySentence,
+        GameType::Memorize,
+        GameType::Consume,
+        GameType::Learn,
+    ];
+
+    /// Command that starts this mode, e.g. `spring-keys words`
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameType::Practice => "practice",
+            GameType::Consume => "consume",
+            GameType::Learn => "learn",
+            GameType::WordByWord => "words",
+            GameType::SentenceBySentence => "sentences",
+            GameType::Memorize => "memorize",
+        }
+    }
+}
+
+impl fmt::Display for GameType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for GameType {
+    type Err = ParseNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter()
+            .find(|game| game.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseNameError { kind: "game mode", name: s.to_string() })
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
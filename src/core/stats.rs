@@ -1,60 +1,291 @@
 use serde::{Serialize, Deserialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
-use log::info;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use chrono::Timelike;
+use log::{info, warn};
+use super::metrics::{heatmap_entries_to_json, ExtendedStats, HeatmapEntry, WpmMode};
+use super::scoring;
+use crate::srs::SrsCard;
+
+/// File the accumulated stats are persisted to between runs
+pub const ACCUMULATED_STATS_FILE: &str = "stats/accumulated.json";
+/// Directory holding the accumulated stats and per-session files
+pub const STATS_DIR: &str = "stats";
+/// Start of every per-session file name in the stats directory, see `session_file_timestamp`
+pub const SESSION_FILE_PREFIX: &str = "session_";
+
+/// Version of the saved stats format written by this build.
+/// Version 1 files predate the `schema_version` field; version 2 files
+/// predate `total_correct_keystrokes`, and version 3 files the weighted WPM totals.
+pub const STATS_SCHEMA_VERSION: u32 = 4;
+
+/// Timestamp of a session file named `session_<unix timestamp>.json`, or `None` for
+/// any other file name
+pub fn session_file_timestamp(file_name: &str) -> Option<u64> {
+    let digits = file_name.strip_prefix(SESSION_FILE_PREFIX)?.strip_suffix(".json")?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+fn legacy_schema_version() -> u32 {
+    1
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccumulatedStats {
+    /// Save format version, see `STATS_SCHEMA_VERSION`
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
     pub total_quotes: usize,
     pub total_keystrokes: usize,
+    /// Keystrokes that matched the quote, for accuracy weighted by quote length
+    #[serde(default)]
+    pub total_correct_keystrokes: usize,
     pub total_errors: usize,
     pub session_errors: usize,  // Track errors for the current session
+    /// Keystroke-weighted mean WPM, `weighted_wpm_numerator / weighted_wpm_denominator`
     pub avg_wpm: f64,
     pub avg_accuracy: f64,
+    /// Sum of each quote's WPM times its keystrokes
+    #[serde(default)]
+    pub weighted_wpm_numerator: f64,
+    /// Sum of the keystrokes the WPM numerator is weighted by
+    #[serde(default)]
+    pub weighted_wpm_denominator: f64,
+    /// All-time per-key average timings (in milliseconds)
+    #[serde(default)]
+    pub key_averages: HashMap<char, ExtendedStats>,
+    /// Best quote score in each quote-length bucket, see `scoring::length_bucket`
+    #[serde(default)]
+    pub high_scores: HashMap<String, u64>,
+    /// Sum of quote WPMs completed in each local hour of the day, see `hourly_average`
+    #[serde(default)]
+    pub hourly_wpm: [f64; 24],
+    /// Quotes completed in each local hour of the day
+    #[serde(default)]
+    pub hourly_counts: [usize; 24],
+    /// Memorize mode review schedule by quote index, see `srs`
+    #[serde(default)]
+    pub srs_cards: BTreeMap<usize, SrsCard>,
+    /// `quote_hash` of every quote text completed, for `QuoteDatabase::next_unseen`
+    #[serde(default)]
+    pub completed_quotes: HashSet<String>,
+    /// Runs of the app that completed at least one quote, the age key timings decay by
+    #[serde(default)]
+    pub session_count: u64,
+    /// Whether this run has been counted in `session_count` yet
+    #[serde(skip)]
+    session_counted: bool,
+    /// Weight key timings keep for every later session, see `Preferences::key_decay`
+    #[serde(skip, default = "default_key_decay")]
+    pub key_decay: f64,
+}
+
+fn default_key_decay() -> f64 {
+    crate::config::DEFAULT_KEY_DECAY
 }
 
 impl AccumulatedStats {
     pub fn new() -> Self {
         Self {
+            schema_version: STATS_SCHEMA_VERSION,
             total_quotes: 0,
             total_keystrokes: 0,
+            total_correct_keystrokes: 0,
             total_errors: 0,
             session_errors: 0,
             avg_wpm: 0.0,
             avg_accuracy: 0.0,
+            weighted_wpm_numerator: 0.0,
+            weighted_wpm_denominator: 0.0,
+            key_averages: HashMap::new(),
+            high_scores: HashMap::new(),
+            hourly_wpm: [0.0; 24],
+            hourly_counts: [0; 24],
+            srs_cards: BTreeMap::new(),
+            completed_quotes: HashSet::new(),
+            session_count: 0,
+            session_counted: false,
+            key_decay: default_key_decay(),
         }
     }
 
     pub fn load_from_directory() -> Self {
+        Self::load_or_new(Path::new(ACCUMULATED_STATS_FILE))
+    }
+
+    /// Stats saved at `path`, or fresh ones if there are none yet or they can't be read.
+    /// The file's directory is created so session files and saves can go there.
+    pub fn load_or_new(path: &Path) -> Self {
         // Create a new instance with default values
         let mut stats = Self::new();
 
         // Create stats directory if it doesn't exist
-        let stats_dir = PathBuf::from("stats");
-        if !stats_dir.exists() {
-            if let Err(e) = fs::create_dir_all(&stats_dir) {
+        if let Some(stats_dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty() && !dir.exists()) {
+            if let Err(e) = fs::create_dir_all(stats_dir) {
                 info!("Failed to create stats directory: {}", e);
                 return stats;
             }
         }
 
-        // Return default stats if directory is empty
+        // Restore previously saved progress if there is any
+        if path.exists() {
+            match Self::load_from_file(path) {
+                Ok(loaded) => stats = loaded,
+                Err(e) => warn!("Skipping saved stats {}: {}", path.display(), e),
+            }
+        }
+
+        // Session errors only cover the current run
+        stats.session_errors = 0;
         stats
     }
 
+    /// Load stats saved by this or an older version. Files from a newer version
+    /// are rejected with `InvalidData` rather than misread.
+    pub fn load_from_file(path: &Path) -> io::Result<Self> {
+        let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidData, e);
+        let json = fs::read_to_string(path)?;
+        let value: serde_json::Value = serde_json::from_str(&json).map_err(|e| invalid(e.to_string()))?;
+
+        let version = value.get("schema_version").and_then(|v| v.as_u64()).unwrap_or(1);
+        if version > STATS_SCHEMA_VERSION as u64 {
+            return Err(invalid(format!(
+                "stats schema version {} is newer than supported version {}",
+                version, STATS_SCHEMA_VERSION
+            )));
+        }
+
+        // Older versions only lack fields that have defaults, so they load as-is
+        let mut stats: Self = serde_json::from_value(value).map_err(|e| invalid(e.to_string()))?;
+        if version < 3 {
+            // Estimate the correct keystrokes from the averaged accuracy, the best older files can do
+            stats.total_correct_keystrokes = (stats.total_keystrokes as f64 * stats.avg_accuracy / 100.0).round() as usize;
+        }
+        if version < 4 {
+            // Treat the old average as if it were already weighted over every keystroke
+            stats.weighted_wpm_denominator = stats.total_keystrokes as f64;
+            stats.weighted_wpm_numerator = stats.avg_wpm * stats.weighted_wpm_denominator;
+        }
+        stats.schema_version = STATS_SCHEMA_VERSION;
+        Ok(stats)
+    }
+
+    /// Write the stats as JSON, creating the parent directory if needed
+    pub fn save_to_file(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)
+    }
+
+    /// Keep at most `max` session files in the stats directory, see `prune_directory_at`
+    pub fn prune_directory(max: usize) -> io::Result<usize> {
+        Self::prune_directory_at(Path::new(STATS_DIR), max)
+    }
+
+    /// Delete the oldest session files in `dir` until at most `max` remain. Only files
+    /// named like `session_<unix timestamp>.json` count as session files (see
+    /// `session_file_timestamp`); the accumulated stats and anything else in the
+    /// directory are never touched. Returns how many files were deleted.
+    pub fn prune_directory_at(dir: &Path, max: usize) -> io::Result<usize> {
+        let mut sessions: Vec<(u64, PathBuf)> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_file())
+            .filter_map(|path| {
+                let timestamp = session_file_timestamp(path.file_name()?.to_str()?)?;
+                Some((timestamp, path))
+            })
+            .collect();
+        if sessions.len() <= max {
+            return Ok(0);
+        }
+
+        sessions.sort();
+        let excess = sessions.len() - max;
+        for (_, path) in &sessions[..excess] {
+            fs::remove_file(path)?;
+        }
+        info!("Pruned {} old session files from {}", excess, dir.display());
+        Ok(excess)
+    }
+
+    /// Headline all-time numbers for display or export. Unlike `avg_accuracy`, which
+    /// averages quotes equally, accuracy here is weighted by keystrokes.
+    pub fn summary(&self) -> StatsSummary {
+        let overall_accuracy = if self.total_keystrokes > 0 {
+            (self.total_correct_keystrokes as f64 / self.total_keystrokes as f64) * 100.0
+        } else {
+            0.0
+        };
+        StatsSummary {
+            total_quotes: self.total_quotes,
+            total_keystrokes: self.total_keystrokes,
+            overall_accuracy,
+            avg_wpm: self.avg_wpm,
+            total_errors: self.total_errors,
+        }
+    }
+
+    /// Keep `score` if it beats the high score for quotes of this length. Returns true on a new high score.
+    pub fn record_score(&mut self, quote_len: usize, score: u64) -> bool {
+        let best = self.high_scores.entry(scoring::length_bucket(quote_len).to_string()).or_insert(0);
+        if score > *best {
+            *best = score;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Best score recorded for quotes of this length, if any
+    pub fn high_score(&self, quote_len: usize) -> Option<u64> {
+        self.high_scores.get(scoring::length_bucket(quote_len)).copied()
+    }
+
+    /// Fold a completed session into the totals, filed under the current local hour
     pub fn update_from_session(&mut self, session: &crate::core::TypingSession) {
+        self.update_from_session_at(session, chrono::Local::now().hour());
+    }
+
+    /// Like `update_from_session`, for a session completed during `hour` (0-23)
+    pub fn update_from_session_at(&mut self, session: &crate::core::TypingSession, hour: u32) {
+        self.update_from_quote_at(session, QuoteTotals::of_session(session), hour);
+    }
+
+    /// Fold the quote just completed in `session` into the totals, counting only `quote`'s
+    /// share of a session that has typed several quotes
+    pub fn update_from_quote(&mut self, session: &crate::core::TypingSession, quote: QuoteTotals) {
+        self.update_from_quote_at(session, quote, chrono::Local::now().hour());
+    }
+
+    /// Like `update_from_quote`, for a quote completed during `hour` (0-23)
+    pub fn update_from_quote_at(&mut self, session: &crate::core::TypingSession, quote: QuoteTotals, hour: u32) {
+        if !self.session_counted {
+            self.session_count += 1;
+            self.session_counted = true;
+        }
         self.total_quotes += 1;
-        self.total_keystrokes += session.metrics.keystrokes;
-        
+        self.total_keystrokes += quote.keystrokes;
+        self.total_correct_keystrokes += quote.correct_keystrokes;
+
         // Don't update error counts here since we're tracking them in real-time
         // during input processing
 
-        // Update running averages
-        let (wpm, accuracy) = session.get_averages();
-        if self.avg_wpm == 0.0 {
-            self.avg_wpm = wpm;
-        } else {
-            self.avg_wpm = 0.95 * self.avg_wpm + 0.05 * wpm;
+        // Weight WPM by keystrokes so a long session outweighs a five-character one
+        let wpm = quote.wpm;
+        let accuracy = quote.accuracy();
+        let weight = quote.keystrokes as f64;
+        self.weighted_wpm_numerator += wpm * weight;
+        self.weighted_wpm_denominator += weight;
+        if self.weighted_wpm_denominator > 0.0 {
+            self.avg_wpm = self.weighted_wpm_numerator / self.weighted_wpm_denominator;
         }
 
         if self.avg_accuracy == 0.0 {
@@ -62,5 +293,196 @@ impl AccumulatedStats {
         } else {
             self.avg_accuracy = 0.95 * self.avg_accuracy + 0.05 * accuracy;
         }
+
+        self.record_score(session.quote_text.chars().count(), session.score);
+        self.completed_quotes.insert(crate::quotes::quote_hash(&session.quote_text));
+        let hour = hour as usize % 24;
+        self.hourly_wpm[hour] += wpm;
+        self.hourly_counts[hour] += 1;
+
+        // Fold the session's per-key averages into the all-time key stats, older sessions decayed
+        let now = Instant::now();
+        for (key, avg_ms) in session.metrics.get_heat_map() {
+            self.key_averages
+                .entry(key)
+                .or_insert_with(ExtendedStats::new)
+                .update_aged(avg_ms, now, self.session_count, self.key_decay);
+        }
+    }
+
+    /// Count quotes completed from now on as a new session, so earlier key timings age by one.
+    /// Each run of the app starts a new session by itself.
+    pub fn begin_session(&mut self) {
+        self.session_counted = false;
+    }
+
+    /// Mean WPM of quotes completed during `hour` (0-23), or `None` if there were none
+    pub fn hourly_average(&self, hour: usize) -> Option<f64> {
+        let count = *self.hourly_counts.get(hour)?;
+        (count > 0).then(|| self.hourly_wpm[hour] / count as f64)
+    }
+
+    /// One bar per hour of the day with quotes, scaled so the fastest hour fills `width`
+    pub fn hourly_chart(&self, width: usize) -> Vec<String> {
+        let averages: Vec<(usize, f64)> = (0..24).filter_map(|hour| self.hourly_average(hour).map(|wpm| (hour, wpm))).collect();
+        let best = averages.iter().map(|(_, wpm)| *wpm).fold(0.0, f64::max);
+        averages
+            .into_iter()
+            .map(|(hour, wpm)| {
+                let bar = if best > 0.0 { (wpm / best * width as f64).round() as usize } else { 0 };
+                format!(
+                    "{:02}:00 {:<width$} {:.1} WPM ({} quotes)",
+                    hour,
+                    "█".repeat(bar),
+                    wpm,
+                    self.hourly_counts[hour],
+                    width = width
+                )
+            })
+            .collect()
+    }
+
+    /// Export the all-time per-key averages as JSON (hit counts aren't tracked across sessions)
+    pub fn heatmap_to_json(&self) -> String {
+        let entries: BTreeMap<char, HeatmapEntry> = self.key_averages
+            .iter()
+            .map(|(key, stats)| (*key, HeatmapEntry { avg_ms: all_time_average(stats), hits: None }))
+            .collect();
+        heatmap_entries_to_json(&entries)
+    }
+
+    /// WPM `text` would be typed at if every key took its all-time average time, for
+    /// setting realistic goals. Keys without data count at the average over all keys;
+    /// with no key data at all this is 0.
+    pub fn estimated_wpm_for(&self, text: &str) -> f64 {
+        if self.key_averages.is_empty() {
+            return 0.0;
+        }
+        let overall = self.key_averages.values().map(all_time_average).sum::<f64>() / self.key_averages.len() as f64;
+        let total_ms: f64 = text
+            .chars()
+            .map(|c| self.key_averages.get(&c).map_or(overall, all_time_average))
+            .sum();
+        if total_ms <= 0.0 {
+            return 0.0;
+        }
+        let words = text.chars().count() as f64 / 5.0;
+        words / (total_ms / 60_000.0)
+    }
+}
+
+/// All-time average of one key's timings, weighted toward recent sessions. Stats saved
+/// before decay was tracked use their plain mean, and ones older still their last 60s average.
+fn all_time_average(stats: &ExtendedStats) -> f64 {
+    if stats.recent_weight > 0.0 {
+        stats.recent_mean
+    } else if stats.count > 0 {
+        stats.mean
+    } else {
+        stats.avg_60s
+    }
+}
+
+/// Counts of one completed quote, when a session may have typed several before it
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct QuoteTotals {
+    pub keystrokes: usize,
+    pub correct_keystrokes: usize,
+    pub correct_spaces: usize,
+    pub errors: usize,
+    /// WPM over the time spent on this quote alone
+    pub wpm: f64,
+}
+
+impl QuoteTotals {
+    /// Totals of a session that has only typed the one quote
+    pub fn of_session(session: &crate::core::TypingSession) -> Self {
+        Self {
+            keystrokes: session.metrics.keystrokes,
+            correct_keystrokes: session.metrics.correct_keystrokes,
+            correct_spaces: session.metrics.correct_spaces,
+            errors: session.metrics.errors.len(),
+            wpm: session.get_averages().0,
+        }
+    }
+
+    /// Percentage of the quote's keystrokes that were correct
+    pub fn accuracy(&self) -> f64 {
+        if self.keystrokes > 0 {
+            self.correct_keystrokes as f64 / self.keystrokes as f64 * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// All-time totals from `AccumulatedStats::summary`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct StatsSummary {
+    pub total_quotes: usize,
+    pub total_keystrokes: usize,
+    /// Correct keystrokes as a percentage of all keystrokes
+    pub overall_accuracy: f64,
+    pub avg_wpm: f64,
+    pub total_errors: usize,
+}
+
+/// Running totals across every quote completed since the app started.
+/// Unlike `AccumulatedStats` this is not persisted, and WPM comes from the totals
+/// rather than a moving average.
+#[derive(Debug, Clone, Default)]
+pub struct SessionAggregate {
+    pub quotes_completed: usize,
+    pub keystrokes: usize,
+    pub correct_keystrokes: usize,
+    /// Correctly typed spaces, the word count under `WpmMode::ActualWords`
+    pub correct_spaces: usize,
+    pub errors: usize,
+    /// Time spent on completed quotes, from loading each quote to finishing it
+    pub typing_time: Duration,
+}
+
+impl SessionAggregate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add one completed quote's counts and duration
+    pub fn record_quote(&mut self, quote: QuoteTotals, duration: Duration) {
+        self.quotes_completed += 1;
+        self.keystrokes += quote.keystrokes;
+        self.correct_keystrokes += quote.correct_keystrokes;
+        self.correct_spaces += quote.correct_spaces;
+        self.errors += quote.errors;
+        self.typing_time += duration;
+    }
+
+    /// WPM over the totals, counting words the way `mode` does for a single quote
+    pub fn wpm(&self, mode: WpmMode) -> f64 {
+        let minutes = self.typing_time.as_secs_f64() / 60.0;
+        if minutes > 0.0 {
+            mode.words(self.correct_keystrokes, self.correct_spaces) / minutes
+        } else {
+            0.0
+        }
     }
-} 
\ No newline at end of file
+
+    pub fn accuracy(&self) -> f64 {
+        if self.keystrokes > 0 {
+            (self.correct_keystrokes as f64 / self.keystrokes as f64) * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Outcome of typing a block of text in one go, as returned by `SpringKeys::type_text`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SessionSummary {
+    pub wpm: f64,
+    pub accuracy: f64,
+    pub errors: usize,
+    pub keystrokes: usize,
+    /// Whether the text finished the quote it was typed against
+    pub completed: bool,
+}
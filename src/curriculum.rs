@@ -0,0 +1,40 @@
+//! Ordered typing lessons served by `spring-keys learn`, each one unlocking the next once passed
+
+/// One step of the curriculum: quotes from `category` until the pass thresholds are met
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LessonStage {
+    /// Name shown to the user
+    pub name: &'static str,
+    /// Quote category the lesson draws its exercises from (case-insensitive)
+    pub category: &'static str,
+    /// Minimum WPM to pass
+    pub min_wpm: f64,
+    /// Minimum accuracy percentage to pass
+    pub min_accuracy: f64,
+}
+
+impl LessonStage {
+    pub fn is_passed(&self, wpm: f64, accuracy: f64) -> bool {
+        wpm >= self.min_wpm && accuracy >= self.min_accuracy
+    }
+}
+
+/// Every lesson, easiest first
+pub fn curriculum() -> Vec<LessonStage> {
+    vec![
+        LessonStage { name: "Home row", category: "LessonsHomeRow", min_wpm: 15.0, min_accuracy: 90.0 },
+        LessonStage { name: "Alternating hands", category: "LessonsAlternateHands", min_wpm: 20.0, min_accuracy: 92.0 },
+        LessonStage { name: "Numbers", category: "LessonsNumbersBasic", min_wpm: 20.0, min_accuracy: 92.0 },
+        LessonStage { name: "Mastery", category: "Lessons", min_wpm: 30.0, min_accuracy: 95.0 },
+    ]
+}
+
+/// Stage to continue with after finishing an exercise at `stage`: the next one
+/// on a pass, otherwise the same one. Never moves past the end of the curriculum.
+pub fn advance(stage: usize, wpm: f64, accuracy: f64) -> usize {
+    let stages = curriculum();
+    match stages.get(stage) {
+        Some(lesson) if lesson.is_passed(wpm, accuracy) => (stage + 1).min(stages.len()),
+        _ => stage,
+    }
+}
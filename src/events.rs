@@ -0,0 +1,47 @@
+//! Observer hooks so an embedding application can react to typing progress
+//! without going through the terminal UI
+
+use std::fmt;
+use crossterm::event::KeyCode;
+use crate::core::goals::GoalResult;
+use crate::quotes::QuoteDifficulty;
+
+/// Something that happened while input was processed
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppEvent {
+    /// A key was counted in the typing metrics
+    KeystrokeRecorded { code: KeyCode, correct: bool },
+    /// The current quote was finished, with its own WPM and accuracy
+    QuoteCompleted { wpm: f64, accuracy: f64 },
+    /// The completed quote reached every goal that is set
+    GoalMet(GoalResult),
+    /// The next quote has a different difficulty than the one just completed
+    DifficultyChanged { from: QuoteDifficulty, to: QuoteDifficulty },
+}
+
+/// Callback registered with `SpringKeys::on_event`
+pub type EventCallback = Box<dyn FnMut(&AppEvent) + Send>;
+
+/// Registered callbacks, called in registration order
+#[derive(Default)]
+pub struct EventHandlers {
+    callbacks: Vec<EventCallback>,
+}
+
+impl EventHandlers {
+    pub fn push(&mut self, callback: EventCallback) {
+        self.callbacks.push(callback);
+    }
+
+    pub fn emit(&mut self, event: &AppEvent) {
+        for callback in &mut self.callbacks {
+            callback(event);
+        }
+    }
+}
+
+impl fmt::Debug for EventHandlers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EventHandlers({} callbacks)", self.callbacks.len())
+    }
+}
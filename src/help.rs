@@ -13,32 +13,87 @@ pub fn print_help() {
 
     println!("COMMANDS:");
     println!("  practice              Practice typing with quotes");
+    println!("  words                 Type each quote one word at a time, pressing Enter after each word");
+    println!("  sentences             Type long passages one sentence at a time, moving on after each sentence");
+    println!("  memorize              Type short quotes from memory; ones typed slowly or with errors come back sooner");
     println!("  consume [INPUT]       Process input and visualize typing results in UI");
+    println!("  learn                 Work through the lesson curriculum, advancing as each lesson is passed");
     println!("  config                Edit configuration");
     println!("  test                  Display test pattern (VGA-style test)");
     println!("  quote                 Output a random quote and exit");
+    println!("  quote find QUERY      List quotes whose text or source contains QUERY");
+    println!("  quote categories      List quote categories with their quote counts");
+    println!("  quote add             Append a quote to its category file (see QUOTE ADD OPTIONS)");
     println!("  moosesay              Display an animated moose with a random quote");
-    println!("  screensaver [SECONDS] Display animated moose screensaver for specified duration\n");
+    println!("  screensaver [SECONDS] Display animated moose screensaver; without SECONDS, runs until any key or mouse movement");
+    println!("  single                Type one quote headlessly from --input tokens (or stdin) and exit");
+    println!("  heatmap export        Write per-key average timings and hit counts to JSON");
+    println!("  stats byhour          Chart average WPM by hour of the day");
+    println!("  compare OLD NEW       Show which keys got faster or slower between two heatmap/metrics JSON files");
+    println!("  check                 Validate the config, quote files and stats directory, then exit 1 if anything is wrong\n");
 
     println!("OPTIONS:");
     println!("  -h, --help            Show this help message");
     println!("  -v, --version         Show version information");
     println!("  -d, --difficulty      Set difficulty level (easy, medium, hard)");
+    println!("  -c, --category        Restrict quotes to a category (all, programming, literature, typewriter)");
+    println!("  --origin ORIGIN       Restrict quotes to one origin, e.g. English (case-insensitive)");
+    println!("  --preset NAME         Practice a fixed text (foxjump, homerow, numbers, pangram)");
+    println!("  --color-mode MODE     Force terminal colors (truecolor, 16) instead of detecting them");
+    println!("  --fps N               Frame rate of moosesay, screensaver and test animations (1-60)");
+    println!("  --seed N              Seed quote selection so the same quotes appear in the same order");
+    println!("  --paragraph N         In practice mode, join N quotes into one long text");
+    println!("  --max-sessions N      Keep at most N session files in stats/ (default 1000)");
+    println!("  --repeat              In practice mode, retype each quote until it meets the accuracy goal (default 95%)");
+    println!("  --all-quotes          In practice mode, include quotes with characters the keyboard can't type");
+    println!("  --favorites           In practice mode, only serve quotes bookmarked with F9");
+    println!("  --unlock-all          Include categories not unlocked yet (SacredTexts, HardStockReports)");
+    println!("  --unseen              In practice mode, prefer quotes not completed before (random once all are done)");
+    println!("  --list-categories     Same as `quote categories`");
+    println!("  --dry-run             Same as `check`");
+    println!("  --json                Print `quote` and `stats` results as JSON (implies --quiet)");
     println!("  -q, --quiet           Quiet mode (minimal logging)");
     println!("  --verbose             Verbose mode (show final screen buffer)");
+    println!("  --blind               Blind mode: hide typed input to practice without looking");
+    println!("  --minimal             Distraction-free mode: show only the quote, input and WPM/accuracy");
+    println!("  --no-altscreen        Draw inline instead of on the alternate screen, leaving the results in scrollback");
     println!("  --                    Force non-interactive mode (no animations)\n");
 
     println!("CONSUME MODE OPTIONS:");
-    println!("  --input=TEXT          Input sequence to process (space-separated tokens)\n");
+    println!("  --input=TEXT          Input sequence to process (space-separated tokens; type a space with <space>)\n");
+
+    println!("SINGLE MODE OPTIONS:");
+    println!("  --input TOKENS        Input sequence to type (space-separated tokens; type a space with <space>); stdin if omitted");
+    println!("  --text TEXT           Type this text instead of a preset or random quote");
+    println!("  --timeout MS          Give up after this many milliseconds");
+    println!("  Exit codes: 0 = quote completed, 1 = input ended first, 2 = timed out\n");
+
+    println!("QUOTE ADD OPTIONS:");
+    println!("  --text TEXT           Quote text");
+    println!("  --source SOURCE       Who or what the quote is from");
+    println!("  -c, --category NAME   Category, e.g. Programming; letters and digits only");
+    println!("  -d, --difficulty      easy, medium or hard; estimated from the text if omitted");
+    println!("  --origin ORIGIN       Language or regional origin (optional)\n");
+
+    println!("HEATMAP OPTIONS:");
+    println!("  --out FILE            Output file for heatmap export (default: heatmap.json)\n");
 
     println!("ENVIRONMENT VARIABLES:");
     println!("  SPRING_KEYS_ENV_INFO  Set to '1' or 'true' to display environment information");
-    println!("  SPRING_KEYS_DEMO_HEATMAP Enable color spectrum visualization for keyboard heatmap\n");
+    println!("  SPRING_KEYS_DEMO_HEATMAP Enable color spectrum visualization for keyboard heatmap");
+    println!("  SPRING_KEYS_KEYLOG    Append every keystroke to a JSONL file ('1' for spring_keys_keylog.jsonl, or a path)");
+    println!("  SPRING_KEYS_PERF      Set to '1' or 'true' to log p50/p99 key-to-render latency on exit");
+    println!("  SPRING_KEYS_SEED      Seed all randomness (quotes, screensaver) so runs repeat exactly");
+    println!("  RUST_LOG              Log level override (e.g. 'debug' or 'spring_keys=warn'), ignoring --quiet\n");
 
     println!("EXAMPLES:");
     println!("  spring-keys practice -d medium");
+    println!("  spring-keys practice --category programming");
     println!("  spring-keys consume \"T h e <space> q u i c k\"");
     println!("  spring-keys test");
+    println!("  spring-keys single --preset foxjump --input \"T h e <space> q u i c k\"");
+    println!("  spring-keys heatmap export --out heatmap.json");
+    println!("  spring-keys compare last-week.json heatmap.json");
     println!("  spring-keys quote     # Get a random quote");
     println!("  spring-keys moosesay  # Get a random quote with an animated moose");
     println!("  spring-keys screensaver 10  # Run moose screensaver for 10 seconds");
@@ -60,19 +115,50 @@ pub fn print_help_quiet() {
 
     println!("COMMANDS:");
     println!("  practice              Practice typing with quotes");
+    println!("  words                 Type each quote one word at a time, pressing Enter after each word");
+    println!("  sentences             Type long passages one sentence at a time, moving on after each sentence");
+    println!("  memorize              Type short quotes from memory; ones typed slowly or with errors come back sooner");
     println!("  consume [INPUT]       Process input and visualize typing results in UI");
+    println!("  learn                 Work through the lesson curriculum, advancing as each lesson is passed");
     println!("  config                Edit configuration");
     println!("  test                  Display test pattern (VGA-style test)");
     println!("  quote                 Output a random quote and exit");
+    println!("  quote find QUERY      List quotes whose text or source contains QUERY");
+    println!("  quote categories      List quote categories with their quote counts");
+    println!("  quote add             Append a quote to its category file (see QUOTE ADD OPTIONS)");
     println!("  moosesay              Display an animated moose with a random quote");
-    println!("  screensaver [SECONDS] Display animated moose screensaver for specified duration\n");
+    println!("  screensaver [SECONDS] Display animated moose screensaver; without SECONDS, runs until any key or mouse movement");
+    println!("  single                Type one quote headlessly from --input tokens (or stdin) and exit");
+    println!("  heatmap export        Write per-key average timings and hit counts to JSON");
+    println!("  stats byhour          Chart average WPM by hour of the day");
+    println!("  compare OLD NEW       Show which keys got faster or slower between two heatmap/metrics JSON files");
+    println!("  check                 Validate the config, quote files and stats directory, then exit 1 if anything is wrong\n");
 
     println!("OPTIONS:");
     println!("  -h, --help            Show this help message");
     println!("  -v, --version         Show version information");
     println!("  -d, --difficulty      Set difficulty level (easy, medium, hard)");
+    println!("  -c, --category        Restrict quotes to a category (all, programming, literature, typewriter)");
+    println!("  --origin ORIGIN       Restrict quotes to one origin, e.g. English (case-insensitive)");
+    println!("  --preset NAME         Practice a fixed text (foxjump, homerow, numbers, pangram)");
+    println!("  --color-mode MODE     Force terminal colors (truecolor, 16) instead of detecting them");
+    println!("  --fps N               Frame rate of moosesay, screensaver and test animations (1-60)");
+    println!("  --seed N              Seed quote selection so the same quotes appear in the same order");
+    println!("  --paragraph N         In practice mode, join N quotes into one long text");
+    println!("  --max-sessions N      Keep at most N session files in stats/ (default 1000)");
+    println!("  --repeat              In practice mode, retype each quote until it meets the accuracy goal (default 95%)");
+    println!("  --all-quotes          In practice mode, include quotes with characters the keyboard can't type");
+    println!("  --favorites           In practice mode, only serve quotes bookmarked with F9");
+    println!("  --unlock-all          Include categories not unlocked yet (SacredTexts, HardStockReports)");
+    println!("  --unseen              In practice mode, prefer quotes not completed before (random once all are done)");
+    println!("  --list-categories     Same as `quote categories`");
+    println!("  --dry-run             Same as `check`");
+    println!("  --json                Print `quote` and `stats` results as JSON (implies --quiet)");
     println!("  -q, --quiet           Quiet mode (minimal logging)");
     println!("  --verbose             Verbose mode (show final screen buffer)");
+    println!("  --blind               Blind mode: hide typed input to practice without looking");
+    println!("  --minimal             Distraction-free mode: show only the quote, input and WPM/accuracy");
+    println!("  --no-altscreen        Draw inline instead of on the alternate screen, leaving the results in scrollback");
     println!("  --                    Force non-interactive mode (no animations)\n");
 }
 
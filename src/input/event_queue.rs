@@ -37,13 +37,25 @@ impl EventQueue {
         true
     }
 
+    /// Take the earliest pressed event. Rolled-over keys can be pushed out of press
+    /// order, so this goes by timestamp; events pressed at the same instant keep push order.
     pub fn pop(&mut self) -> Option<KeyboardEvent> {
         self.last_processed = Some(Instant::now());
-        self.events.pop_front()
+        let index = self.earliest_index()?;
+        self.events.remove(index)
     }
 
+    /// The event `pop` would return next
     pub fn peek(&self) -> Option<&KeyboardEvent> {
-        self.events.front()
+        self.events.get(self.earliest_index()?)
+    }
+
+    fn earliest_index(&self) -> Option<usize> {
+        self.events
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, event)| event.timestamp)
+            .map(|(index, _)| index)
     }
 
     pub fn clear(&mut self) {
@@ -61,13 +73,7 @@ impl EventQueue {
 
     pub fn cleanup_old_events(&mut self) {
         let now = Instant::now();
-        while let Some(event) = self.events.front() {
-            if now.duration_since(event.timestamp) > EVENT_TIMEOUT {
-                self.events.pop_front();
-            } else {
-                break;
-            }
-        }
+        self.events.retain(|event| now.duration_since(event.timestamp) <= EVENT_TIMEOUT);
     }
 }
 
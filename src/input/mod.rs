@@ -1,11 +1,50 @@
-use std::time::Instant;
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use crossterm::event::{KeyCode, KeyModifiers};
+use log::warn;
+use unicode_segmentation::UnicodeSegmentation;
 use crate::core::TypingSession;
 use crate::ui::heatmap::register_key_press;
 
 mod event_queue;
 pub use event_queue::{EventQueue, KeyboardEvent};
 
+/// Environment variable that enables the keystroke log
+pub const KEYLOG_ENV_VAR: &str = "SPRING_KEYS_KEYLOG";
+/// Keystroke log file used when the env var is set to "1" or "true"
+pub const DEFAULT_KEYLOG_FILE: &str = "spring_keys_keylog.jsonl";
+/// Most edit states kept for `InputProcessor::undo`
+pub const UNDO_HISTORY_LIMIT: usize = 100;
+/// Column interval of the tab stops used when expanding tabs in quotes
+pub const TAB_WIDTH: usize = 4;
+
+/// Replace each tab with spaces up to the next tab stop so quote positions
+/// stay one column per character
+pub fn expand_tabs(text: &str) -> String {
+    let mut expanded = String::with_capacity(text.len());
+    let mut column = 0;
+    for c in text.chars() {
+        match c {
+            '\t' => {
+                let width = TAB_WIDTH - column % TAB_WIDTH;
+                expanded.extend(std::iter::repeat_n(' ', width));
+                column += width;
+            }
+            '\n' => {
+                expanded.push(c);
+                column = 0;
+            }
+            _ => {
+                expanded.push(c);
+                column += 1;
+            }
+        }
+    }
+    expanded
+}
+
 #[derive(Debug)]
 pub struct InputProcessor {
     pub current_text: String,
@@ -14,6 +53,47 @@ pub struct InputProcessor {
     pub last_error: Option<bool>,
     pub caps_lock_enabled: bool,
     pub last_key_time: Option<Instant>,
+    /// Opt-in JSONL keystroke log for debugging input issues
+    pub keylog: Option<File>,
+    /// Strict drill mode: a wrong character is counted but never inserted
+    pub stop_on_error: bool,
+    /// When a character was last rejected in strict mode, so the UI can flash
+    pub rejected_at: Option<Instant>,
+    /// A rejection that hasn't been counted as an error yet
+    rejection_pending: bool,
+    /// Count each backspace that erases a character as an error
+    pub backspace_penalty: bool,
+    /// Penalized backspaces that haven't been counted yet
+    backspace_penalties_pending: usize,
+    /// Take case from SHIFT and the character itself, ignoring `caps_lock_enabled`
+    pub respect_shift_only: bool,
+    /// `(current_text, cursor_position)` before each edit, oldest first
+    undo_history: VecDeque<(String, usize)>,
+    /// States undone since the last edit, most recent last
+    redo_history: Vec<(String, usize)>,
+}
+
+/// How one character of the quote compares with the input, see `char_diff`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharState {
+    Correct,
+    Wrong,
+    /// Not typed yet
+    Pending,
+}
+
+/// Compare `typed` against `expected` character by character, giving one state per
+/// character of `expected`. Input past the end of the quote is ignored.
+pub fn char_diff(expected: &str, typed: &str) -> Vec<CharState> {
+    let mut typed = typed.chars();
+    expected
+        .chars()
+        .map(|e| match typed.next() {
+            Some(t) if t == e => CharState::Correct,
+            Some(_) => CharState::Wrong,
+            None => CharState::Pending,
+        })
+        .collect()
 }
 
 #[derive(Debug, Clone)]
@@ -25,6 +105,10 @@ pub struct ValidationResult {
 
 impl InputProcessor {
     pub fn new() -> Self {
+        Self::with_keylog(open_keylog_from_env())
+    }
+
+    fn with_keylog(keylog: Option<File>) -> Self {
         Self {
             current_text: String::new(),
             cursor_position: 0,
@@ -32,23 +116,56 @@ impl InputProcessor {
             last_error: None,
             caps_lock_enabled: false,
             last_key_time: None,
+            keylog,
+            stop_on_error: false,
+            rejected_at: None,
+            rejection_pending: false,
+            backspace_penalty: false,
+            backspace_penalties_pending: 0,
+            respect_shift_only: false,
+            undo_history: VecDeque::new(),
+            redo_history: Vec::new(),
         }
     }
 
-    pub fn process_key_event(&mut self, key: KeyCode, modifiers: KeyModifiers, typing_session: Option<&mut TypingSession>) {
-        let event = KeyboardEvent::new(key, modifiers);
-        self.event_queue.push(event);
+    pub fn process_key_event(&mut self, key: KeyCode, modifiers: KeyModifiers, mut typing_session: Option<&mut TypingSession>) {
+        // Quotes have their tabs expanded, so Tab types spaces up to the next stop
+        if key == KeyCode::Tab {
+            let width = TAB_WIDTH - self.cursor_position % TAB_WIDTH;
+            for _ in 0..width {
+                self.process_key_event(KeyCode::Char(' '), KeyModifiers::NONE, typing_session.as_deref_mut());
+                self.process_queued_events();
+            }
+            return;
+        }
+
         self.process_modifiers(key, modifiers);
         
+        let resulting_char = match key {
+            KeyCode::Char(_) if is_editing_shortcut(key, modifiers) => None,
+            KeyCode::Char(c) => Some(self.apply_case(c, modifiers)),
+            _ => None,
+        };
+        self.log_keystroke(key, modifiers, resulting_char);
+
+        // In strict mode a wrong character never reaches the input buffer
+        let rejected = match (self.stop_on_error, resulting_char, typing_session.as_deref()) {
+            (true, Some(c), Some(session)) => !self.validate_next_char(&session.quote_text, c).is_valid,
+            _ => false,
+        };
+        if rejected {
+            self.rejected_at = Some(Instant::now());
+            self.rejection_pending = true;
+        } else {
+            self.event_queue.push(KeyboardEvent::new(key, modifiers));
+        }
+
         // Record the keystroke in the typing session metrics
         if let Some(session) = typing_session {
             match key {
-                KeyCode::Char(c) => {
-                    let processed_char = if modifiers.contains(KeyModifiers::SHIFT) {
-                        c.to_ascii_uppercase()
-                    } else {
-                        self.handle_caps_lock(c)
-                    };
+                KeyCode::Char(_) if is_editing_shortcut(key, modifiers) => {}
+                KeyCode::Char(_) => {
+                    let processed_char = resulting_char.unwrap_or_default();
                     session.record_keystroke(processed_char);
                     // Register key press for animation
                     register_key_press(processed_char);
@@ -57,6 +174,7 @@ impl InputProcessor {
                     // Reset cursor position on Enter press
                     self.cursor_position = 0;
                 },
+                KeyCode::Backspace if is_editing_shortcut(key, modifiers) => {}
                 KeyCode::Backspace => {
                     session.record_keystroke('\u{232B}'); // Unicode backspace symbol
                 },
@@ -71,20 +189,99 @@ impl InputProcessor {
         self.event_queue.cleanup_old_events();
         
         while let Some(event) = self.event_queue.pop() {
+            if event.modifiers.contains(KeyModifiers::CONTROL) {
+                match event.key {
+                    KeyCode::Char('z') => { self.undo(); continue; }
+                    KeyCode::Char('y') => { self.redo(); continue; }
+                    _ => {}
+                }
+            }
+
+            let before = (self.current_text.clone(), self.cursor_position);
             match event.key {
+                KeyCode::Char('a') if event.modifiers.contains(KeyModifiers::CONTROL) => self.move_cursor_home(),
+                KeyCode::Char('e') if event.modifiers.contains(KeyModifiers::CONTROL) => self.move_cursor_end(),
+                KeyCode::Char('w') if event.modifiers.contains(KeyModifiers::CONTROL) => self.delete_word_back(),
+                KeyCode::Char('u') if event.modifiers.contains(KeyModifiers::CONTROL) => self.delete_to_start(),
+                KeyCode::Backspace if event.modifiers.contains(KeyModifiers::CONTROL) => self.delete_word_back(),
+                KeyCode::Backspace if event.modifiers.contains(KeyModifiers::SHIFT) => self.clear_line(),
                 KeyCode::Char(c) => {
-                    let processed_char = if event.modifiers.contains(KeyModifiers::SHIFT) {
-                        c.to_ascii_uppercase()
-                    } else {
-                        self.handle_caps_lock(c)
-                    };
+                    let processed_char = self.apply_case(c, event.modifiers);
                     self.insert_char(processed_char);
                 }
-                KeyCode::Backspace => self.handle_backspace(),
+                KeyCode::Backspace => {
+                    self.handle_backspace();
+                }
+                KeyCode::Delete => {
+                    self.delete_forward();
+                }
                 KeyCode::Left => self.move_cursor_left(),
                 KeyCode::Right => self.move_cursor_right(),
+                KeyCode::Home => self.move_cursor_home(),
+                KeyCode::End => self.move_cursor_end(),
                 _ => {}
             }
+            self.record_edit(before);
+        }
+    }
+
+    /// Remember the state before an edit so it can be undone. Cursor moves alone
+    /// don't change the text and aren't recorded.
+    fn record_edit(&mut self, before: (String, usize)) {
+        if before.0 == self.current_text {
+            return;
+        }
+        if self.undo_history.len() == UNDO_HISTORY_LIMIT {
+            self.undo_history.pop_front();
+        }
+        self.undo_history.push_back(before);
+        self.redo_history.clear();
+    }
+
+    /// Restore the input as it was before the last edit (Ctrl+Z), returning whether
+    /// there was one. Only the buffer changes; recorded keystrokes stay counted.
+    pub fn undo(&mut self) -> bool {
+        let Some((text, cursor)) = self.undo_history.pop_back() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut self.current_text, text);
+        self.redo_history.push((current, self.cursor_position));
+        self.cursor_position = cursor;
+        true
+    }
+
+    /// Reapply the last undone edit (Ctrl+Y), returning whether there was one
+    pub fn redo(&mut self) -> bool {
+        let Some((text, cursor)) = self.redo_history.pop() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut self.current_text, text);
+        self.undo_history.push_back((current, self.cursor_position));
+        self.cursor_position = cursor;
+        true
+    }
+
+    /// Append a keystroke record to the keylog, if enabled
+    fn log_keystroke(&mut self, key: KeyCode, modifiers: KeyModifiers, resulting_char: Option<char>) {
+        let Some(file) = self.keylog.as_mut() else {
+            return;
+        };
+
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        let record = serde_json::json!({
+            "timestamp_ms": timestamp_ms,
+            "keycode": format!("{:?}", key),
+            "modifiers": format!("{:?}", modifiers),
+            "resulting_char": resulting_char,
+        });
+
+        // Flush per keystroke so the log survives a crash
+        if let Err(e) = writeln!(file, "{}", record).and_then(|_| file.flush()) {
+            warn!("Failed to write keystroke log, disabling it: {}", e);
+            self.keylog = None;
         }
     }
 
@@ -94,6 +291,19 @@ impl InputProcessor {
         }
     }
 
+    /// Case of a typed character. SHIFT always uppercases. Otherwise the internal caps
+    /// lock state decides, unless `respect_shift_only` is set, in which case the
+    /// character is kept as the terminal sent it (terminals apply caps lock themselves).
+    fn apply_case(&self, c: char, modifiers: KeyModifiers) -> char {
+        if modifiers.contains(KeyModifiers::SHIFT) {
+            c.to_ascii_uppercase()
+        } else if self.respect_shift_only {
+            c
+        } else {
+            self.handle_caps_lock(c)
+        }
+    }
+
     pub fn handle_caps_lock(&self, c: char) -> char {
         if self.caps_lock_enabled {
             c.to_ascii_uppercase()
@@ -102,16 +312,83 @@ impl InputProcessor {
         }
     }
 
+    /// Number of characters in the input buffer
+    pub fn char_len(&self) -> usize {
+        self.current_text.chars().count()
+    }
+
+    /// Byte offset of a character index in the input buffer.
+    /// `cursor_position` is kept in char units so multi-byte input doesn't split characters.
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.current_text
+            .char_indices()
+            .nth(char_index)
+            .map(|(i, _)| i)
+            .unwrap_or(self.current_text.len())
+    }
+
     fn insert_char(&mut self, c: char) {
-        self.current_text.insert(self.cursor_position, c);
+        let index = self.byte_index(self.cursor_position);
+        self.current_text.insert(index, c);
         self.cursor_position += 1;
     }
 
-    fn handle_backspace(&mut self) {
-        if self.cursor_position > 0 {
-            self.cursor_position -= 1;
-            self.current_text.remove(self.cursor_position);
+    /// Erase the character before the cursor, returning whether there was one.
+    /// With `backspace_penalty` set, each erased character is queued for `take_backspace_penalties`.
+    fn handle_backspace(&mut self) -> bool {
+        if self.cursor_position == 0 {
+            return false;
+        }
+        self.cursor_position -= 1;
+        let index = self.byte_index(self.cursor_position);
+        self.current_text.remove(index);
+        if self.backspace_penalty {
+            self.backspace_penalties_pending += 1;
+        }
+        true
+    }
+
+    /// Remove the character under the cursor (the Delete key), leaving the cursor where it is.
+    /// Returns false at the end of the input, where there is nothing to delete.
+    pub fn delete_forward(&mut self) -> bool {
+        if self.cursor_position >= self.char_len() {
+            return false;
+        }
+        let index = self.byte_index(self.cursor_position);
+        self.current_text.remove(index);
+        true
+    }
+
+    /// Delete the word before the cursor, back to the previous whitespace boundary (Ctrl+W)
+    pub fn delete_word_back(&mut self) {
+        let chars: Vec<char> = self.current_text.chars().collect();
+        let mut start = self.cursor_position.min(chars.len());
+
+        // Skip whitespace right before the cursor, then the word itself
+        while start > 0 && chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        while start > 0 && !chars[start - 1].is_whitespace() {
+            start -= 1;
         }
+
+        let from = self.byte_index(start);
+        let to = self.byte_index(self.cursor_position);
+        self.current_text.replace_range(from..to, "");
+        self.cursor_position = start;
+    }
+
+    /// Delete everything before the cursor (Ctrl+U)
+    pub fn delete_to_start(&mut self) {
+        let to = self.byte_index(self.cursor_position);
+        self.current_text.replace_range(..to, "");
+        self.cursor_position = 0;
+    }
+
+    /// Delete the whole input, keeping the undo history (Shift+Backspace)
+    pub fn clear_line(&mut self) {
+        self.current_text.clear();
+        self.cursor_position = 0;
     }
 
     fn move_cursor_left(&mut self) {
@@ -121,20 +398,34 @@ impl InputProcessor {
     }
 
     fn move_cursor_right(&mut self) {
-        if self.cursor_position < self.current_text.len() {
+        if self.cursor_position < self.char_len() {
             self.cursor_position += 1;
         }
     }
 
+    pub fn move_cursor_home(&mut self) {
+        self.cursor_position = 0;
+    }
+
+    pub fn move_cursor_end(&mut self) {
+        self.cursor_position = self.char_len();
+    }
+
+    /// Compare the input against `expected` one grapheme cluster at a time, so an accented
+    /// letter built from combining marks or a multi-codepoint emoji counts as a single
+    /// character. The grapheme being typed may be a partial match (e.g. the base letter
+    /// before its combining accent); `position` is the index of the first wrong grapheme.
     pub fn validate_input(&self, expected: &str) -> ValidationResult {
-        let current = self.current_text.as_str();
+        let current: Vec<&str> = self.current_text.graphemes(true).collect();
+        let expected: Vec<&str> = expected.graphemes(true).collect();
         let mut is_valid = true;
         let mut error = None;
         let mut error_position = 0;
-        
-        // Check character by character up to the current input length
-        for (i, (actual, expected)) in current.chars().zip(expected.chars()).enumerate() {
-            if actual != expected {
+
+        for (i, (actual, expected_grapheme)) in current.iter().zip(&expected).enumerate() {
+            let is_last_typed = i + 1 == current.len();
+            let matches = actual == expected_grapheme || (is_last_typed && expected_grapheme.starts_with(actual));
+            if !matches {
                 is_valid = false;
                 error = Some(true);
                 error_position = i;
@@ -142,12 +433,6 @@ impl InputProcessor {
             }
         }
 
-        // If we've matched all characters typed so far, it's valid
-        if is_valid && current.len() <= expected.len() {
-            is_valid = true;
-            error = None;
-        }
-
         ValidationResult {
             is_valid,
             error,
@@ -155,6 +440,41 @@ impl InputProcessor {
         }
     }
 
+    /// Check whether typing `c` at the cursor would keep the input matching `expected`,
+    /// by grapheme cluster like `validate_input`: `c` may start or continue the grapheme
+    /// it lands in, but the ones before it must be complete. `position` is the index of
+    /// the first wrong grapheme, or of the one `c` lands in.
+    pub fn validate_next_char(&self, expected: &str, c: char) -> ValidationResult {
+        let mut typed: String = self.current_text.chars().take(self.cursor_position).collect();
+        typed.push(c);
+        let typed: Vec<&str> = typed.graphemes(true).collect();
+        let expected: Vec<&str> = expected.graphemes(true).collect();
+        let last = typed.len() - 1;
+        let wrong = (0..typed.len()).find(|&i| match expected.get(i) {
+            Some(grapheme) if i == last => !grapheme.starts_with(typed[i]),
+            Some(grapheme) => *grapheme != typed[i],
+            None => true,
+        });
+        let is_valid = wrong.is_none();
+        let position = wrong.unwrap_or(last);
+
+        ValidationResult {
+            is_valid,
+            error: if is_valid { None } else { Some(true) },
+            position,
+        }
+    }
+
+    /// Returns true once for each character rejected in strict mode
+    pub fn take_rejection(&mut self) -> bool {
+        std::mem::take(&mut self.rejection_pending)
+    }
+
+    /// Number of penalized backspaces since the last call
+    pub fn take_backspace_penalties(&mut self) -> usize {
+        std::mem::take(&mut self.backspace_penalties_pending)
+    }
+
     pub fn update_error_state(&mut self, result: &ValidationResult) {
         self.last_error = result.error;
     }
@@ -165,15 +485,47 @@ impl InputProcessor {
         self.event_queue.clear();
         self.last_error = None;
         self.last_key_time = None;
+        self.rejected_at = None;
+        self.rejection_pending = false;
+        self.backspace_penalties_pending = 0;
+        self.undo_history.clear();
+        self.redo_history.clear();
+    }
+
+    /// Replace the input with `text` and put the cursor at character `cursor`, clamped
+    /// to the end of the text, e.g. to resume a checkpoint without replaying keystrokes.
+    /// Queued events and undo history belong to the old text and are dropped.
+    pub fn set_text(&mut self, text: &str, cursor: usize) {
+        self.current_text = text.to_string();
+        self.cursor_position = cursor.min(self.char_len());
+        self.event_queue.clear();
+        self.undo_history.clear();
+        self.redo_history.clear();
+    }
+
+    /// Time since the last key, or `None` if nothing has been typed since the last `clear`
+    pub fn idle_duration(&self) -> Option<Duration> {
+        self.last_key_time.map(|t| t.elapsed())
     }
 
-    pub fn backspace(&mut self) {
-        self.handle_backspace();
+    /// Erase the character before the cursor, returning whether there was one
+    pub fn backspace(&mut self) -> bool {
+        let before = (self.current_text.clone(), self.cursor_position);
+        let erased = self.handle_backspace();
+        self.record_edit(before);
+        erased
     }
 
     /// Process a token from an automated input sequence
     /// This allows simulating key presses from a space-separated token sequence
     pub fn process_token<'a>(&mut self, token: &str, typing_session: Option<&'a mut TypingSession>) -> bool {
+        // Navigation and function keys
+        if let Some(key) = parse_key_token(token) {
+            self.process_key_event(key, KeyModifiers::NONE, typing_session);
+            self.process_queued_events();
+            return true;
+        }
+
         let success = match token {
             "<space>" => {
                 let key = KeyCode::Char(' ');
@@ -190,6 +542,14 @@ impl InputProcessor {
                 self.process_key_event(key, KeyModifiers::NONE, typing_session);
                 true
             },
+            "<ctrl+bs>" | "<ctrl+backspace>" => {
+                self.process_key_event(KeyCode::Backspace, KeyModifiers::CONTROL, typing_session);
+                true
+            },
+            "<shift+bs>" | "<shift+backspace>" => {
+                self.process_key_event(KeyCode::Backspace, KeyModifiers::SHIFT, typing_session);
+                true
+            },
             "<tab>" => {
                 let key = KeyCode::Tab;
                 self.process_key_event(key, KeyModifiers::NONE, typing_session);
@@ -221,7 +581,7 @@ impl InputProcessor {
                 }
             },
             // Regular single character
-            s if s.len() == 1 => {
+            s if s.chars().count() == 1 => {
                 if let Some(c) = s.chars().next() {
                     // Skip processing spaces in token sequence
                     if c == ' ' {
@@ -253,9 +613,33 @@ impl InputProcessor {
         success
     }
     
-    /// Process a sequence of tokens separated by spaces
-    pub fn process_token_sequence(&mut self, sequence: &str, mut typing_session: Option<&mut TypingSession>) -> usize {
-        let tokens: Vec<&str> = sequence.split_whitespace().collect();
+    /// Text a whitespace-separated token sequence leaves in an empty input, e.g. `Hi you`
+    /// for `"H i <space> y o u"`. Nothing is written to the keylog.
+    pub fn text_from_tokens(sequence: &str) -> String {
+        let mut processor = Self::with_keylog(None);
+        processor.process_token_sequence(sequence, None);
+        processor.current_text
+    }
+
+    /// Process a sequence of tokens separated by whitespace. Runs of whitespace are one
+    /// separator, so a space to type must be written as `<space>`: `"a <space> <space> b"`
+    /// types `a  b`, while `"a  b"` types `ab`.
+    pub fn process_token_sequence(&mut self, sequence: &str, typing_session: Option<&mut TypingSession>) -> usize {
+        self.process_tokens(sequence.split_whitespace(), typing_session)
+    }
+
+    /// Like `process_token_sequence`, but tokens are separated by `separator` instead of
+    /// whitespace. Consecutive separators still count as one (empty tokens are skipped),
+    /// and spaces are typed with `<space>` here too: with `"|"`, `"a|<space>|<space>|b"`
+    /// types `a  b`.
+    pub fn process_token_sequence_with(&mut self, sequence: &str, separator: &str, typing_session: Option<&mut TypingSession>) -> usize {
+        if separator.is_empty() {
+            return self.process_token_sequence(sequence, typing_session);
+        }
+        self.process_tokens(sequence.split(separator).filter(|token| !token.is_empty()), typing_session)
+    }
+
+    fn process_tokens<'t>(&mut self, tokens: impl Iterator<Item = &'t str>, mut typing_session: Option<&mut TypingSession>) -> usize {
         let mut processed = 0;
         
         for token in tokens {
@@ -268,4 +652,52 @@ impl InputProcessor {
         
         processed
     }
-} 
\ No newline at end of file
+}
+
+/// Key combinations that edit the buffer instead of typing a character or a plain backspace
+fn is_editing_shortcut(key: KeyCode, modifiers: KeyModifiers) -> bool {
+    match key {
+        KeyCode::Backspace => modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::SHIFT),
+        _ => modifiers.contains(KeyModifiers::CONTROL) && matches!(key, KeyCode::Char('a' | 'e' | 'w' | 'u' | 'z' | 'y')),
+    }
+}
+
+/// Map navigation and function key tokens (`<up>`, `<del>`, `<f5>`, ...) to key codes
+fn parse_key_token(token: &str) -> Option<KeyCode> {
+    let key = match token {
+        "<up>" => KeyCode::Up,
+        "<down>" => KeyCode::Down,
+        "<left>" => KeyCode::Left,
+        "<right>" => KeyCode::Right,
+        "<home>" => KeyCode::Home,
+        "<end>" => KeyCode::End,
+        "<del>" | "<delete>" => KeyCode::Delete,
+        s if s.starts_with("<f") && s.ends_with('>') => {
+            let n = s[2..s.len() - 1].parse::<u8>().ok()?;
+            if !(1..=12).contains(&n) {
+                return None;
+            }
+            KeyCode::F(n)
+        }
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// Open the keystroke log if `SPRING_KEYS_KEYLOG` is set.
+/// The value is used as the file path, except "1"/"true" which use the default file.
+fn open_keylog_from_env() -> Option<File> {
+    let value = std::env::var(KEYLOG_ENV_VAR).ok()?;
+    let path = match value.to_lowercase().as_str() {
+        "" | "1" | "true" => DEFAULT_KEYLOG_FILE.to_string(),
+        _ => value,
+    };
+
+    match OpenOptions::new().create(true).append(true).open(&path) {
+        Ok(file) => Some(file),
+        Err(e) => {
+            warn!("Failed to open keystroke log {}: {}", path, e);
+            None
+        }
+    }
+}
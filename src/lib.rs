@@ -8,25 +8,59 @@ pub mod help;
 pub mod moosesay;
 pub mod logger;
 pub mod vga_test;
+pub mod presets;
+pub mod curriculum;
+pub mod output;
+pub mod events;
+pub mod srs;
+pub mod check;
+pub mod rng;
 
 // Import required crates
-use log::info;
+use log::{info, warn};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 // Re-export commonly used types for convenience
-pub use core::metrics::{TypingMetrics, Finger, ExtendedStats};
+pub use core::metrics::{TypingMetrics, Finger, ExtendedStats, KeyboardLayout, MetricsSnapshot, MistakeEntry, WpmMode};
 pub use core::{TypingSession, TypingError};
 pub use core::state::{GameState, GameType, GameStatus};
-pub use core::stats::AccumulatedStats;
+pub use core::stats::{AccumulatedStats, QuoteTotals, SessionAggregate, SessionSummary, StatsSummary, ACCUMULATED_STATS_FILE, STATS_DIR, STATS_SCHEMA_VERSION};
+pub use core::goals::GoalResult;
+pub use events::AppEvent;
+pub use curriculum::LessonStage;
 pub use input::InputProcessor;
 pub use config::{Config, DifficultyLevel};
-pub use quotes::{Quote, QuoteDatabase, QuoteDifficulty, CategoryCycle};
+pub use quotes::{Quote, QuoteDatabase, QuoteDifficulty, CategoryCycle, CategoryGroup};
 pub use ui::TerminalUI;
 pub use ui::color_spectrum;
 
 // Re-export commonly used types from dependencies
 pub use crossterm::event::{KeyCode, KeyModifiers};
 
+/// Number of columns in the WPM sparkline shown after a completed quote
+pub const SPARKLINE_WIDTH: usize = 60;
+
+/// How long the "goals met" banner stays up after a quote is completed
+pub const GOAL_BANNER_DURATION: Duration = Duration::from_secs(3);
+
+/// How often the UI loop persists accumulated stats
+pub const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a notice such as "no quotes in category" stays in the header
+pub const NOTICE_DURATION: Duration = Duration::from_secs(3);
+
+/// Goes between the quotes joined in paragraph mode
+pub const PARAGRAPH_SEPARATOR: &str = " ";
+
+/// Accuracy a repeated quote must reach when no accuracy goal is set
+pub const DEFAULT_REPEAT_ACCURACY: f64 = 95.0;
+
+/// Time `type_text` and `type_tokens` count per keystroke, a steady 60 WPM, so the WPM
+/// they report depends on the input rather than on how fast the loop runs
+pub const SIMULATED_KEY_INTERVAL: Duration = Duration::from_millis(200);
+
 // Define SpringKeys struct for testing
 #[derive(Debug)]
 pub struct SpringKeys {
@@ -36,52 +70,303 @@ pub struct SpringKeys {
     pub config: config::Config,
     pub quote_db: QuoteDatabase,
     pub accumulated_stats: AccumulatedStats,
+    /// WPM sparkline of the most recently completed quote
+    pub last_sparkline: Option<String>,
+    /// Rhythm consistency of the last completed quote, see `TypingMetrics::rhythm_consistency`
+    pub last_rhythm: Option<f64>,
+    /// Per-word WPM of the last quote completed in word-by-word mode
+    pub last_word_wpms: Vec<f64>,
+    /// Index of the quote being reviewed in memorize mode
+    pub memorize_index: Option<usize>,
+    /// Goal outcome of the most recently completed quote and when it was completed
+    last_goal: Option<(GoalResult, Instant)>,
+    /// Totals across all quotes completed since the app started
+    pub session_aggregate: SessionAggregate,
+    /// Session metric counts when the current quote was loaded, to tell this quote's share apart
+    quote_baseline: QuoteBaseline,
+    /// When the accumulated stats were last written by `autosave_if_due`
+    last_autosave: Instant,
+    /// Seed the quote order was fixed with, if any, so it can be shared
+    pub seed: Option<u64>,
+    /// Join this many quotes into each session instead of typing one at a time
+    pub paragraph_length: Option<usize>,
+    /// Difficulty of the current quote; estimated with `QuoteDifficulty::classify` for custom text
+    pub quote_difficulty: QuoteDifficulty,
+    /// Reload the same quote after completion until its accuracy reaches the goal
+    pub repeat_until_passed: bool,
+    /// Prefer quotes that haven't been completed before, see `QuoteDatabase::next_unseen`
+    pub prefer_unseen: bool,
+    /// Keep a results card after each completed quote; set by the interactive UI
+    pub show_results_card: bool,
+    /// Metrics of the quote just completed, while its results card is showing
+    pub pending_results: Option<TypingMetrics>,
+    /// Callbacks registered with `on_event`
+    events: events::EventHandlers,
+    /// Message for the header and when it was raised, see `notice`
+    notice: Option<(String, Instant)>,
+    /// File `config` was loaded from, reread by `reload_config`
+    pub config_path: PathBuf,
+    /// File the accumulated stats were loaded from and are autosaved to, see `load_stats_from`
+    pub stats_path: PathBuf,
+    /// Render mode from a reloaded config, switched to when the next quote loads
+    pending_render_mode: Option<config::RenderMode>,
+    /// Command line settings kept over the config file, see `set_config_overrides`
+    config_overrides: config::ConfigOverrides,
+}
+
+/// Snapshot of the cumulative session counts at the start of a quote
+#[derive(Debug, Clone, Copy)]
+struct QuoteBaseline {
+    keystrokes: usize,
+    correct_keystrokes: usize,
+    correct_spaces: usize,
+    errors: usize,
+    started: Instant,
+}
+
+impl QuoteBaseline {
+    fn capture(session: Option<&TypingSession>) -> Self {
+        let metrics = session.map(|s| &s.metrics);
+        Self {
+            keystrokes: metrics.map_or(0, |m| m.keystrokes),
+            correct_keystrokes: metrics.map_or(0, |m| m.correct_keystrokes),
+            correct_spaces: metrics.map_or(0, |m| m.correct_spaces),
+            errors: metrics.map_or(0, |m| m.errors.len()),
+            started: Instant::now(),
+        }
+    }
+
+    /// Counts `session` has added since this baseline, i.e. the current quote's own,
+    /// with WPM over the time since the quote was loaded
+    fn totals(&self, session: &TypingSession) -> QuoteTotals {
+        let minutes = self.started.elapsed().as_secs_f64() / 60.0;
+        let correct = session.metrics.correct_keystrokes.saturating_sub(self.correct_keystrokes);
+        let spaces = session.metrics.correct_spaces.saturating_sub(self.correct_spaces);
+        QuoteTotals {
+            keystrokes: session.metrics.keystrokes.saturating_sub(self.keystrokes),
+            correct_keystrokes: correct,
+            correct_spaces: spaces,
+            errors: session.metrics.errors.len().saturating_sub(self.errors),
+            wpm: if minutes > 0.0 { session.metrics.wpm_mode.words(correct, spaces) / minutes } else { 0.0 },
+        }
+    }
 }
 
 impl SpringKeys {
     pub fn new() -> Self {
+        Self::new_with_options(false)
+    }
+
+    /// Like `new`, but loading quotes doesn't print anything
+    pub fn new_silent() -> Self {
+        Self::new_with_options(true)
+    }
+
+    fn new_with_options(quiet_mode: bool) -> Self {
         // Load configuration or create default
-        let config_path = std::path::PathBuf::from(config::DEFAULT_CONFIG_FILE);
-        let config = config::Config::load_or_default(config_path);
+        let config_path = PathBuf::from(config::DEFAULT_CONFIG_FILE);
+        let config = config::Config::load_or_default(&config_path);
         
         // Load accumulated stats from the stats directory
         info!("Loading accumulated statistics from stats directory...");
-        let accumulated_stats = AccumulatedStats::load_from_directory();
+        let mut accumulated_stats = AccumulatedStats::load_from_directory();
+        accumulated_stats.key_decay = config.preferences.key_decay;
         info!("Loaded stats from {} quotes", accumulated_stats.total_quotes);
+
+        // Categories not unlocked yet stay out of every mode, see `quotes::LOCKED_CATEGORIES`
+        let mut quote_db = QuoteDatabase::from_directory(quotes::QUOTES_DIR, quiet_mode);
+        let locked = quote_db.lock_categories(&accumulated_stats, &config.preferences.unlocked_categories);
+        info!("Holding back {} quotes from locked categories", locked);
         
         Self {
             game_state: GameState::default(),
             input_processor: InputProcessor::new(),
             typing_session: None,
             config,
-            quote_db: QuoteDatabase::new(),
+            quote_db,
             accumulated_stats,
+            last_sparkline: None,
+            last_rhythm: None,
+            last_word_wpms: Vec::new(),
+            memorize_index: None,
+            last_goal: None,
+            session_aggregate: SessionAggregate::new(),
+            quote_baseline: QuoteBaseline::capture(None),
+            last_autosave: Instant::now(),
+            seed: None,
+            paragraph_length: None,
+            quote_difficulty: QuoteDifficulty::Easy,
+            repeat_until_passed: false,
+            prefer_unseen: false,
+            show_results_card: false,
+            pending_results: None,
+            events: events::EventHandlers::default(),
+            notice: None,
+            config_path,
+            stats_path: PathBuf::from(ACCUMULATED_STATS_FILE),
+            pending_render_mode: None,
+            config_overrides: config::ConfigOverrides::default(),
+        }
+    }
+
+    /// Call `callback` for every `AppEvent` from now on, e.g. to drive another UI
+    pub fn on_event(&mut self, callback: events::EventCallback) {
+        self.events.push(callback);
+    }
+
+    /// Load the config from `path` and make it the one `reload_config` rereads.
+    /// See `reload_config` for which settings apply right away.
+    pub fn load_config_from<P: AsRef<Path>>(&mut self, path: P) -> Result<(), config::ConfigError> {
+        let loaded = config::Config::load(&path)?;
+        self.config_path = path.as_ref().to_path_buf();
+        self.apply_config(loaded);
+        Ok(())
+    }
+
+    /// Apply settings given on the command line, and keep applying them over the config
+    /// file when it's reloaded
+    pub fn set_config_overrides(&mut self, overrides: config::ConfigOverrides) {
+        overrides.apply(&mut self.config);
+        self.config_overrides = overrides;
+    }
+
+    /// Replace the accumulated stats with the ones saved at `path` (fresh ones if there
+    /// are none yet), and autosave there from now on
+    pub fn load_stats_from<P: AsRef<Path>>(&mut self, path: P) {
+        self.stats_path = path.as_ref().to_path_buf();
+        self.accumulated_stats = AccumulatedStats::load_or_new(&self.stats_path);
+        self.accumulated_stats.key_decay = self.config.preferences.key_decay;
+    }
+
+    /// Reread the config file, e.g. after the user edited it. Preferences, theme and
+    /// display options apply immediately; the render mode changes the screen layout,
+    /// so it waits for the next quote. A notice reports the outcome.
+    pub fn reload_config(&mut self) -> Result<(), config::ConfigError> {
+        let path = self.config_path.clone();
+        let result = self.load_config_from(&path);
+        let message = match &result {
+            Ok(()) => format!("Reloaded {}", path.display()),
+            Err(e) => {
+                warn!("Failed to reload config: {}", e);
+                "Config reload failed, see log".to_string()
+            }
+        };
+        self.notice = Some((message, Instant::now()));
+        result
+    }
+
+    /// Add the current quote to the favorites, or remove it if it's already one, and save
+    /// the change to the config file. Returns whether it's now a favorite, or `None`
+    /// without a quote.
+    pub fn toggle_favorite(&mut self) -> Option<bool> {
+        let session = self.typing_session.as_ref()?;
+        // Hash the quote as loaded, before its tabs were expanded for typing
+        let text = self.quote_db.find_by_text(&session.quote_text).map_or(session.quote_text.as_str(), |q| q.text.as_str());
+        let hash = quotes::quote_hash(text);
+
+        let favorites = &mut self.config.preferences.favorite_quotes;
+        let now_favorite = match favorites.iter().position(|h| *h == hash) {
+            Some(i) => {
+                favorites.remove(i);
+                false
+            }
+            None => {
+                favorites.push(hash);
+                true
+            }
+        };
+
+        // Save into the file's own settings so command line overrides aren't written out
+        let mut on_disk = config::Config::load(&self.config_path).unwrap_or_else(|_| self.config.clone());
+        on_disk.preferences.favorite_quotes = self.config.preferences.favorite_quotes.clone();
+        let message = match on_disk.save(&self.config_path) {
+            Ok(()) if now_favorite => "Added to favorites".to_string(),
+            Ok(()) => "Removed from favorites".to_string(),
+            Err(e) => {
+                warn!("Failed to save favorites: {}", e);
+                "Favorites not saved, see log".to_string()
+            }
+        };
+        self.notice = Some((message, Instant::now()));
+        Some(now_favorite)
+    }
+
+    fn apply_config(&mut self, mut loaded: config::Config) {
+        self.config_overrides.apply(&mut loaded);
+        if loaded.ui.render_mode != self.config.ui.render_mode {
+            self.pending_render_mode = Some(loaded.ui.render_mode);
+            loaded.ui.render_mode = self.config.ui.render_mode;
         }
+        self.accumulated_stats.key_decay = loaded.preferences.key_decay;
+        self.config = loaded;
+    }
+
+    /// Fix the quote order so anyone using the same seed gets the same quotes
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+        self.quote_db.set_seed(seed);
+    }
+
+    /// Persist the accumulated stats if at least `interval` has passed since the last save.
+    /// Returns true if the stats were written.
+    pub fn autosave_if_due(&mut self, interval: Duration) -> bool {
+        if self.last_autosave.elapsed() < interval {
+            return false;
+        }
+        self.last_autosave = Instant::now();
+        match self.accumulated_stats.save_to_file(&self.stats_path) {
+            Ok(()) => {
+                // Totals are saved above, so old session files can go safely
+                let stats_dir = self.stats_path.parent().unwrap_or(Path::new("."));
+                if let Err(e) = AccumulatedStats::prune_directory_at(stats_dir, self.config.preferences.max_sessions) {
+                    warn!("Failed to prune stats directory: {}", e);
+                }
+                true
+            }
+            Err(e) => {
+                warn!("Failed to autosave stats: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Lesson the user is on, or `None` once the whole curriculum is passed
+    pub fn lesson_stage(&self) -> Option<LessonStage> {
+        curriculum::curriculum().get(self.config.preferences.lesson_stage).copied()
     }
 
     /// Load a new quote and prepare the session for typing
     fn load_quote(&mut self, text: Option<String>) {
-        let quote_text = match text {
-            Some(t) => t,
+        self.memorize_index = None;
+        if let Some(mode) = self.pending_render_mode.take() {
+            self.config.ui.render_mode = mode;
+        }
+        let text = text.or_else(|| self.paragraph_length.map(|count| self.next_paragraph(count)));
+        let (quote_text, quote_difficulty) = match text {
+            // Custom text has no label, so estimate its difficulty
+            Some(t) => {
+                let difficulty = QuoteDifficulty::classify(&t);
+                (t, difficulty)
+            }
             None => {
-                // Use a random quote based on user's difficulty setting
-                let difficulty = match self.config.preferences.difficulty {
-                    config::DifficultyLevel::Beginner => QuoteDifficulty::Easy,
-                    config::DifficultyLevel::Intermediate => QuoteDifficulty::Medium,
-                    config::DifficultyLevel::Advanced | config::DifficultyLevel::Expert => QuoteDifficulty::Hard,
-                };
-                
-                if let Some(quote) = self.quote_db.next_by_difficulty(difficulty) {
-                    info!("Selected quote: \"{}\" ({})", quote.text, quote.source);
-                    quote.text.clone()
+                let lesson = self.lesson_stage().filter(|_| self.game_state.current_game == GameType::Learn);
+                let lesson_quote = lesson.and_then(|stage| self.quote_db.next_in_category(stage.category));
+                let memorize_quote = if self.game_state.current_game == GameType::Memorize { self.next_memorize_quote() } else { None };
+
+                let quote = if let Some(quote) = lesson_quote {
+                    info!("Selected lesson quote: \"{}\" ({})", quote.text, quote.source);
+                    quote
+                } else if let Some(quote) = memorize_quote {
+                    info!("Selected quote to memorize: \"{}\" ({})", quote.text, quote.source);
+                    quote
                 } else {
-                    // Fallback to a random quote if no quote for the specific difficulty
-                    let quote = self.quote_db.next_random();
-                    info!("Selected random quote: \"{}\" ({})", quote.text, quote.source);
-                    quote.text.clone()
-                }
+                    self.next_filtered_quote()
+                };
+                (quote.text, quote.difficulty)
             }
         };
+        self.quote_difficulty = quote_difficulty;
+        let quote_text = input::expand_tabs(&quote_text);
         
         info!("Loading new quote: {}", quote_text);
         
@@ -94,8 +379,57 @@ impl SpringKeys {
         } else {
             self.typing_session = Some(TypingSession::new(quote_text));
         }
+        if let Some(session) = &mut self.typing_session {
+            session.metrics.wpm_mode = self.config.preferences.wpm_mode;
+            session.quote_metrics.wpm_mode = self.config.preferences.wpm_mode;
+        }
+        self.quote_baseline = QuoteBaseline::capture(self.typing_session.as_ref());
     }
     
+    /// Quote from the active category if one is selected, otherwise an unseen one when
+    /// preferred, then one of the difficulty for the user's level. The active origin
+    /// applies throughout.
+    fn next_filtered_quote(&mut self) -> Quote {
+        let category = self.quote_db.get_active_category();
+        let category_quote = if category == CategoryCycle::All { None } else { self.quote_db.next_by_category(category) };
+        if let Some(quote) = category_quote {
+            info!("Selected {} quote: \"{}\" ({})", category.name(), quote.text, quote.source);
+            return quote;
+        }
+        if let Some(quote) = self.prefer_unseen.then(|| self.quote_db.next_unseen(&self.accumulated_stats.completed_quotes).cloned()).flatten() {
+            info!("Selected unseen quote: \"{}\" ({})", quote.text, quote.source);
+            return quote;
+        }
+        // Use a random quote based on user's difficulty setting
+        let difficulty = self.config.quote_difficulty_for(self.config.preferences.difficulty);
+        if let Some(quote) = self.quote_db.next_by_difficulty(difficulty) {
+            info!("Selected quote: \"{}\" ({})", quote.text, quote.source);
+            return quote;
+        }
+        // Fallback to a random quote if no quote for the specific difficulty
+        let quote = self.quote_db.next_random();
+        info!("Selected random quote: \"{}\" ({})", quote.text, quote.source);
+        quote
+    }
+
+    /// Join `count` quotes, each picked like a single quote by `next_filtered_quote`, into
+    /// one long text for endurance practice
+    fn next_paragraph(&mut self, count: usize) -> String {
+        (0..count)
+            .map(|_| self.next_filtered_quote().text)
+            .collect::<Vec<_>>()
+            .join(PARAGRAPH_SEPARATOR)
+    }
+
+    /// Next short quote the memorize schedule wants reviewed, remembering its index
+    fn next_memorize_quote(&mut self) -> Option<Quote> {
+        let candidates = self.quote_db.short_quote_indices(srs::MEMORIZE_MAX_CHARS);
+        let now = chrono::Utc::now().timestamp();
+        let index = srs::next_quote(&self.accumulated_stats.srs_cards, &candidates, now)?;
+        self.memorize_index = Some(index);
+        self.quote_db.quote_at(index).cloned()
+    }
+
     pub fn start_typing_session(&mut self, text: Option<String>) {
         self.load_quote(text);
 
@@ -108,21 +442,186 @@ impl SpringKeys {
     }
     
     pub fn process_input(&mut self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        self.input_processor.stop_on_error = self.config.preferences.stop_on_error;
+        self.input_processor.backspace_penalty = self.config.preferences.backspace_penalty;
+        self.input_processor.respect_shift_only = self.config.preferences.respect_shift_only;
+
+        // In word-by-word mode Enter submits the word instead of reaching the input buffer
+        if code == KeyCode::Enter && self.game_state.current_game == GameType::WordByWord {
+            self.submit_word();
+            return true;
+        }
+
+        let counts_before = self.typing_session.as_ref().map(|s| (s.metrics.keystrokes, s.metrics.errors.len()));
+
         // Pass the typing session as a mutable reference to the input processor
         let mut_session = self.typing_session.as_mut();
         self.input_processor.process_key_event(code, modifiers, mut_session);
         self.input_processor.process_queued_events();
 
+        let counts_after = self.typing_session.as_ref().map(|s| (s.metrics.keystrokes, s.metrics.errors.len()));
+        if let (Some((keys_before, errors_before)), Some((keys_after, errors_after))) = (counts_before, counts_after) {
+            if keys_after > keys_before {
+                self.events.emit(&AppEvent::KeystrokeRecorded { code, correct: errors_after == errors_before });
+            }
+        }
+
         // Register key press for heatmap animation
         if let KeyCode::Char(c) = code {
             crate::ui::heatmap::register_key_press(c);
         }
 
+        self.check_progress();
+        true
+    }
+
+    /// Type `s` one character at a time through `process_input`, as scripted tests and
+    /// benchmarks do. Uppercase letters are sent with SHIFT. Returns how many quotes were completed.
+    pub fn feed_str(&mut self, s: &str) -> usize {
+        let completed_before = self.session_aggregate.quotes_completed;
+        for c in s.chars() {
+            let modifiers = if c.is_uppercase() { KeyModifiers::SHIFT } else { KeyModifiers::NONE };
+            self.process_input(KeyCode::Char(c), modifiers);
+        }
+        self.session_aggregate.quotes_completed - completed_before
+    }
+
+    /// Type `text` against the current quote, as `feed_str` does, and summarize just those
+    /// keystrokes, timed `SIMULATED_KEY_INTERVAL` apart
+    pub fn type_text(&mut self, text: &str) -> SessionSummary {
+        self.summarize_typing(|app| app.feed_str(text) > 0)
+    }
+
+    /// Like `type_text`, for whitespace-separated input tokens (`a`, `<space>`, `<bs>`, ...)
+    /// fed through `process_token`
+    pub fn type_tokens(&mut self, tokens: &str) -> SessionSummary {
+        self.summarize_typing(|app| {
+            let mut completed = false;
+            for token in tokens.split_whitespace() {
+                completed |= app.process_token(token);
+            }
+            completed
+        })
+    }
+
+    /// Summarize the keystrokes `feed` types, given whether it completed a quote, timed
+    /// `SIMULATED_KEY_INTERVAL` apart like `type_text`
+    pub fn summarize_typing(&mut self, feed: impl FnOnce(&mut Self) -> bool) -> SessionSummary {
+        let before = self.typed_counts();
+        let completed = feed(self);
+
+        let mut typed = SessionAggregate::new();
+        let after = self.typed_counts();
+        typed.keystrokes = after.keystrokes - before.keystrokes;
+        typed.correct_keystrokes = after.correct_keystrokes - before.correct_keystrokes;
+        typed.correct_spaces = after.correct_spaces - before.correct_spaces;
+        typed.errors = after.errors - before.errors;
+        typed.typing_time = SIMULATED_KEY_INTERVAL * typed.keystrokes as u32;
+
+        SessionSummary {
+            wpm: typed.wpm(self.config.preferences.wpm_mode),
+            accuracy: typed.accuracy(),
+            errors: typed.errors,
+            keystrokes: typed.keystrokes,
+            completed,
+        }
+    }
+
+    /// Counts for every completed quote plus the one in progress
+    fn typed_counts(&self) -> SessionAggregate {
+        let mut counts = self.session_aggregate.clone();
+        if let Some(session) = &self.typing_session {
+            let base = self.quote_baseline;
+            counts.keystrokes += session.metrics.keystrokes - base.keystrokes;
+            counts.correct_keystrokes += session.metrics.correct_keystrokes - base.correct_keystrokes;
+            counts.correct_spaces += session.metrics.correct_spaces - base.correct_spaces;
+            counts.errors += session.metrics.errors.len() - base.errors;
+        }
+        counts
+    }
+
+    /// Feed one automated input token (`a`, `<space>`, `<bs>`, ...) as if it were typed.
+    /// Returns true if the token completed the current quote.
+    pub fn process_token(&mut self, token: &str) -> bool {
+        self.input_processor.stop_on_error = self.config.preferences.stop_on_error;
+        self.input_processor.backspace_penalty = self.config.preferences.backspace_penalty;
+        self.input_processor.respect_shift_only = self.config.preferences.respect_shift_only;
+
+        let mut_session = self.typing_session.as_mut();
+        if !self.input_processor.process_token(token, mut_session) {
+            return false;
+        }
+        self.check_progress()
+    }
+
+    /// Submit the input as the current word in word-by-word mode. A correct word clears
+    /// the input for the next one; a wrong one counts as an error and stays for fixing.
+    /// Returns true if it was the quote's last word.
+    pub fn submit_word(&mut self) -> bool {
+        let Some(session) = &mut self.typing_session else {
+            return false;
+        };
+        if session.submit_word(&self.input_processor.current_text) {
+            self.input_processor.clear();
+            self.check_progress()
+        } else {
+            self.accumulated_stats.session_errors += 1;
+            self.accumulated_stats.total_errors += 1;
+            false
+        }
+    }
+
+    /// Close the results card. The next quote is already loaded, so its clock restarts
+    /// now rather than counting the time spent reading the card.
+    pub fn dismiss_results(&mut self) {
+        if self.pending_results.take().is_some() {
+            self.quote_baseline.started = Instant::now();
+            if let Some(session) = &mut self.typing_session {
+                session.start_time = Instant::now();
+            }
+        }
+    }
+
+    /// Erase the character before the cursor without recording a keystroke, as the UI does
+    pub fn backspace(&mut self) {
+        self.input_processor.backspace_penalty = self.config.preferences.backspace_penalty;
+        self.input_processor.backspace();
+        self.apply_backspace_penalties();
+    }
+
+    /// Count penalized backspaces as errors and take them off the quote's score
+    fn apply_backspace_penalties(&mut self) {
+        let penalties = self.input_processor.take_backspace_penalties();
+        if penalties == 0 {
+            return;
+        }
+        if let Some(session) = &mut self.typing_session {
+            session.penalize_backspaces(penalties);
+        }
+        self.accumulated_stats.session_errors += penalties;
+        self.accumulated_stats.total_errors += penalties;
+    }
+
+    /// Validate the input against the quote, update metrics, and move on to a new quote
+    /// once the current one is complete. Returns true if a quote was completed.
+    fn check_progress(&mut self) -> bool {
+        self.apply_backspace_penalties();
+        let word_by_word = self.game_state.current_game == GameType::WordByWord;
+        let by_sentence = self.game_state.current_game == GameType::SentenceBySentence;
         if let Some(session) = &mut self.typing_session {
-            let result = self.input_processor.validate_input(&session.quote_text);
+            // Word by word (or by sentence), the input only ever holds the current word or sentence
+            let expected = if word_by_word {
+                session.current_word().unwrap_or_default()
+            } else if by_sentence {
+                session.current_sentence().unwrap_or_default()
+            } else {
+                &session.quote_text
+            };
+            let result = self.input_processor.validate_input(expected);
+            let sentence_done = by_sentence && !expected.is_empty() && self.input_processor.current_text == expected;
             
-            // Check if this input resulted in an error
-            if !result.is_valid {
+            // Check if this input resulted in an error (or a strict-mode rejection)
+            if !result.is_valid || self.input_processor.take_rejection() {
                 // Increment both session and total error counts immediately
                 self.accumulated_stats.session_errors += 1;
                 self.accumulated_stats.total_errors += 1;
@@ -130,21 +629,174 @@ impl SpringKeys {
             
             self.input_processor.update_error_state(&result);
             session.calculate_metrics();
+            if sentence_done {
+                session.advance_sentence(self.config.preferences.sentence_skip_spaces);
+                self.input_processor.clear();
+            }
 
             // Start a new typing session if the current text matches the expected text
-            if result.is_valid && self.input_processor.current_text.len() == session.quote_text.len() {
+            let is_complete = if word_by_word {
+                session.current_word().is_none()
+            } else if by_sentence {
+                session.current_sentence().is_none()
+            } else if self.config.preferences.require_exact_completion {
+                self.input_processor.current_text == session.quote_text
+            } else {
+                result.is_valid && self.input_processor.current_text.len() == session.quote_text.len()
+            };
+            if is_complete {
                 // Update accumulated stats before starting new session
-                self.accumulated_stats.update_from_session(session);
-                self.start_typing_session(None);
+                let base = self.quote_baseline;
+                let quote_totals = base.totals(session);
+                self.accumulated_stats.update_from_quote(session, quote_totals);
+
+                self.session_aggregate.record_quote(quote_totals, base.started.elapsed());
+                self.last_sparkline = Some(session.quote_metrics.render_sparkline(SPARKLINE_WIDTH));
+                self.last_rhythm = Some(session.quote_metrics.rhythm_consistency());
+                if word_by_word {
+                    self.last_word_wpms = session.word_wpms.clone();
+                }
+                if self.show_results_card {
+                    self.pending_results = Some(session.quote_metrics.clone());
+                }
+
+                // In repeat mode the same quote comes back until this attempt is accurate enough
+                let repeat_text = if self.repeat_until_passed {
+                    let attempt_accuracy = quote_totals.accuracy();
+                    let target = self.config.preferences.goal_accuracy.unwrap_or(DEFAULT_REPEAT_ACCURACY);
+                    (attempt_accuracy < target).then(|| session.quote_text.clone())
+                } else {
+                    None
+                };
+
+                // Events and goals report this quote alone, not the earlier quotes of the session
+                self.events.emit(&AppEvent::QuoteCompleted { wpm: quote_totals.wpm, accuracy: quote_totals.accuracy() });
+                let prefs = &self.config.preferences;
+                if let Some(result) = GoalResult::evaluate(prefs.goal_wpm, prefs.goal_accuracy, quote_totals.wpm, quote_totals.accuracy()) {
+                    info!("Goal result: {:?}", result);
+                    self.last_goal = Some((result, Instant::now()));
+                    if result.is_met() {
+                        self.events.emit(&AppEvent::GoalMet(result));
+                    }
+                }
+                if self.game_state.current_game == GameType::Learn {
+                    self.record_lesson_result(quote_totals.wpm, quote_totals.accuracy());
+                }
+                self.unlock_earned_categories();
+                if let Some(index) = self.memorize_index {
+                    let now = chrono::Utc::now().timestamp();
+                    // Graded on this quote alone, not on the earlier quotes of the session
+                    srs::record_review(&mut self.accumulated_stats.srs_cards, index, quote_totals.wpm, quote_totals.accuracy(), now);
+                }
+
+                let previous_difficulty = self.quote_difficulty;
+                self.start_typing_session(repeat_text);
+                if self.quote_difficulty != previous_difficulty {
+                    self.events.emit(&AppEvent::DifficultyChanged { from: previous_difficulty, to: self.quote_difficulty });
+                }
+                return true;
             }
         }
-        true
+        false
     }
     
+    /// Hold back the quotes of categories the user hasn't unlocked yet, returning how many
+    pub fn lock_categories(&mut self) -> usize {
+        self.quote_db.lock_categories(&self.accumulated_stats, &self.config.preferences.unlocked_categories)
+    }
+
+    /// Unlock every locked category the accumulated stats now earn, remembering it in the
+    /// config and announcing it in the header
+    fn unlock_earned_categories(&mut self) {
+        for unlock in quotes::LOCKED_CATEGORIES {
+            let unlocked = &mut self.config.preferences.unlocked_categories;
+            if !unlock.is_earned(&self.accumulated_stats) || unlocked.iter().any(|name| name.eq_ignore_ascii_case(unlock.category)) {
+                continue;
+            }
+            info!("Category {} unlocked", unlock.category);
+            unlocked.push(unlock.category.to_string());
+            self.quote_db.unlock_category(&unlock);
+            self.notice = Some((format!("Unlocked {} quotes!", unlock.category), Instant::now()));
+            // Save into the file's own settings so command line overrides aren't written out
+            let mut on_disk = config::Config::load(&self.config_path).unwrap_or_else(|_| self.config.clone());
+            on_disk.preferences.unlocked_categories = self.config.preferences.unlocked_categories.clone();
+            if let Err(e) = on_disk.save(&self.config_path) {
+                warn!("Failed to save unlocked categories: {}", e);
+            }
+        }
+    }
+
+    /// Move on to the next lesson if this result passes the current one, and remember it in the config
+    fn record_lesson_result(&mut self, wpm: f64, accuracy: f64) {
+        let stage = self.config.preferences.lesson_stage;
+        let next = curriculum::advance(stage, wpm, accuracy);
+        if next != stage {
+            info!("Lesson {} passed, advancing to lesson {}", stage + 1, next + 1);
+            self.config.preferences.lesson_stage = next;
+            // Save into the file's own settings so command line overrides aren't written out
+            let mut on_disk = config::Config::load(&self.config_path).unwrap_or_else(|_| self.config.clone());
+            on_disk.preferences.lesson_stage = next;
+            if let Err(e) = on_disk.save(&self.config_path) {
+                warn!("Failed to save lesson progress: {}", e);
+            }
+        }
+    }
+
+    /// WPM for the quote currently being typed, from its own keystrokes only
+    pub fn current_quote_wpm(&self) -> f64 {
+        self.typing_session.as_ref().map_or(0.0, |session| self.quote_baseline.totals(session).wpm)
+    }
+
+    /// Goal outcome of the most recently completed quote, if any goals are set
+    pub fn last_goal_result(&self) -> Option<GoalResult> {
+        self.last_goal.map(|(result, _)| result)
+    }
+
+    /// Switch the quote pool to `category` and load a quote from it. A category without any
+    /// loaded quotes, or with only locked ones, is refused with a notice, keeping the current
+    /// category and quote.
+    pub fn select_category(&mut self, category: CategoryCycle) -> bool {
+        if category != CategoryCycle::All && self.quote_db.count_in_category(category) == 0 {
+            let message = if self.quote_db.locked_in_category(category) > 0 {
+                format!("{} quotes are locked", category.name())
+            } else {
+                format!("No quotes in {} category", category.name())
+            };
+            warn!("{}", message);
+            self.notice = Some((message, Instant::now()));
+            return false;
+        }
+        self.quote_db.set_active_category(category);
+        self.start_typing_session(None);
+        true
+    }
+
+    /// Message the UI should show for a few seconds, if one was raised recently
+    pub fn notice(&self) -> Option<&str> {
+        match &self.notice {
+            Some((message, at)) if at.elapsed() < NOTICE_DURATION => Some(message),
+            _ => None,
+        }
+    }
+
+    /// Whether the goals were just met and the UI should show its banner
+    pub fn goal_banner_active(&self) -> bool {
+        matches!(self.last_goal, Some((result, at)) if result.is_met() && at.elapsed() < GOAL_BANNER_DURATION)
+    }
+
     pub fn get_heat_map(&self) -> Option<HashMap<char, f64>> {
         self.typing_session.as_ref().map(|session| session.metrics.get_heat_map())
     }
     
+    /// Export the heat map as JSON, from the live session if there is one,
+    /// otherwise from the accumulated all-time key averages
+    pub fn heatmap_to_json(&self) -> String {
+        match &self.typing_session {
+            Some(session) => session.metrics.heatmap_to_json(),
+            None => self.accumulated_stats.heatmap_to_json(),
+        }
+    }
+
     pub fn get_finger_performance(&self) -> Option<&HashMap<Finger, ExtendedStats>> {
         self.typing_session.as_ref().map(|session| session.metrics.finger_performance())
     }
@@ -1,35 +1,223 @@
-use log::{LevelFilter, SetLoggerError};
+use chrono::NaiveDate;
+use log::{LevelFilter, Record, SetLoggerError};
 use log4rs::{
     append::{
         file::FileAppender,
+        Append,
     },
     config::{Appender, Config, Root},
-    encode::pattern::PatternEncoder,
+    encode::{pattern::PatternEncoder, writer::simple::SimpleWriter, Encode},
     filter::threshold::ThresholdFilter,
 };
-use std::path::Path;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// Environment variable read for a level override, in `RUST_LOG` style
+pub const LOG_LEVEL_ENV_VAR: &str = "RUST_LOG";
+/// Log target of this crate, for `RUST_LOG=spring_keys=debug` style directives
+const LOG_TARGET: &str = "spring_keys";
+/// Default cap on the combined size of the daily log files
+pub const DEFAULT_MAX_LOG_BYTES: u64 = 10 * 1024 * 1024;
+/// Base log file the app writes daily logs next to
+pub const DEFAULT_LOG_FILE: &str = "logs/spring-keys.log";
+/// Format of each log line
+const LOG_PATTERN: &str = "[{d(%Y-%m-%d %H:%M:%S)}] [{l}] - {m}\n";
+
+/// Settings for `init_logger_with`
+#[derive(Debug, Clone)]
+pub struct LoggerOptions {
+    /// Level used when no override applies
+    pub level: LevelFilter,
+    /// Let `RUST_LOG` override `level`
+    pub level_from_env: bool,
+    /// Base log file; each day logs to a dated file next to it, e.g. `app-2024-05-01.log`
+    pub log_file: Option<PathBuf>,
+    /// Oldest daily files are deleted whenever a new day's file is started until all of
+    /// them fit in this many bytes
+    pub max_total_bytes: u64,
+}
+
+impl Default for LoggerOptions {
+    fn default() -> Self {
+        Self {
+            level: LevelFilter::Info,
+            level_from_env: true,
+            log_file: None,
+            max_total_bytes: DEFAULT_MAX_LOG_BYTES,
+        }
+    }
+}
+
+impl LoggerOptions {
+    /// `level`, unless `level_from_env` is set and `RUST_LOG` names a level
+    pub fn effective_level(&self) -> LevelFilter {
+        if !self.level_from_env {
+            return self.level;
+        }
+        std::env::var(LOG_LEVEL_ENV_VAR)
+            .ok()
+            .and_then(|value| parse_level_directives(&value))
+            .unwrap_or(self.level)
+    }
+}
+
+/// Level for this crate from comma-separated `RUST_LOG` directives. A `spring_keys=LEVEL`
+/// directive wins over a bare `LEVEL`; directives for other crates are ignored.
+pub fn parse_level_directives(value: &str) -> Option<LevelFilter> {
+    let mut bare = None;
+    for directive in value.split(',').map(str::trim) {
+        match directive.split_once('=') {
+            Some((target, level)) if target.trim() == LOG_TARGET => return LevelFilter::from_str(level.trim()).ok(),
+            Some(_) => {}
+            None => bare = bare.or_else(|| LevelFilter::from_str(directive).ok()),
+        }
+    }
+    bare
+}
+
+/// Dated file the logger writes to today for the base path `log_file`
+pub fn daily_log_path(log_file: &Path, date: chrono::NaiveDate) -> PathBuf {
+    let stem = log_file.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let name = match log_file.extension() {
+        Some(ext) => format!("{}-{}.{}", stem, date.format("%Y-%m-%d"), ext.to_string_lossy()),
+        None => format!("{}-{}", stem, date.format("%Y-%m-%d")),
+    };
+    log_file.with_file_name(name)
+}
+
+/// Delete the oldest daily files of `log_file` until their total size is at most `max_bytes`.
+/// `keep` (today's file) is never deleted. Returns how many files were removed.
+pub fn prune_daily_logs(log_file: &Path, keep: &Path, max_bytes: u64) -> io::Result<usize> {
+    let dir = match log_file.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let prefix = format!("{}-", log_file.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default());
+    let extension = log_file.extension();
+
+    let mut logs: Vec<(PathBuf, u64)> = Vec::new();
+    for entry in fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        let is_daily = path.file_name().is_some_and(|name| name.to_string_lossy().starts_with(&prefix))
+            && path.extension() == extension;
+        if is_daily && path.is_file() {
+            logs.push((path, entry.metadata()?.len()));
+        }
+    }
+    // Dates in the names sort oldest first
+    logs.sort();
+
+    let mut total: u64 = logs.iter().map(|(_, len)| len).sum();
+    let mut removed = 0;
+    for (path, len) in logs {
+        if total <= max_bytes {
+            break;
+        }
+        if path.file_name() == keep.file_name() {
+            continue;
+        }
+        fs::remove_file(&path)?;
+        total -= len;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// Appender writing to the dated file of a base path. When the date changes while the
+/// app runs it moves on to the new day's file and prunes the old ones.
+#[derive(Debug)]
+struct DailyFileAppender {
+    base: PathBuf,
+    max_total_bytes: u64,
+    encoder: PatternEncoder,
+    /// Date of the open file and the file itself
+    current: Mutex<Option<(NaiveDate, BufWriter<File>)>>,
+}
+
+impl DailyFileAppender {
+    fn new(base: &Path, max_total_bytes: u64) -> Self {
+        Self {
+            base: base.to_path_buf(),
+            max_total_bytes,
+            encoder: PatternEncoder::new(LOG_PATTERN),
+            current: Mutex::new(None),
+        }
+    }
+
+    /// Write `record` to the file for `date`, opening it first if it isn't the current one
+    fn append_on(&self, record: &Record, date: NaiveDate) -> anyhow::Result<()> {
+        let mut current = self.current.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if current.as_ref().map(|(day, _)| *day) != Some(date) {
+            if let Some((_, mut old)) = current.take() {
+                old.flush()?;
+            }
+            let path = daily_log_path(&self.base, date);
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs::create_dir_all(parent)?;
+            }
+            let file = OpenOptions::new().create(true).append(true).open(&path)?;
+            if let Err(e) = prune_daily_logs(&self.base, &path, self.max_total_bytes) {
+                eprintln!("Failed to prune old log files: {}", e);
+            }
+            *current = Some((date, BufWriter::new(file)));
+        }
+        let (_, file) = current.as_mut().expect("log file opened above");
+        self.encoder.encode(&mut SimpleWriter(&mut *file), record)?;
+        file.flush()?;
+        Ok(())
+    }
+}
+
+impl Append for DailyFileAppender {
+    fn append(&self, record: &Record) -> anyhow::Result<()> {
+        self.append_on(record, chrono::Local::now().date_naive())
+    }
+
+    fn flush(&self) {
+        if let Some((_, file)) = self.current.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).as_mut() {
+            let _ = file.flush();
+        }
+    }
+}
 
 /// Initialize the application logger
 pub fn init_logger<P: AsRef<Path>>(
     log_level: LevelFilter,
     log_file: Option<P>,
 ) -> Result<(), SetLoggerError> {
+    let appender = log_file.map(|path| -> Box<dyn Append> {
+        Box::new(
+            FileAppender::builder()
+                .encoder(Box::new(PatternEncoder::new(LOG_PATTERN)))
+                .build(path)
+                .expect("Failed to create log file appender"),
+        )
+    });
+    build_logger(log_level, appender)
+}
+
+/// Initialize the logger with an env level override and daily rotated log files
+pub fn init_logger_with(options: &LoggerOptions) -> Result<(), SetLoggerError> {
+    let appender = options
+        .log_file
+        .as_ref()
+        .map(|base| -> Box<dyn Append> { Box::new(DailyFileAppender::new(base, options.max_total_bytes)) });
+    build_logger(options.effective_level(), appender)
+}
+
+fn build_logger(log_level: LevelFilter, appender: Option<Box<dyn Append>>) -> Result<(), SetLoggerError> {
     let mut config_builder = Config::builder();
     let mut root_builder = Root::builder();
 
     // Only add file logging, no stdout
-    if let Some(log_file_path) = log_file {
-        let file = FileAppender::builder()
-            .encoder(Box::new(PatternEncoder::new(
-                "[{d(%Y-%m-%d %H:%M:%S)}] [{l}] - {m}\n",
-            )))
-            .build(log_file_path)
-            .expect("Failed to create log file appender");
-
+    if let Some(appender) = appender {
         config_builder = config_builder.appender(
             Appender::builder()
                 .filter(Box::new(ThresholdFilter::new(log_level)))
-                .build("file", Box::new(file)),
+                .build("file", appender),
         );
 
         root_builder = root_builder.appender("file");
@@ -41,4 +229,41 @@ pub fn init_logger<P: AsRef<Path>>(
 
     log4rs::init_config(config)?;
     Ok(())
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_daily_appender_moves_to_a_new_file_when_the_date_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("logs").join("spring-keys.log");
+        let appender = DailyFileAppender::new(&base, DEFAULT_MAX_LOG_BYTES);
+        let first = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        let second = NaiveDate::from_ymd_opt(2024, 5, 2).unwrap();
+
+        appender.append_on(&Record::builder().args(format_args!("before midnight")).build(), first).unwrap();
+        appender.append_on(&Record::builder().args(format_args!("after midnight")).build(), second).unwrap();
+
+        let first_log = fs::read_to_string(daily_log_path(&base, first)).unwrap();
+        let second_log = fs::read_to_string(daily_log_path(&base, second)).unwrap();
+        assert!(first_log.contains("before midnight") && !first_log.contains("after midnight"));
+        assert!(second_log.contains("after midnight") && !second_log.contains("before midnight"));
+    }
+
+    #[test]
+    fn test_daily_appender_prunes_when_the_date_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("spring-keys.log");
+        let appender = DailyFileAppender::new(&base, 1);
+        let first = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        let second = NaiveDate::from_ymd_opt(2024, 5, 2).unwrap();
+
+        appender.append_on(&Record::builder().args(format_args!("old")).build(), first).unwrap();
+        appender.append_on(&Record::builder().args(format_args!("new")).build(), second).unwrap();
+
+        assert!(!daily_log_path(&base, first).exists(), "Yesterday's log is over the cap and goes");
+        assert!(daily_log_path(&base, second).exists());
+    }
+}
@@ -1,142 +1,32 @@
-mod core;
-mod input;
-mod ui;
-mod config;
-mod logger;
-mod quotes;
-mod help;
-mod vga_test;
-mod moosesay;
-
-use std::path::PathBuf;
 use std::env;
+use std::path::{Path, PathBuf};
 use log::{info, LevelFilter};
-use std::collections::HashMap;
 use std::io::{self, IsTerminal};
 use std::time::Duration;
 use std::thread;
-use crossterm::event::{poll, read, Event, KeyCode, KeyModifiers};
-use crossterm::terminal::{enable_raw_mode, disable_raw_mode};
-
-use crate::core::{TypingSession, TypingError};
-use crate::core::metrics::{TypingMetrics, Finger, ExtendedStats};
-use crate::core::state::{GameState, GameType, GameStatus};
-use crate::core::stats::AccumulatedStats;
-use crate::input::InputProcessor;
-use crate::config::{Config, DifficultyLevel};
-use crate::quotes::{Quote, QuoteDatabase, QuoteDifficulty, CategoryCycle};
-use crate::ui::TerminalUI;
-
-#[derive(Debug)]
-pub struct SpringKeys {
-    pub game_state: GameState,
-    pub input_processor: InputProcessor,
-    pub typing_session: Option<TypingSession>,
-    pub config: Config,
-    pub quote_db: QuoteDatabase,
-    pub accumulated_stats: AccumulatedStats,
-}
-
-impl SpringKeys {
-    pub fn new() -> Self {
-        // Load configuration or create default
-        let config_path = PathBuf::from(config::DEFAULT_CONFIG_FILE);
-        let config = Config::load_or_default(config_path);
-        
-        // Load accumulated stats from the stats directory
-        info!("Loading accumulated statistics from stats directory...");
-        let accumulated_stats = AccumulatedStats::load_from_directory();
-        info!("Loaded stats from {} quotes", accumulated_stats.total_quotes);
-        
-        Self {
-            game_state: GameState::default(),
-            input_processor: InputProcessor::new(),
-            typing_session: None,
-            config,
-            quote_db: QuoteDatabase::new(),
-            accumulated_stats,
-        }
-    }
+use crossterm::event::{poll, read, Event, KeyCode};
 
-    pub fn start_typing_session(&mut self, text: Option<String>) {
-        let quote_text = match text {
-            Some(t) => t,
-            None => {
-                // Use a random quote based on user's difficulty setting
-                let difficulty = match self.config.preferences.difficulty {
-                    DifficultyLevel::Beginner => QuoteDifficulty::Easy,
-                    DifficultyLevel::Intermediate => QuoteDifficulty::Medium,
-                    DifficultyLevel::Advanced | DifficultyLevel::Expert => QuoteDifficulty::Hard,
-                };
-                
-                if let Some(quote) = self.quote_db.next_by_difficulty(difficulty) {
-                    info!("Selected quote: \"{}\" ({})", quote.text, quote.source);
-                    quote.text.clone()
-                } else {
-                    // Fallback to a random quote if no quote for the specific difficulty
-                    let quote = self.quote_db.next_random();
-                    info!("Selected random quote: \"{}\" ({})", quote.text, quote.source);
-                    quote.text.clone()
-                }
-            }
-        };
-        
-        info!("Starting typing session with text: {}", quote_text);
-        if let Some(session) = &mut self.typing_session {
-            // If we already have a session, just load the new quote
-            session.load_new_quote(quote_text);
-        } else {
-            // Create a new session if we don't have one
-            self.typing_session = Some(TypingSession::new(quote_text));
-        }
-        self.input_processor.clear();
-    }
-
-    pub fn process_input(&mut self, key: KeyCode, modifiers: KeyModifiers) {
-        // Pass the typing session as a mutable reference to the input processor
-        let mut_session = self.typing_session.as_mut();
-        self.input_processor.process_key_event(key, modifiers, mut_session);
-        self.input_processor.process_queued_events();
-
-        if let Some(session) = &mut self.typing_session {
-            let result = self.input_processor.validate_input(&session.quote_text);
-            self.input_processor.update_error_state(&result);
-            session.calculate_metrics();
-
-            // Start a new typing session if the current text matches the expected text
-            if result.is_valid && self.input_processor.current_text.len() == session.quote_text.len() {
-                // Update accumulated stats before starting new session
-                self.accumulated_stats.update_from_session(session);
-                self.start_typing_session(None);
-            }
-        }
-    }
+use spring_keys::{check, config, help, logger, moosesay, output, quotes, vga_test};
+use spring_keys::presets::Preset;
+use spring_keys::ui::caps::ColorMode;
+use spring_keys::ui::frame_rate;
+use spring_keys::core::metrics::{diff_key_averages, key_averages_from_json};
+use spring_keys::config::{ConfigOverrides, RenderMode};
+use spring_keys::{CategoryCycle, DifficultyLevel, GameType, InputProcessor, KeyboardLayout, QuoteDifficulty, SpringKeys, TerminalUI, STATS_DIR};
 
-    pub fn change_game(&mut self, game_type: GameType) {
-        info!("Changing game type to {:?}", game_type);
-        self.game_state = GameState::new(game_type);
-        self.typing_session = None;
-        self.input_processor.clear();
-    }
-    
-    pub fn get_heat_map(&self) -> Option<HashMap<char, f64>> {
-        self.typing_session.as_ref().map(|session| session.metrics.get_heat_map())
-    }
-    
-    pub fn get_finger_performance(&self) -> Option<&HashMap<Finger, ExtendedStats>> {
-        self.typing_session.as_ref().map(|session| session.metrics.finger_performance())
-    }
+const DEFAULT_HEATMAP_EXPORT_FILE: &str = "heatmap.json";
+const COMPARE_TOP_KEYS: usize = 5;
+/// Width of the longest bar in `stats byhour`
+const HOURLY_CHART_WIDTH: usize = 40;
+/// When set, consume mode prints final stats as JSON and exits instead of opening the UI
+const TEST_MODE_ENV_VAR: &str = "SPRING_KEYS_TEST_MODE";
 
-    pub fn get_averages(&self) -> Option<(f64, f64)> {
-        // If we have a current session, use its stats
-        if let Some(session) = &self.typing_session {
-            Some(session.get_averages())
-        } else {
-            // Otherwise use accumulated stats
-            Some((self.accumulated_stats.avg_wpm, self.accumulated_stats.avg_accuracy))
-        }
-    }
-}
+/// `single` mode exit codes: the quote was completed
+const SINGLE_EXIT_COMPLETED: i32 = 0;
+/// `single` mode exit codes: the input ran out before the quote was completed
+const SINGLE_EXIT_INCOMPLETE: i32 = 1;
+/// `single` mode exit codes: `--timeout` expired before the quote was completed
+const SINGLE_EXIT_TIMEOUT: i32 = 2;
 
 fn parse_difficulty(arg: &str) -> Option<QuoteDifficulty> {
     match arg.to_lowercase().as_str() {
@@ -147,39 +37,48 @@ fn parse_difficulty(arg: &str) -> Option<QuoteDifficulty> {
     }
 }
 
-fn run_consume_mode(app: &mut SpringKeys, input_sequence: Option<&str>) -> io::Result<()> {
+fn run_consume_mode(app: &mut SpringKeys, input_sequence: Option<&str>, color_mode: Option<ColorMode>) -> io::Result<()> {
     // Set demo heatmap to ensure the visualization works
     std::env::set_var("SPRING_KEYS_DEMO_HEATMAP", "1");
-    
-    // Initialize and run the UI
-    let mut ui = TerminalUI::new()?;
-    ui.init()?;
-    
+
     // Process input sequence if provided
     if let Some(input_text) = input_sequence {
         info!("Processing input sequence in consume mode: {}", input_text);
-        
-        // Start a new typing session with the input text
-        app.start_typing_session(Some(input_text.to_string()));
-        
-        // Process input
-        for c in input_text.chars() {
-            if let Some(_) = &mut app.typing_session {
-                // Process the character
-                app.process_input(KeyCode::Char(c), KeyModifiers::NONE);
-            }
+
+        // The quote is whatever the tokens type out, so replaying them completes it
+        app.start_typing_session(Some(InputProcessor::text_from_tokens(input_text)));
+
+        let summary = app.type_tokens(input_text);
+
+        // Headless runs report the results instead of showing the UI
+        if env::var(TEST_MODE_ENV_VAR).is_ok() {
+            println!("{}", serde_json::json!({
+                "tokens": input_text.split_whitespace().count(),
+                "keystrokes": summary.keystrokes,
+                "wpm": summary.wpm,
+                "accuracy": summary.accuracy,
+            }));
+            return Ok(());
         }
     }
 
+    // Initialize and run the UI
+    let mut ui = TerminalUI::new()?;
+    if let Some(mode) = color_mode {
+        ui.set_color_mode(mode);
+    }
+    ui.set_alternate_screen(app.config.ui.alternate_screen);
+    ui.init()?;
+
     // Main consume-mode loop (quit on ESC)
     while !ui.should_quit() {
         ui.render_frame(app)?;
         // Check for ESC key to exit
         if poll(Duration::from_millis(100))? {
-            if let Event::Key(key_event) = read()? {
-                if key_event.code == KeyCode::Esc {
-                    break;
-                }
+            match read()? {
+                Event::Key(key_event) if key_event.code == KeyCode::Esc => break,
+                Event::Resize(width, height) => ui.handle_resize(width, height)?,
+                _ => {}
             }
         }
         thread::sleep(Duration::from_millis(100));
@@ -188,6 +87,97 @@ fn run_consume_mode(app: &mut SpringKeys, input_sequence: Option<&str>) -> io::R
     ui.cleanup()
 }
 
+fn load_key_averages(path: &str) -> io::Result<std::collections::HashMap<char, f64>> {
+    let content = std::fs::read_to_string(path)?;
+    key_averages_from_json(&content).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("{} is not a metrics or heatmap JSON file", path))
+    })
+}
+
+fn run_compare(old_path: &str, new_path: &str) -> io::Result<()> {
+    let baseline = load_key_averages(old_path)?;
+    let current = load_key_averages(new_path)?;
+
+    let mut deltas: Vec<(char, f64)> = diff_key_averages(&current, &baseline).into_iter().collect();
+    if deltas.is_empty() {
+        println!("No keys in common between {} and {}", old_path, new_path);
+        return Ok(());
+    }
+    deltas.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    println!("Most improved keys:");
+    for (key, delta) in deltas.iter().filter(|(_, d)| *d < 0.0).take(COMPARE_TOP_KEYS) {
+        println!("  {:?}  {:+.1}ms", key, delta);
+    }
+    println!("Most regressed keys:");
+    for (key, delta) in deltas.iter().rev().filter(|(_, d)| *d > 0.0).take(COMPARE_TOP_KEYS) {
+        println!("  {:?}  {:+.1}ms", key, delta);
+    }
+    Ok(())
+}
+
+/// Type one quote headlessly from a token sequence (or stdin, one token sequence per line)
+/// and return the process exit code
+fn run_single_mode(app: &mut SpringKeys, text: Option<String>, input: Option<&str>, timeout: Option<Duration>, quiet_mode: bool) -> i32 {
+    app.start_typing_session(text);
+    let quote = app.typing_session.as_ref().map(|s| s.quote_text.clone()).unwrap_or_default();
+    if !quiet_mode {
+        println!("Quote: {}", quote);
+    }
+
+    let deadline = timeout.map(|t| std::time::Instant::now() + t);
+    let timed_out = || deadline.is_some_and(|d| std::time::Instant::now() >= d);
+
+    // Tokens come from --input, or else from stdin lines
+    let lines: Box<dyn Iterator<Item = String>> = match input {
+        Some(input) => Box::new(std::iter::once(input.to_string())),
+        None => {
+            let (tx, rx) = std::sync::mpsc::channel();
+            thread::spawn(move || {
+                for line in io::stdin().lines().map_while(Result::ok) {
+                    if tx.send(line).is_err() {
+                        break;
+                    }
+                }
+            });
+            Box::new(std::iter::from_fn(move || match deadline {
+                Some(d) => rx.recv_timeout(d.saturating_duration_since(std::time::Instant::now())).ok(),
+                None => rx.recv().ok(),
+            }))
+        }
+    };
+
+    // Summarized here rather than read back afterwards, when the next quote has already loaded
+    let mut code = SINGLE_EXIT_INCOMPLETE;
+    let summary = app.summarize_typing(|app| {
+        for line in lines {
+            for token in line.split_whitespace() {
+                if timed_out() {
+                    code = SINGLE_EXIT_TIMEOUT;
+                    return false;
+                }
+                if app.process_token(token) {
+                    code = SINGLE_EXIT_COMPLETED;
+                    return true;
+                }
+            }
+        }
+        if timed_out() {
+            code = SINGLE_EXIT_TIMEOUT;
+        }
+        false
+    });
+
+    if !quiet_mode {
+        match code {
+            SINGLE_EXIT_COMPLETED => println!("Completed! WPM: {:.1} | Accuracy: {:.1}%", summary.wpm, summary.accuracy),
+            SINGLE_EXIT_TIMEOUT => println!("Timed out before the quote was completed"),
+            _ => println!("Input ended before the quote was completed"),
+        }
+    }
+    code
+}
+
 fn main() -> std::io::Result<()> {
     // Parse command line arguments
     let args: Vec<String> = env::args().collect();
@@ -196,9 +186,34 @@ fn main() -> std::io::Result<()> {
     let mut verbose_mode = false;
     let mut command = None;
     let mut demo_heatmap = true;  // Default to demo heatmap enabled for better visual experience
-    let mut consume_input = None; // Input for consume mode
+    let mut consume_input = None; // Token input for consume and single modes
     let mut force_non_interactive = false; // New flag for non-interactive mode
     let mut duration = None; // Duration for screensaver mode
+    let mut subcommand = None; // Subcommand, e.g. `heatmap export`
+    let mut search_query = None; // Search text for `quote find`
+    let mut out_path = None; // Output file for export commands
+    let mut category = None; // Restrict quotes to a single category
+    let mut compare_files = Vec::new(); // Old and new files for `compare`
+    let mut origin = None; // Restrict quotes to a single origin, e.g. English
+    let mut preset = None; // Fixed practice text instead of a random quote
+    let mut custom_text = None; // Custom quote text for single mode or `quote add`
+    let mut quote_source = None; // Source of the quote added by `quote add`
+    let mut category_name = None; // Category as typed, which `quote add` takes verbatim
+    let mut timeout = None; // Time limit for single mode
+    let mut blind_mode = false; // Hide typed input
+    let mut minimal = false; // Draw only the quote, input and one stat line
+    let mut no_altscreen = false; // Draw inline so the last frame stays in scrollback
+    let mut seed = None; // Seed for a reproducible quote order
+    let mut color_mode = None; // Override the detected terminal color mode
+    let mut fps = None; // Frame rate for animated screens
+    let mut paragraph = None; // Number of quotes joined into each practice session
+    let mut max_sessions = None; // Cap on session files kept in the stats directory
+    let mut all_quotes = false; // Practice with quotes the keyboard can't fully type too
+    let mut favorites = false; // Practice only quotes bookmarked with F9
+    let mut unlock_all = false; // Practice with categories not unlocked yet too
+    let mut unseen = false; // Prefer quotes that haven't been completed yet
+    let mut repeat = false; // Retype each quote until it is accurate enough
+    let mut json_output = false; // Machine-readable output for non-interactive commands
     
     let mut i = 1;
     while i < args.len() {
@@ -230,19 +245,191 @@ fn main() -> std::io::Result<()> {
                     i += 1;
                 }
             },
+            "-c" | "--category" => {
+                if i + 1 < args.len() {
+                    category = CategoryCycle::from_name(&args[i + 1]);
+                    category_name = Some(args[i + 1].clone());
+                    // `quote add` can target any category file, not just the cycled ones
+                    let adding = command.as_deref() == Some("quote") && subcommand.as_deref() == Some("add");
+                    if category.is_none() && !adding {
+                        match quotes::locked_category(&args[i + 1]) {
+                            // Locked categories can't be picked, so say how their quotes come up instead
+                            Some(unlock) => eprintln!(
+                                "{} quotes are locked until you {}, then they're mixed into practice (or right away with --unlock-all). --category takes one of: {}",
+                                unlock.category,
+                                unlock.requirement(),
+                                CategoryCycle::valid_names()
+                            ),
+                            None => eprintln!("Unknown category '{}'. Use one of: {}", args[i + 1], CategoryCycle::valid_names()),
+                        }
+                        return Ok(());
+                    }
+                    i += 1;
+                }
+            },
+            "--preset" => {
+                if i + 1 < args.len() {
+                    preset = Preset::from_name(&args[i + 1]);
+                    if preset.is_none() {
+                        eprintln!("Unknown preset '{}'. Use one of: {}", args[i + 1], Preset::valid_names());
+                        return Ok(());
+                    }
+                    i += 1;
+                }
+            },
+            "--input" => {
+                if i + 1 < args.len() {
+                    consume_input = Some(args[i + 1].clone());
+                    i += 1;
+                }
+            },
+            s if s.starts_with("--input=") => {
+                consume_input = Some(s["--input=".len()..].to_string());
+            },
+            "--text" => {
+                if i + 1 < args.len() {
+                    custom_text = Some(args[i + 1].clone());
+                    i += 1;
+                }
+            },
+            "--source" => {
+                if i + 1 < args.len() {
+                    quote_source = Some(args[i + 1].clone());
+                    i += 1;
+                }
+            },
+            "--color-mode" => {
+                if i + 1 < args.len() {
+                    color_mode = ColorMode::from_name(&args[i + 1]);
+                    if color_mode.is_none() {
+                        eprintln!("Unknown color mode '{}'. Use one of: {}", args[i + 1], ColorMode::NAMES.join(", "));
+                        return Ok(());
+                    }
+                    i += 1;
+                }
+            },
+            "--fps" => {
+                if i + 1 < args.len() {
+                    match args[i + 1].parse::<u32>() {
+                        Ok(value) => fps = Some(frame_rate::clamp_fps(value)),
+                        Err(_) => {
+                            eprintln!("Invalid frame rate '{}'. Use a number from {} to {}", args[i + 1], frame_rate::MIN_FPS, frame_rate::MAX_FPS);
+                            return Ok(());
+                        }
+                    }
+                    i += 1;
+                }
+            },
+            "--seed" => {
+                if i + 1 < args.len() {
+                    match args[i + 1].parse::<u64>() {
+                        Ok(value) => seed = Some(value),
+                        Err(_) => {
+                            eprintln!("Invalid seed '{}'. Use a non-negative integer", args[i + 1]);
+                            return Ok(());
+                        }
+                    }
+                    i += 1;
+                }
+            },
+            "--repeat" => {
+                repeat = true;
+            },
+            "--all-quotes" => {
+                all_quotes = true;
+            },
+            "--favorites" => {
+                favorites = true;
+            },
+            "--unlock-all" => {
+                unlock_all = true;
+            },
+            "--unseen" => {
+                unseen = true;
+            },
+            "--max-sessions" => {
+                if i + 1 < args.len() {
+                    match args[i + 1].parse::<usize>() {
+                        Ok(count) => max_sessions = Some(count),
+                        Err(_) => {
+                            eprintln!("Invalid session limit '{}'. Use a non-negative number of files", args[i + 1]);
+                            return Ok(());
+                        }
+                    }
+                    i += 1;
+                }
+            },
+            "--paragraph" => {
+                if i + 1 < args.len() {
+                    match args[i + 1].parse::<usize>() {
+                        Ok(count) if count > 0 => paragraph = Some(count),
+                        _ => {
+                            eprintln!("Invalid paragraph length '{}'. Use a positive number of quotes", args[i + 1]);
+                            return Ok(());
+                        }
+                    }
+                    i += 1;
+                }
+            },
+            "--timeout" => {
+                if i + 1 < args.len() {
+                    match args[i + 1].parse::<u64>() {
+                        Ok(ms) => timeout = Some(Duration::from_millis(ms)),
+                        Err(_) => {
+                            eprintln!("Invalid timeout '{}'. Use a number of milliseconds", args[i + 1]);
+                            return Ok(());
+                        }
+                    }
+                    i += 1;
+                }
+            },
+            "--origin" => {
+                if i + 1 < args.len() {
+                    origin = Some(args[i + 1].clone());
+                    i += 1;
+                }
+            },
             "-q" | "--quiet" => {
                 quiet_mode = true;
             },
+            "--json" => {
+                // Nothing but the JSON may reach stdout
+                json_output = true;
+                quiet_mode = true;
+            },
+            "--blind" => {
+                blind_mode = true;
+            },
+            "--minimal" => {
+                minimal = true;
+            },
+            "--no-altscreen" => {
+                no_altscreen = true;
+            },
             "--verbose" => {
                 verbose_mode = true;
             },
+            "--dry-run" => {
+                command = Some("check".to_string());
+            },
+            "--list-categories" => {
+                command = Some("quote".to_string());
+                subcommand = Some("categories".to_string());
+            },
             "--demo-heatmap" => {
                 demo_heatmap = true;
             },
             "--no-demo" => {
                 demo_heatmap = false;
             },
-            "practice" | "config" | "test" | "consume" | "quote" | "moosesay" | "screensaver" => {
+            "--out" => {
+                if i + 1 < args.len() {
+                    out_path = Some(args[i + 1].clone());
+                    i += 1;
+                }
+            },
+            s if GameType::ALL.iter().any(|game| game.name() == s)
+                || matches!(s, "check" | "config" | "test" | "quote" | "moosesay" | "screensaver" | "heatmap" | "stats" | "compare" | "single") => {
                 command = Some(args[i].clone());
                 
                 // If this is consume mode and the next arg doesn't start with '-'
@@ -250,6 +437,27 @@ fn main() -> std::io::Result<()> {
                     consume_input = Some(args[i + 1].clone());
                     i += 1;
                 }
+                // If this is heatmap or stats mode, the next arg is its subcommand
+                else if matches!(args[i].as_str(), "heatmap" | "stats") && i + 1 < args.len() && !args[i + 1].starts_with('-') {
+                    subcommand = Some(args[i + 1].clone());
+                    i += 1;
+                }
+                // If this is quote mode, the next arg may be a subcommand such as `find <query>`
+                else if args[i].as_str() == "quote" && i + 1 < args.len() && !args[i + 1].starts_with('-') {
+                    subcommand = Some(args[i + 1].clone());
+                    i += 1;
+                    if subcommand.as_deref() == Some("find") && i + 1 < args.len() {
+                        search_query = Some(args[i + 1].clone());
+                        i += 1;
+                    }
+                }
+                // If this is compare mode, the next two args are the files to compare
+                else if args[i].as_str() == "compare" {
+                    while compare_files.len() < 2 && i + 1 < args.len() && !args[i + 1].starts_with('-') {
+                        compare_files.push(args[i + 1].clone());
+                        i += 1;
+                    }
+                }
                 // If this is screensaver mode and the next arg is a number
                 else if args[i].as_str() == "screensaver" && i + 1 < args.len() {
                     if let Ok(dur) = args[i + 1].parse::<u64>() {
@@ -269,49 +477,159 @@ fn main() -> std::io::Result<()> {
 
     // Set up logging based on quiet mode
     let log_level = if quiet_mode { LevelFilter::Error } else { LevelFilter::Info };
-    let _ = logger::init_logger(log_level, None::<PathBuf>);
+    let _ = logger::init_logger_with(&logger::LoggerOptions {
+        level: log_level,
+        log_file: Some(PathBuf::from(logger::DEFAULT_LOG_FILE)),
+        ..Default::default()
+    });
     
     if !quiet_mode {
         info!("Starting SpringKeys application");
     }
     
-    // Initialize application
-    let mut app = SpringKeys::new();
+    // Validate the setup without loading the app, which would create missing files
+    if command.as_deref() == Some("check") {
+        let report = check::run_checks(Path::new(config::DEFAULT_CONFIG_FILE), Path::new(quotes::QUOTES_DIR), Path::new(STATS_DIR));
+        output::emit(&report, json_output);
+        std::process::exit(if report.is_ok() { 0 } else { 1 });
+    }
 
-    // Create a single instance of QuoteDatabase
-    let mut quote_db = if quiet_mode {
-        quotes::QuoteDatabase::new_silent()
-    } else {
-        quotes::QuoteDatabase::new()
-    };
+    // Initialize application
+    let mut app = if quiet_mode { SpringKeys::new_silent() } else { SpringKeys::new() };
 
-    // Apply difficulty if specified
-    if let Some(diff) = difficulty {
-        app.config.preferences.difficulty = match diff {
+    // Command line settings win over the config file, also when F12 reloads it
+    app.set_config_overrides(ConfigOverrides {
+        difficulty: difficulty.map(|diff| match diff {
             QuoteDifficulty::Easy => DifficultyLevel::Beginner,
             QuoteDifficulty::Medium => DifficultyLevel::Intermediate,
             QuoteDifficulty::Hard => DifficultyLevel::Advanced,
-        };
+        }),
+        max_sessions,
+        render_mode: minimal.then_some(RenderMode::Minimal),
+        blind_mode: blind_mode.then_some(true),
+        alternate_screen: no_altscreen.then_some(false),
+    });
+
+    // Locked categories are held back from the start. Bookmarked quotes were chosen by the
+    // user, so practicing favorites never holds them back.
+    if unlock_all || favorites {
+        app.quote_db.unlock_all();
+    }
+
+    // Apply category if specified
+    if let Some(category) = category {
+        if app.quote_db.count_in_category(category) == 0 && app.quote_db.locked_in_category(category) > 0 {
+            eprintln!("All {} quotes are in locked categories. Pass --unlock-all to practice them anyway.", category.name());
+            return Ok(());
+        }
+        app.quote_db.set_active_category(category);
+    }
+
+    // Apply origin if specified
+    if let Some(origin) = &origin {
+        if app.quote_db.next_by_origin(origin).is_none() {
+            eprintln!("No quotes found with origin '{}'", origin);
+            return Ok(());
+        }
+        app.quote_db.set_active_origin(Some(origin.clone()));
+    }
+
+    // Seed after origin validation so only real quote selections consume the sequence
+    if let Some(seed) = seed {
+        app.set_seed(seed);
+        if !quiet_mode {
+            println!("Quote seed: {} (share it to get the same quotes)", seed);
+        }
     }
 
     // Handle special commands that don't need the full app initialization
     if let Some(cmd) = &command {
+        // Commands that start a typing mode switch to it before any mode-specific setup
+        if let Ok(game) = cmd.parse::<GameType>() {
+            app.change_game(game);
+        }
         match cmd.as_str() {
+            "quote" if subcommand.as_deref() == Some("add") => {
+                let (Some(text), Some(source), Some(category)) = (custom_text, quote_source, category_name) else {
+                    eprintln!("Missing quote details. Use: spring-keys quote add --text TEXT --source SOURCE --category NAME [--difficulty LEVEL]");
+                    return Ok(());
+                };
+                let quote = quotes::Quote {
+                    // Without a level, estimate it the way custom practice text is
+                    difficulty: difficulty.unwrap_or_else(|| QuoteDifficulty::classify(&text)),
+                    text,
+                    source,
+                    category,
+                    origin: origin.unwrap_or_default(),
+                };
+                match quotes::add_quote_to_directory(quotes::QUOTES_DIR, &quote) {
+                    Ok(path) => {
+                        if !quiet_mode {
+                            println!("Added quote to {}", path.display());
+                        }
+                    }
+                    Err(e) => eprintln!("Could not add quote: {}", e),
+                }
+                return Ok(());
+            }
             "quote" | "moosesay" | "screensaver" => {
                 let mut quote_db = if quiet_mode {
                     quotes::QuoteDatabase::new_silent()
                 } else {
                     quotes::QuoteDatabase::new()
                 };
+                if unlock_all {
+                    quote_db.unlock_all();
+                }
+                if let Some(category) = category {
+                    quote_db.set_active_category(category);
+                }
+                quote_db.set_active_origin(origin.clone());
+                if let Some(seed) = seed {
+                    quote_db.set_seed(seed);
+                }
                 
                 match cmd.as_str() {
-                    "quote" => {
-                        let quote = quote_db.next_random();
-                        if !quiet_mode {
-                            println!("{}", quote.text);
-                            println!("— {}", quote.source);
+                    "quote" => match subcommand.as_deref() {
+                        None => {
+                            let quote = quote_db.next_random();
+                            if json_output {
+                                output::emit(&quote, true);
+                            } else if !quiet_mode {
+                                println!("{}", quote.text);
+                                println!("— {}", quote.source);
+                            }
                         }
-                    }
+                        Some("find") => {
+                            if let Some(query) = search_query.as_deref() {
+                                let matches = quote_db.search(query);
+                                if json_output {
+                                    output::emit(&matches, true);
+                                } else if matches.is_empty() {
+                                    println!("No quotes matching '{}'", query);
+                                } else {
+                                    for quote in matches {
+                                        println!("[{}] {}", quote.category, quote.text);
+                                        println!("— {}", quote.source);
+                                    }
+                                }
+                            } else {
+                                eprintln!("Missing search text. Use: spring-keys quote find QUERY");
+                            }
+                        }
+                        Some("categories") => {
+                            if json_output {
+                                output::emit(&quote_db.category_counts(), true);
+                            } else {
+                                for line in quote_db.category_listing() {
+                                    println!("{}", line);
+                                }
+                            }
+                        }
+                        Some(_) => {
+                            eprintln!("Unknown quote command. Use: spring-keys quote [find QUERY | categories | add]");
+                        }
+                    },
                     "moosesay" => {
                         // Skip animation in non-interactive mode or quiet mode
                         if force_non_interactive || !std::io::stdout().is_terminal() || quiet_mode {
@@ -321,7 +639,7 @@ fn main() -> std::io::Result<()> {
                                 println!("— {}", quote.source);
                             }
                         } else {
-                            moosesay::animate_moose_quote(1, quiet_mode, verbose_mode)?;
+                            moosesay::animate_moose_quote(Some(1), fps.unwrap_or(moosesay::DEFAULT_FPS), quiet_mode, verbose_mode)?;
                         }
                     }
                     "screensaver" => {
@@ -333,8 +651,8 @@ fn main() -> std::io::Result<()> {
                                 println!("— {}", quote.source);
                             }
                         } else {
-                            let duration = duration.unwrap_or(1);
-                            moosesay::animate_moose_quote(duration, quiet_mode, verbose_mode)?;
+                            // Without a duration, run until any key or mouse movement
+                            moosesay::animate_moose_quote(duration, fps.unwrap_or(moosesay::DEFAULT_FPS), quiet_mode, verbose_mode)?;
                         }
                     }
                     _ => unreachable!()
@@ -342,8 +660,39 @@ fn main() -> std::io::Result<()> {
                 return Ok(());
             }
             "practice" => {
-                app.change_game(GameType::Practice);
+                app.paragraph_length = paragraph;
+                app.repeat_until_passed = repeat;
+                app.prefer_unseen = unseen;
+                if favorites {
+                    if app.quote_db.favorites_only(&app.config.preferences.favorite_quotes) == 0 {
+                        println!("No favorite quotes yet. Press F9 while practicing to bookmark one.");
+                        return Ok(());
+                    }
+                } else if app.config.preferences.typeable_quotes_only && !all_quotes {
+                    let removed = app.quote_db.typeable_only(KeyboardLayout::Qwerty);
+                    info!("Skipping {} quotes that can't be typed on the keyboard", removed);
+                }
                 // Start a typing session to show the keyboard immediately
+                app.start_typing_session(preset.map(|p| p.text().to_string()));
+            },
+            "words" => {
+                app.start_typing_session(preset.map(|p| p.text().to_string()));
+            },
+            "sentences" => {
+                app.start_typing_session(preset.map(|p| p.text().to_string()));
+            },
+            "memorize" => {
+                app.start_typing_session(None);
+            },
+            "learn" => {
+                if let Some(stage) = app.lesson_stage() {
+                    if !quiet_mode {
+                        println!("Lesson {}: {}", app.config.preferences.lesson_stage + 1, stage.name);
+                    }
+                } else {
+                    println!("All lessons passed! Use practice mode to keep improving.");
+                    return Ok(());
+                }
                 app.start_typing_session(None);
             },
             "config" => {
@@ -351,11 +700,68 @@ fn main() -> std::io::Result<()> {
                 return Ok(());
             },
             "test" => {
-                return vga_test::run_test_screen();
+                return vga_test::run_test_screen(fps.unwrap_or(vga_test::DEFAULT_FPS));
             },
             "consume" => {
-                app.change_game(GameType::Consume);
-                return run_consume_mode(&mut app, consume_input.as_deref());
+                return run_consume_mode(&mut app, consume_input.as_deref(), color_mode);
+            },
+            "stats" => {
+                return match subcommand.as_deref() {
+                    Some("byhour") => {
+                        let stats = &app.accumulated_stats;
+                        if json_output {
+                            let hours: Vec<_> = (0..24)
+                                .filter_map(|hour| stats.hourly_average(hour).map(|wpm| {
+                                    serde_json::json!({ "hour": hour, "avg_wpm": wpm, "quotes": stats.hourly_counts[hour] })
+                                }))
+                                .collect();
+                            output::emit(&hours, true);
+                        } else {
+                            let chart = stats.hourly_chart(HOURLY_CHART_WIDTH);
+                            if chart.is_empty() {
+                                println!("No completed quotes yet");
+                            }
+                            for line in chart {
+                                println!("{}", line);
+                            }
+                        }
+                        Ok(())
+                    }
+                    _ => {
+                        eprintln!("Unknown stats command. Use: spring-keys stats byhour");
+                        Ok(())
+                    }
+                };
+            },
+            "heatmap" => {
+                return match subcommand.as_deref() {
+                    Some("export") => {
+                        let out = out_path.as_deref().unwrap_or(DEFAULT_HEATMAP_EXPORT_FILE);
+                        std::fs::write(out, app.heatmap_to_json())?;
+                        if !quiet_mode {
+                            println!("Heat map exported to {}", out);
+                        }
+                        Ok(())
+                    }
+                    _ => {
+                        eprintln!("Unknown heatmap command. Use: spring-keys heatmap export [--out FILE]");
+                        Ok(())
+                    }
+                };
+            },
+            "single" => {
+                let text = custom_text.or(preset.map(|p| p.text().to_string()));
+                let code = run_single_mode(&mut app, text, consume_input.as_deref(), timeout, quiet_mode);
+                std::process::exit(code);
+            },
+            "compare" => {
+                return match compare_files.as_slice() {
+                    [old, new] => run_compare(old, new),
+                    _ => {
+                        eprintln!("Usage: spring-keys compare <old.json> <new.json>");
+                        Ok(())
+                    }
+                };
             },
             _ => {
                 // If no terminal is detected, default to practice mode instead of showing error
@@ -394,6 +800,10 @@ fn main() -> std::io::Result<()> {
     
     // Initialize and run the UI
     let mut ui = TerminalUI::new()?;
+    if let Some(mode) = color_mode {
+        ui.set_color_mode(mode);
+    }
+    ui.set_alternate_screen(app.config.ui.alternate_screen);
     ui.init()?;
     
     let result = ui.run(&mut app);
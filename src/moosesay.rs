@@ -2,18 +2,20 @@ use std::io::{self, Write};
 use std::thread;
 use std::time::{Duration, Instant};
 use rand::Rng;
+use crate::rng;
 use crossterm::{
     terminal::{self, Clear, ClearType},
     cursor::{Hide, Show, MoveTo},
     queue,
     execute,
-    event::{poll, read, Event, KeyCode, KeyModifiers},
+    event::{poll, read, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
     style::{Color, Print, ResetColor, SetForegroundColor, SetBackgroundColor},
     ExecutableCommand,
 };
 use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::cmp::Ordering;
 use crate::quotes;
+use crate::ui::frame_rate;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum Direction {
@@ -29,7 +31,7 @@ enum Direction {
 
 impl Direction {
     fn random() -> Self {
-        match rand::random::<u8>() % 8 {
+        match rng::global_rng().gen::<u8>() % 8 {
             0 => Direction::Up,
             1 => Direction::Down,
             2 => Direction::Left,
@@ -286,7 +288,7 @@ impl Moose {
             }
         } else {
             // Random movement when no trees are available
-            if rand::random::<f64>() < 0.1 {
+            if rng::global_rng().gen::<f64>() < 0.1 {
                 self.direction = Direction::random();
             }
 
@@ -707,7 +709,7 @@ impl Moose {
 
 impl MooseStyle {
     fn random() -> Self {
-        match rand::random::<u8>() % 8 {
+        match rng::global_rng().gen::<u8>() % 8 {
             0 => MooseStyle::Default,
             1 => MooseStyle::VK2001,
             2 => MooseStyle::JGSVK,
@@ -722,7 +724,7 @@ impl MooseStyle {
 
 impl Tree {
     fn new(width: u16, height: u16) -> Self {
-        let mut rng = rand::thread_rng();
+        let mut rng = rng::global_rng();
         Self {
             x: rng.gen_range(0..width.max(1) as i32),
             y: rng.gen_range(0..height.max(1) as i32),
@@ -769,7 +771,7 @@ impl Tree {
                     
                     // Add new branches
                     if self.growth_stage > 1 {
-                        let dx = if rand::random::<bool>() { 1 } else { -1 };
+                        let dx = if rng::global_rng().gen::<bool>() { 1 } else { -1 };
                         self.branches.push((dx, -(self.growth_stage as i32)));
                     }
                 }
@@ -967,10 +969,10 @@ impl Tree {
     }
 
     fn spawn_seed(&mut self) -> Option<Seed> {
-        if self.growth_stage >= 3 && !self.is_dead && !self.is_falling && rand::random::<f64>() < 0.1 {
+        if self.growth_stage >= 3 && !self.is_dead && !self.is_falling && rng::global_rng().gen::<f64>() < 0.1 {
             self.is_multiplying = true;
             self.event_timer = 20.0; // Show multiplying animation for 2 seconds
-            let mut rng = rand::thread_rng();
+            let mut rng = rng::global_rng();
             Some(Seed {
                 x: self.x,
                 y: self.y,
@@ -988,7 +990,7 @@ impl Tree {
 
 impl Seed {
     fn new(width: u16, height: u16) -> Self {
-        let mut rng = rand::thread_rng();
+        let mut rng = rng::global_rng();
         Self {
             x: rng.gen_range(0..width.max(1) as i32),
             y: rng.gen_range(0..height.max(1) as i32),
@@ -1012,8 +1014,8 @@ impl Seed {
             }
 
             // Random horizontal movement
-            if rand::random::<f64>() < 0.1 {
-                self.dx = rand::thread_rng().gen_range(-1..=1);
+            if rng::global_rng().gen::<f64>() < 0.1 {
+                self.dx = rng::global_rng().gen_range(-1..=1);
             }
 
             // Move seed
@@ -1043,7 +1045,7 @@ impl Seed {
 
 impl Animal {
     fn new(width: u16, height: u16, is_rabbit: bool) -> Self {
-        let mut rng = rand::thread_rng();
+        let mut rng = rng::global_rng();
         Self {
             x: rng.gen_range(0..width.max(1) as i32),
             y: rng.gen_range(0..height.max(1) as i32),
@@ -1107,7 +1109,7 @@ impl Animal {
                         self.path.clear();
                         
                         // Spawn new rabbit immediately after eating a sprout
-                        let mut rng = rand::thread_rng();
+                        let mut rng = rng::global_rng();
                         return Some(Animal {
                             x: self.x + rng.gen_range(-2..=2),
                             y: self.y + rng.gen_range(-2..=2),
@@ -1124,7 +1126,7 @@ impl Animal {
                 }
             } else {
                 // Random movement when no sprouts are available
-                let mut rng = rand::thread_rng();
+                let mut rng = rng::global_rng();
                 self.x += rng.gen_range(-1..=1);
                 self.y += rng.gen_range(-1..=1);
                 self.x = self.x.clamp(0, width as i32 - 1);
@@ -1162,7 +1164,7 @@ impl Animal {
                     self.target_nut = None;
                 }
             } else {
-                let mut rng = rand::thread_rng();
+                let mut rng = rng::global_rng();
                 self.x += rng.gen_range(-1..=1);
                 self.y += rng.gen_range(-1..=1);
                 self.x = self.x.clamp(0, width as i32 - 1);
@@ -1275,7 +1277,37 @@ fn draw_animal(animal: &Animal, stdout: &mut io::Stdout) -> io::Result<()> {
     )
 }
 
-pub fn animate_moose_quote(duration: u64, quiet_mode: bool, verbose_mode: bool) -> io::Result<()> {
+/// Puts the terminal into raw mode with mouse capture and undoes it on drop,
+/// so the terminal is restored even if the animation loop bails out early
+struct ScreensaverTerminal;
+
+impl ScreensaverTerminal {
+    fn enter(stdout: &mut impl Write) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let guard = Self;
+        execute!(stdout, EnableMouseCapture, Clear(ClearType::All), Hide)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for ScreensaverTerminal {
+    fn drop(&mut self) {
+        let mut stdout = io::stdout();
+        let _ = execute!(stdout, DisableMouseCapture, Clear(ClearType::All), Show);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Frame rate of the moose animation when `--fps` isn't given
+pub const DEFAULT_FPS: u32 = 10;
+
+/// Run the moose animation at `fps` frames per second for `duration` seconds, or
+/// until any key press or mouse movement when `duration` is `None`
+pub fn animate_moose_quote(duration: Option<u64>, fps: u32, quiet_mode: bool, verbose_mode: bool) -> io::Result<()> {
+    if duration == Some(0) {
+        return Ok(());
+    }
+
     let mut stdout = io::stdout();
     let (width, height) = terminal::size()?;
 
@@ -1303,17 +1335,23 @@ pub fn animate_moose_quote(duration: u64, quiet_mode: bool, verbose_mode: bool)
     // Get start time
     let start = Instant::now();
 
-    // Clear screen and hide cursor
-    queue!(stdout, terminal::Clear(terminal::ClearType::All), Hide)?;
-    stdout.flush()?;
+    // Clear screen, hide cursor and listen for the mouse
+    let guard = ScreensaverTerminal::enter(&mut stdout)?;
 
     // Main animation loop
-    while start.elapsed() < Duration::from_secs(duration) {
+    while duration.is_none_or(|secs| start.elapsed() < Duration::from_secs(secs)) {
+        let frame_start = Instant::now();
+
         // Check for input
-        if poll(Duration::from_millis(100))? {
-            if let Ok(Event::Key(event)) = read() {
+        if poll(Duration::ZERO)? {
+            let event = read()?;
+            // An endless screensaver exits on any activity
+            if duration.is_none() && matches!(event, Event::Key(_) | Event::Mouse(_)) {
+                break;
+            }
+            if let Event::Key(event) = event {
                 match event.code {
-                    KeyCode::Esc | KeyCode::Char('d') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    KeyCode::Esc | KeyCode::Char('c') | KeyCode::Char('d') if event.modifiers.contains(KeyModifiers::CONTROL) => {
                         break;
                     }
                     KeyCode::Char(c) => {
@@ -1346,13 +1384,12 @@ pub fn animate_moose_quote(duration: u64, quiet_mode: bool, verbose_mode: bool)
         // Flush output
         stdout.flush()?;
 
-        // Sleep for a bit
-        thread::sleep(Duration::from_millis(100));
+        // Wait out the rest of the frame
+        thread::sleep(frame_rate::frame_delay(fps, frame_start.elapsed()));
     }
 
-    // Clear screen and show cursor
-    queue!(stdout, terminal::Clear(terminal::ClearType::All), Show)?;
-    stdout.flush()?;
+    // Clear screen, show cursor and restore the terminal
+    drop(guard);
 
     Ok(())
 }
@@ -1486,7 +1523,7 @@ fn update_and_draw_moose(stdout: &mut impl Write, moose: &mut Moose, trees: &mut
                 "I am the destroyer of forests!",
                 "Moose: 1, Tree: 0",
             ];
-            let quote = stomp_quotes[rand::thread_rng().gen_range(0..stomp_quotes.len())];
+            let quote = stomp_quotes[rng::global_rng().gen_range(0..stomp_quotes.len())];
             moose.speech_bubble = Some(quote.to_string());
             moose.speech_timer = 50.0;
         }
@@ -0,0 +1,43 @@
+use serde::Serialize;
+use serde_json::Value;
+
+/// Print `value` for a non-interactive command: pretty JSON with `--json`, otherwise a plain table
+pub fn emit(value: &impl Serialize, json: bool) {
+    println!("{}", render(value, json));
+}
+
+/// The text `emit` prints
+pub fn render(value: &impl Serialize, json: bool) -> String {
+    let value = match serde_json::to_value(value) {
+        Ok(value) => value,
+        Err(e) => return format!("Failed to format output: {}", e),
+    };
+    if json {
+        serde_json::to_string_pretty(&value).unwrap_or_default()
+    } else {
+        render_table(&value)
+    }
+}
+
+/// Objects become aligned `key  value` rows and arrays one block per item, separated by blank lines
+fn render_table(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let width = map.keys().map(|key| key.chars().count()).max().unwrap_or(0);
+            map.iter()
+                .map(|(key, value)| format!("{:width$}  {}", key, render_cell(value), width = width))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        Value::Array(items) => items.iter().map(render_table).collect::<Vec<_>>().join("\n\n"),
+        other => render_cell(other),
+    }
+}
+
+fn render_cell(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => "-".to_string(),
+        other => other.to_string(),
+    }
+}
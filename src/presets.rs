@@ -0,0 +1,49 @@
+//! Fixed practice texts selectable with `--preset`, for repeatable drills and tests
+
+/// A named, deterministic practice text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// The classic "quick brown fox" sentence
+    FoxJump,
+    /// Home row keys only
+    HomeRow,
+    /// Digits and number formatting
+    Numbers,
+    /// A pangram using every letter of the alphabet
+    Pangram,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 4] = [Preset::FoxJump, Preset::HomeRow, Preset::Numbers, Preset::Pangram];
+
+    /// Name used on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            Preset::FoxJump => "foxjump",
+            Preset::HomeRow => "homerow",
+            Preset::Numbers => "numbers",
+            Preset::Pangram => "pangram",
+        }
+    }
+
+    /// Parse a preset name case-insensitively
+    pub fn from_name(name: &str) -> Option<Preset> {
+        let name = name.to_lowercase();
+        Self::ALL.iter().copied().find(|preset| preset.name() == name)
+    }
+
+    /// Comma-separated list of valid names, for error messages
+    pub fn valid_names() -> String {
+        Self::ALL.iter().map(|preset| preset.name()).collect::<Vec<_>>().join(", ")
+    }
+
+    /// The text to type for this preset
+    pub fn text(&self) -> &'static str {
+        match self {
+            Preset::FoxJump => "The quick brown fox jumps over the lazy dog.",
+            Preset::HomeRow => "asdf jkl; a sad lad asks; all fall; dad has a glass flask",
+            Preset::Numbers => "1 2 3 4 5 6 7 8 9 0 10 25 50 100 2024 3.14 42",
+            Preset::Pangram => "Pack my box with five dozen liquor jugs.",
+        }
+    }
+}
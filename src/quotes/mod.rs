@@ -1,25 +1,157 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::io::{self, BufRead, BufReader};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use log::warn;
+use crate::core::metrics::KeyboardLayout;
+use crate::config::{Config, DEFAULT_CONFIG_FILE};
+use crate::core::stats::{AccumulatedStats, ACCUMULATED_STATS_FILE};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Directory that quote category files are loaded from
+pub const QUOTES_DIR: &str = "quotes/categories";
+
+/// Categories whose subcategories share their name as a prefix, e.g. `SacredTextsQuran`
+pub const CATEGORY_GROUPS: [&str; 2] = ["SacredTexts", "Holiday"];
+
+/// Shown in category listings for legacy quotes without a category
+const UNCATEGORIZED: &str = "(uncategorized)";
+
+/// A harder category kept out of practice until the user sustains an accuracy on easier quotes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CategoryUnlock {
+    /// Category name; subcategories sharing it as a prefix are covered too
+    pub category: &'static str,
+    /// Quotes that must be completed first
+    pub min_quotes: usize,
+    /// Minimum all-time (decayed) average accuracy percentage
+    pub min_accuracy: f64,
+}
+
+impl CategoryUnlock {
+    /// Whether a quote's category is this one or one of its subcategories, ignoring case and spaces
+    pub fn covers(&self, quote_category: &str) -> bool {
+        let name: String = quote_category.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_lowercase();
+        name.starts_with(&self.category.to_lowercase())
+    }
+
+    pub fn is_earned(&self, stats: &AccumulatedStats) -> bool {
+        stats.total_quotes >= self.min_quotes && stats.avg_accuracy >= self.min_accuracy
+    }
+
+    /// What it takes to unlock, for messages, e.g. "complete 25 quotes at 95% average accuracy"
+    pub fn requirement(&self) -> String {
+        format!("complete {} quotes at {:.0}% average accuracy", self.min_quotes, self.min_accuracy)
+    }
+}
+
+/// Categories that start locked, unlocked by `--unlock-all` or by meeting their thresholds
+pub const LOCKED_CATEGORIES: [CategoryUnlock; 2] = [
+    CategoryUnlock { category: "SacredTexts", min_quotes: 25, min_accuracy: 95.0 },
+    CategoryUnlock { category: "HardStockReports", min_quotes: 50, min_accuracy: 97.0 },
+];
+
+/// The `LOCKED_CATEGORIES` entry covering `category`, if it's one that starts locked
+pub fn locked_category(category: &str) -> Option<CategoryUnlock> {
+    LOCKED_CATEGORIES.into_iter().find(|unlock| unlock.covers(category))
+}
+
+/// Whether quotes in `category` may be served to a user with these stats. Categories
+/// that were never locked always are.
+pub fn is_category_unlocked(category: &str, stats: &AccumulatedStats) -> bool {
+    LOCKED_CATEGORIES
+        .iter()
+        .filter(|unlock| unlock.covers(category))
+        .all(|unlock| unlock.is_earned(stats))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum QuoteDifficulty {
     Easy,
     Medium,
     Hard,
 }
 
+impl QuoteDifficulty {
+    /// Guess the difficulty of text that didn't come with a label, such as a custom quote.
+    /// Length, punctuation and symbol density, capitals, digits and long words each add
+    /// to a score, and the total picks the level.
+    pub fn classify(text: &str) -> QuoteDifficulty {
+        let chars: Vec<char> = text.chars().collect();
+        if chars.is_empty() {
+            return QuoteDifficulty::Easy;
+        }
+
+        let mut score = match chars.len() {
+            0..=59 => 0,
+            60..=149 => 1,
+            _ => 2,
+        };
+
+        let punctuation = chars.iter().filter(|c| !c.is_alphanumeric() && !c.is_whitespace()).count();
+        let punctuation_density = punctuation as f64 / chars.len() as f64;
+        if punctuation_density > 0.15 {
+            score += 2;
+        } else if punctuation_density > 0.08 {
+            score += 1;
+        }
+
+        let letters: Vec<&char> = chars.iter().filter(|c| c.is_alphabetic()).collect();
+        let uppercase = letters.iter().filter(|c| c.is_uppercase()).count();
+        if !letters.is_empty() && uppercase as f64 / letters.len() as f64 > 0.15 {
+            score += 1;
+        }
+
+        if chars.iter().any(|c| c.is_ascii_digit()) {
+            score += 1;
+        }
+        // Sentence punctuation is easy to reach; anything else means a trip to the symbol row
+        if chars.iter().any(|c| !c.is_alphanumeric() && !c.is_whitespace() && !".,'!?;:-\"".contains(*c)) {
+            score += 1;
+        }
+
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let avg_word_len = words.iter().map(|w| w.chars().count()).sum::<usize>() as f64 / words.len().max(1) as f64;
+        if avg_word_len > 6.0 {
+            score += 1;
+        }
+
+        match score {
+            0..=1 => QuoteDifficulty::Easy,
+            2..=3 => QuoteDifficulty::Medium,
+            _ => QuoteDifficulty::Hard,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Quote {
     pub text: String,
     pub source: String,
     pub difficulty: QuoteDifficulty,
+    /// Category name from the quote file; empty for legacy quotes without one
+    #[serde(default)]
+    pub category: String,
+    /// Language or regional origin of the quote; empty when unknown
+    #[serde(default)]
+    pub origin: String,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+impl Quote {
+    /// Number of characters in the text, which is what the cursor counts
+    pub fn char_count(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    /// Whether every character of the text has a key on `layout`
+    pub fn is_typeable(&self, layout: KeyboardLayout) -> bool {
+        self.text.chars().all(|c| layout.has_key(c))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CategoryCycle {
     All,
     Programming,
@@ -27,14 +159,133 @@ pub enum CategoryCycle {
     Typewriter,
 }
 
+impl CategoryCycle {
+    /// Every category in the order the UI cycles through them
+    pub const ALL: [CategoryCycle; 4] = [
+        CategoryCycle::All,
+        CategoryCycle::Programming,
+        CategoryCycle::Literature,
+        CategoryCycle::Typewriter,
+    ];
+
+    /// Lowercase name used on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            CategoryCycle::All => "all",
+            CategoryCycle::Programming => "programming",
+            CategoryCycle::Literature => "literature",
+            CategoryCycle::Typewriter => "typewriter",
+        }
+    }
+
+    /// Parse a category name case-insensitively
+    pub fn from_name(name: &str) -> Option<CategoryCycle> {
+        let name = name.to_lowercase();
+        Self::ALL.iter().copied().find(|category| category.name() == name)
+    }
+
+    /// Comma-separated list of valid names, for error messages
+    pub fn valid_names() -> String {
+        Self::ALL.iter().map(|category| category.name()).collect::<Vec<_>>().join(", ")
+    }
+
+    /// Whether a quote's free-form category string belongs to this category
+    pub fn matches(&self, quote_category: &str) -> bool {
+        match self {
+            CategoryCycle::All => true,
+            _ => quote_category.to_lowercase().contains(self.name()),
+        }
+    }
+}
+
+/// Coarse grouping of the free-form category names, for a two-level category menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CategoryGroup {
+    Wisdom,
+    Scripture,
+    Lessons,
+    Entertainment,
+    Technical,
+    Holiday,
+}
+
+impl CategoryGroup {
+    /// Every group in menu order
+    pub const ALL: [CategoryGroup; 6] = [
+        CategoryGroup::Wisdom,
+        CategoryGroup::Scripture,
+        CategoryGroup::Lessons,
+        CategoryGroup::Entertainment,
+        CategoryGroup::Technical,
+        CategoryGroup::Holiday,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CategoryGroup::Wisdom => "Wisdom",
+            CategoryGroup::Scripture => "Scripture",
+            CategoryGroup::Lessons => "Lessons",
+            CategoryGroup::Entertainment => "Entertainment",
+            CategoryGroup::Technical => "Technical",
+            CategoryGroup::Holiday => "Holiday",
+        }
+    }
+
+    /// Group a quote's category name belongs to, ignoring case and spaces. Anything not
+    /// recognized as another group (proverbs, philosophy, folk sayings...) is `Wisdom`.
+    pub fn for_category(category: &str) -> CategoryGroup {
+        let name: String = category.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_lowercase();
+        let has = |words: &[&str]| words.iter().any(|word| name.contains(word));
+
+        if name.starts_with("holiday") {
+            CategoryGroup::Holiday
+        } else if name.starts_with("lessons") || has(&["tautolog", "tonguetwister"]) {
+            CategoryGroup::Lessons
+        } else if has(&["programming", "typewriter"]) {
+            CategoryGroup::Technical
+        } else if name.starts_with("sacredtext") || has(&["scripture", "sacred", "religious"]) {
+            CategoryGroup::Scripture
+        } else if has(&["literature", "lyrics", "anime", "pirate", "humor"]) {
+            CategoryGroup::Entertainment
+        } else {
+            CategoryGroup::Wisdom
+        }
+    }
+
+    /// The names in `categories` that belong to this group, in their original order
+    pub fn categories<'a, I>(&self, categories: I) -> Vec<String>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        categories
+            .into_iter()
+            .filter(|name| CategoryGroup::for_category(name) == *self)
+            .map(str::to_string)
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct QuoteDatabase {
     quotes: Vec<Quote>,
     active_category: CategoryCycle,
+    /// When set, only quotes with this origin (case-insensitive) are selected
+    active_origin: Option<String>,
+    /// Lowercased (text, source) of each quote, kept in step with `quotes` for `search`
+    search_keys: Vec<(String, String)>,
+    /// Indices into `quotes` for each difficulty, so selection doesn't rescan every quote
+    difficulty_index: HashMap<QuoteDifficulty, Vec<usize>>,
+    /// Indices into `quotes` matching each category cycle entry
+    category_index: HashMap<CategoryCycle, Vec<usize>>,
+    /// Source of randomness for quote selection; seed it to reproduce a quote order
+    rng: StdRng,
     quiet_mode: bool,
+    /// Quotes of locked categories, held back from selection until unlocked
+    locked: Vec<Quote>,
 }
 
 impl QuoteDatabase {
+    /// The shipped quotes, with the categories the saved progress hasn't unlocked held back
     pub fn new() -> Self {
         Self::new_with_options(false)
     }
@@ -44,29 +295,95 @@ impl QuoteDatabase {
     }
 
     fn new_with_options(quiet_mode: bool) -> Self {
+        let mut db = Self::from_directory(QUOTES_DIR, quiet_mode);
+        // Read the progress without creating anything, since this may be a one-off lookup
+        let stats = AccumulatedStats::load_from_file(Path::new(ACCUMULATED_STATS_FILE)).unwrap_or_else(|_| AccumulatedStats::new());
+        let unlocked = Config::load(DEFAULT_CONFIG_FILE).map(|config| config.preferences.unlocked_categories).unwrap_or_default();
+        db.lock_categories(&stats, &unlocked);
+        db
+    }
+
+    /// Load quotes from every JSON file in the given directory
+    pub fn from_directory<P: AsRef<Path>>(dir: P, quiet_mode: bool) -> Self {
         let mut db = Self {
             quotes: Vec::new(),
             active_category: CategoryCycle::All,
+            active_origin: None,
+            search_keys: Vec::new(),
+            difficulty_index: HashMap::new(),
+            category_index: HashMap::new(),
+            rng: crate::rng::new_rng(),
             quiet_mode,
+            locked: Vec::new(),
         };
-        db.load_quotes();
+        db.load_quotes(dir.as_ref());
+        db.ensure_not_empty();
+        db.build_indexes();
         db
     }
 
-    fn load_quotes(&mut self) {
-        let categories_dir = "quotes/categories";
+    /// Build a database from in-memory quotes
+    pub fn from_quotes(quotes: Vec<Quote>) -> Self {
+        let mut db = Self {
+            quotes,
+            active_category: CategoryCycle::All,
+            active_origin: None,
+            search_keys: Vec::new(),
+            difficulty_index: HashMap::new(),
+            category_index: HashMap::new(),
+            rng: crate::rng::new_rng(),
+            quiet_mode: true,
+            locked: Vec::new(),
+        };
+        db.ensure_not_empty();
+        db.build_indexes();
+        db
+    }
+
+    /// Fall back to the built-in quotes so selection never runs on an empty list
+    fn ensure_not_empty(&mut self) {
+        if self.quotes.is_empty() {
+            warn!("No quotes loaded, falling back to built-in default quotes");
+            self.quotes = default_quotes();
+        }
+    }
+
+    /// Precompute the search keys and selection indices; call whenever `quotes` changes
+    fn build_indexes(&mut self) {
+        self.search_keys = self.quotes
+            .iter()
+            .map(|q| (q.text.to_lowercase(), q.source.to_lowercase()))
+            .collect();
+
+        self.difficulty_index.clear();
+        self.category_index.clear();
+        for (i, quote) in self.quotes.iter().enumerate() {
+            self.difficulty_index.entry(quote.difficulty).or_default().push(i);
+            for category in CategoryCycle::ALL {
+                if category.matches(&quote.category) {
+                    self.category_index.entry(category).or_default().push(i);
+                }
+            }
+        }
+    }
+
+    fn load_quotes(&mut self, categories_dir: &Path) {
         if let Ok(entries) = fs::read_dir(categories_dir) {
-            for entry in entries.flatten() {
-                if let Some(ext) = entry.path().extension() {
-                    if ext == "json" {
-                        if let Ok(content) = fs::read_to_string(entry.path()) {
-                            if let Ok(mut quotes) = serde_json::from_str::<Vec<Quote>>(&content) {
-                                let count = quotes.len();
-                                self.quotes.append(&mut quotes);
-                                if !self.quiet_mode {
-                                    println!("Loaded {:3} quotes from {:?}", count, entry.path().display());
-                                }
-                            }
+            // Sorted so the quote order, and with it a seeded run, is the same on every filesystem
+            let mut paths: Vec<_> = entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect();
+            paths.sort();
+
+            for path in paths {
+                if let Ok(content) = fs::read_to_string(&path) {
+                    if let Ok(mut quotes) = serde_json::from_str::<Vec<Quote>>(&content) {
+                        let count = quotes.len();
+                        self.quotes.append(&mut quotes);
+                        if !self.quiet_mode {
+                            println!("Loaded {:3} quotes from {:?}", count, path.display());
                         }
                     }
                 }
@@ -77,28 +394,78 @@ impl QuoteDatabase {
         }
     }
 
+    /// Reseed quote selection so the same seed always serves the same sequence of quotes
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
     pub fn next_random(&mut self) -> Quote {
-        let mut rng = rand::thread_rng();
-        let quotes = match self.active_category {
-            CategoryCycle::All => &self.quotes,
-            CategoryCycle::Programming => &self.quotes, // TODO: Filter by category
-            CategoryCycle::Literature => &self.quotes,  // TODO: Filter by category
-            CategoryCycle::Typewriter => &self.quotes,  // TODO: Filter by category
-        };
-        quotes[rng.gen_range(0..quotes.len())].clone()
+        // Fall back to the whole database if the active filters match no quotes
+        match self.next_by_category(self.active_category) {
+            Some(quote) => quote,
+            None => self.quotes[self.rng.gen_range(0..self.quotes.len())].clone(),
+        }
+    }
+
+    /// Join `count` random quotes with `sep` into one long text for endurance practice
+    pub fn next_paragraph(&mut self, count: usize, sep: &str) -> String {
+        (0..count)
+            .map(|_| self.next_random().text)
+            .collect::<Vec<_>>()
+            .join(sep)
+    }
+
+    pub fn next_by_category(&mut self, category: CategoryCycle) -> Option<Quote> {
+        let indices = self.category_index.get(&category).map_or(&[][..], Vec::as_slice);
+        choose_indexed(&mut self.rng, &self.quotes, indices, self.active_origin.as_deref())
+    }
+
+    /// Number of quotes `next_by_category` can pick from for `category`, with the active origin applied
+    pub fn count_in_category(&self, category: CategoryCycle) -> usize {
+        let origin = self.active_origin.as_deref();
+        self.category_index
+            .get(&category)
+            .map_or(0, |indices| indices.iter().filter(|&&i| matches_origin(origin, &self.quotes[i])).count())
+    }
+
+    /// Number of quotes matching `category` that `lock_categories` is holding back
+    pub fn locked_in_category(&self, category: CategoryCycle) -> usize {
+        self.locked.iter().filter(|quote| category.matches(&quote.category)).count()
+    }
+
+    /// Pick a quote whose category is exactly `name`, ignoring case
+    pub fn next_in_category(&mut self, name: &str) -> Option<Quote> {
+        self.pick(|q| q.category.eq_ignore_ascii_case(name))
     }
 
     pub fn next_by_difficulty(&mut self, difficulty: QuoteDifficulty) -> Option<Quote> {
-        let mut rng = rand::thread_rng();
+        let indices = self.difficulty_index.get(&difficulty).map_or(&[][..], Vec::as_slice);
+        choose_indexed(&mut self.rng, &self.quotes, indices, self.active_origin.as_deref())
+    }
+
+    pub fn next_by_origin(&mut self, origin: &str) -> Option<Quote> {
         let matching_quotes: Vec<_> = self.quotes.iter()
-            .filter(|q| q.difficulty == difficulty)
+            .filter(|q| q.origin.eq_ignore_ascii_case(origin))
             .collect();
+        choose(&mut self.rng, &matching_quotes)
+    }
 
-        if matching_quotes.is_empty() {
-            None
-        } else {
-            Some(matching_quotes[rng.gen_range(0..matching_quotes.len())].clone())
-        }
+    /// Pick a random quote matching `filter` and the active origin, if any
+    fn pick<F: Fn(&Quote) -> bool>(&mut self, filter: F) -> Option<Quote> {
+        let origin = self.active_origin.as_deref();
+        let matching_quotes: Vec<_> = self.quotes.iter()
+            .filter(|q| matches_origin(origin, q) && filter(q))
+            .collect();
+        choose(&mut self.rng, &matching_quotes)
+    }
+
+    /// Restrict selection to quotes from one origin, or clear the restriction with `None`
+    pub fn set_active_origin(&mut self, origin: Option<String>) {
+        self.active_origin = origin;
+    }
+
+    pub fn get_active_origin(&self) -> Option<&str> {
+        self.active_origin.as_deref()
     }
 
     pub fn set_active_category(&mut self, category: CategoryCycle) {
@@ -109,7 +476,302 @@ impl QuoteDatabase {
         self.active_category
     }
 
+    /// Drop quotes with characters `layout` has no key for, returning how many were removed.
+    /// If none are typeable, the built-in quotes are used instead.
+    pub fn typeable_only(&mut self, layout: KeyboardLayout) -> usize {
+        let before = self.quotes.len();
+        self.quotes.retain(|quote| quote.is_typeable(layout));
+        let removed = before - self.quotes.len();
+        self.ensure_not_empty();
+        self.build_indexes();
+        removed
+    }
+
+    /// Hold back the quotes of every `LOCKED_CATEGORIES` entry that is neither listed in
+    /// `unlocked` nor earned with `stats`, returning how many quotes were held back
+    pub fn lock_categories(&mut self, stats: &AccumulatedStats, unlocked: &[String]) -> usize {
+        let locked: Vec<CategoryUnlock> = LOCKED_CATEGORIES
+            .into_iter()
+            .filter(|unlock| !unlock.is_earned(stats) && !unlocked.iter().any(|name| name.eq_ignore_ascii_case(unlock.category)))
+            .collect();
+        let (held, kept): (Vec<Quote>, Vec<Quote>) = std::mem::take(&mut self.quotes)
+            .into_iter()
+            .partition(|quote| locked.iter().any(|unlock| unlock.covers(&quote.category)));
+        self.quotes = kept;
+        let count = self.locked.len();
+        self.locked.extend(held);
+        self.ensure_not_empty();
+        self.build_indexes();
+        self.locked.len() - count
+    }
+
+    /// Make the held-back quotes of a locked category selectable again, returning how many there were
+    pub fn unlock_category(&mut self, unlock: &CategoryUnlock) -> usize {
+        let (released, held): (Vec<Quote>, Vec<Quote>) = std::mem::take(&mut self.locked)
+            .into_iter()
+            .partition(|quote| unlock.covers(&quote.category));
+        self.locked = held;
+        if released.is_empty() {
+            return 0;
+        }
+        let count = released.len();
+        self.quotes.extend(released);
+        self.build_indexes();
+        count
+    }
+
+    /// Make every held-back quote selectable again, as `--unlock-all` asks, returning how many there were
+    pub fn unlock_all(&mut self) -> usize {
+        let released = std::mem::take(&mut self.locked);
+        let count = released.len();
+        if count > 0 {
+            self.quotes.extend(released);
+            self.build_indexes();
+        }
+        count
+    }
+
+    /// Keep only the quotes whose `quote_hash` is in `favorites`, returning how many are left.
+    /// When none of them are loaded the database is left as it was and 0 is returned.
+    pub fn favorites_only(&mut self, favorites: &[String]) -> usize {
+        let is_favorite = |quote: &Quote| favorites.contains(&quote_hash(&quote.text));
+        if !self.quotes.iter().any(is_favorite) {
+            return 0;
+        }
+        self.quotes.retain(is_favorite);
+        self.build_indexes();
+        self.quotes.len()
+    }
+
+    /// A random quote (with the active origin, if any) whose `quote_hash` isn't in `seen`,
+    /// or `None` once every quote has been seen
+    pub fn next_unseen(&mut self, seen: &HashSet<String>) -> Option<&Quote> {
+        let origin = self.active_origin.as_deref();
+        let unseen: Vec<usize> = (0..self.quotes.len())
+            .filter(|&i| matches_origin(origin, &self.quotes[i]) && !seen.contains(&quote_hash(&self.quotes[i].text)))
+            .collect();
+        if unseen.is_empty() {
+            return None;
+        }
+        let index = unseen[self.rng.gen_range(0..unseen.len())];
+        self.quotes.get(index)
+    }
+
+    /// The loaded quote whose text is exactly `text`
+    pub fn find_by_text(&self, text: &str) -> Option<&Quote> {
+        self.quotes.iter().find(|quote| quote.text == text)
+    }
+
+    /// Quote at `index` in load order, as used by the memorize schedule
+    pub fn quote_at(&self, index: usize) -> Option<&Quote> {
+        self.quotes.get(index)
+    }
+
+    /// Indices of quotes with at most `max_chars` characters
+    pub fn short_quote_indices(&self, max_chars: usize) -> Vec<usize> {
+        self.quotes
+            .iter()
+            .enumerate()
+            .filter(|(_, quote)| quote.char_count() <= max_chars)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
     pub fn total_quotes(&self) -> usize {
         self.quotes.len()
     }
-} 
\ No newline at end of file
+
+    /// Number of loaded quotes in each category name, ignoring the active filters
+    pub fn category_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for quote in &self.quotes {
+            let name = if quote.category.is_empty() { UNCATEGORIZED } else { quote.category.as_str() };
+            *counts.entry(name.to_string()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Loaded category names in `group`, sorted; legacy quotes without a category are left out
+    pub fn categories_in_group(&self, group: CategoryGroup) -> Vec<String> {
+        let counts = self.category_counts();
+        group.categories(counts.keys().map(String::as_str).filter(|name| *name != UNCATEGORIZED))
+    }
+
+    /// One line per category with its quote count, sorted alphabetically (ignoring case).
+    /// Subcategories of `CATEGORY_GROUPS` are indented under their group, whose count
+    /// includes theirs.
+    pub fn category_listing(&self) -> Vec<String> {
+        let counts = self.category_counts();
+        let group_of = |name: &str| {
+            CATEGORY_GROUPS.iter().copied().find(|group| name.len() > group.len() && name.starts_with(group))
+        };
+
+        // Groups are listed even when no quote uses the bare group name
+        let mut top_level: Vec<&str> = counts.keys().map(String::as_str).filter(|name| group_of(name).is_none()).collect();
+        for group in CATEGORY_GROUPS {
+            if !counts.contains_key(group) && counts.keys().any(|name| group_of(name) == Some(group)) {
+                top_level.push(group);
+            }
+        }
+        top_level.sort_by_key(|name| name.to_lowercase());
+
+        let mut lines = Vec::new();
+        for name in top_level {
+            let mut members: Vec<(&String, &usize)> = counts.iter().filter(|(sub, _)| group_of(sub) == Some(name)).collect();
+            members.sort_by_key(|(sub, _)| sub.to_lowercase());
+            let total = counts.get(name).copied().unwrap_or(0) + members.iter().map(|(_, count)| **count).sum::<usize>();
+            lines.push(format!("{} ({})", name, total));
+            for (sub, count) in members {
+                lines.push(format!("  {} ({})", sub, count));
+            }
+        }
+        lines
+    }
+
+    /// Quotes whose text or source contains `query`, ignoring case
+    pub fn search(&self, query: &str) -> Vec<&Quote> {
+        let query = query.to_lowercase();
+        self.quotes
+            .iter()
+            .zip(&self.search_keys)
+            .filter(|(_, (text, source))| text.contains(&query) || source.contains(&query))
+            .map(|(quote, _)| quote)
+            .collect()
+    }
+}
+
+/// Stable identifier for a quote's text, used to store favorites: 64-bit FNV-1a as hex.
+/// Unlike `DefaultHasher` it doesn't change between Rust releases.
+pub fn quote_hash(text: &str) -> String {
+    let hash = text.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Whether `name` can be used as a category: letters and digits only, like `Programming`
+/// or `SacredTextsQuran`
+pub fn is_valid_category_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// File name a category's added quotes go to: the name in snake case, so `HardStockReports`
+/// is `hard_stock_reports.json`
+pub fn category_file_name(category: &str) -> String {
+    let mut name = String::new();
+    let mut prev_lower = false;
+    for c in category.chars() {
+        if c.is_ascii_uppercase() && prev_lower {
+            name.push('_');
+        }
+        prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        name.push(c.to_ascii_lowercase());
+    }
+    format!("{}.json", name)
+}
+
+/// Append `quote` to `<dir>/<category_file_name>`, or if that doesn't exist, to the first file
+/// by name that already holds quotes of its category, or else create the former. Returns the
+/// file written.
+pub fn add_quote_to_directory<P: AsRef<Path>>(dir: P, quote: &Quote) -> io::Result<PathBuf> {
+    let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidInput, msg);
+    if quote.text.trim().is_empty() {
+        return Err(invalid("quote text is empty".to_string()));
+    }
+    if quote.source.trim().is_empty() {
+        return Err(invalid("quote source is empty".to_string()));
+    }
+    if !is_valid_category_name(&quote.category) {
+        return Err(invalid(format!("invalid category name '{}', use letters and digits only", quote.category)));
+    }
+
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let own_file = dir.join(category_file_name(&quote.category));
+    let mut target = None;
+    if !own_file.exists() {
+        // Sorted, so the same file is picked whatever order the filesystem lists them in
+        let mut paths: Vec<_> = fs::read_dir(dir)?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+        for path in paths {
+            let content = fs::read_to_string(&path)?;
+            if let Ok(quotes) = serde_json::from_str::<Vec<Quote>>(&content) {
+                if quotes.first().is_some_and(|q| q.category.eq_ignore_ascii_case(&quote.category)) {
+                    target = Some((path, quotes));
+                    break;
+                }
+            }
+        }
+    }
+
+    let (path, mut quotes) = match target {
+        Some(found) => found,
+        None => {
+            let path = own_file;
+            // Never overwrite a file that exists but can't be read back as quotes
+            let quotes = match fs::read_to_string(&path) {
+                Ok(content) => serde_json::from_str(&content).map_err(|e| invalid(format!("{}: {}", path.display(), e)))?,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+                Err(e) => return Err(e),
+            };
+            (path, quotes)
+        }
+    };
+    quotes.push(quote.clone());
+    fs::write(&path, serde_json::to_string_pretty(&quotes)?)?;
+    Ok(path)
+}
+
+fn matches_origin(origin: Option<&str>, quote: &Quote) -> bool {
+    match origin {
+        Some(origin) => quote.origin.eq_ignore_ascii_case(origin),
+        None => true,
+    }
+}
+
+/// Pick one of the quotes at `indices`. Without an origin filter this samples an index
+/// directly, so it takes the same time however many quotes are loaded.
+fn choose_indexed(rng: &mut StdRng, quotes: &[Quote], indices: &[usize], origin: Option<&str>) -> Option<Quote> {
+    if origin.is_none() {
+        if indices.is_empty() {
+            return None;
+        }
+        return Some(quotes[indices[rng.gen_range(0..indices.len())]].clone());
+    }
+    let matching_quotes: Vec<_> = indices.iter()
+        .map(|&i| &quotes[i])
+        .filter(|q| matches_origin(origin, q))
+        .collect();
+    choose(rng, &matching_quotes)
+}
+
+fn choose(rng: &mut StdRng, quotes: &[&Quote]) -> Option<Quote> {
+    if quotes.is_empty() {
+        None
+    } else {
+        Some(quotes[rng.gen_range(0..quotes.len())].clone())
+    }
+}
+
+/// Built-in quotes used when no quote files can be loaded
+pub fn default_quotes() -> Vec<Quote> {
+    let quote = |text: &str, source: &str, difficulty| Quote {
+        text: text.to_string(),
+        source: source.to_string(),
+        difficulty,
+        category: "Default".to_string(),
+        origin: "English".to_string(),
+    };
+
+    vec![
+        quote("The quick brown fox jumps over the lazy dog.", "Typing Practice", QuoteDifficulty::Easy),
+        quote("Practice makes perfect.", "Proverb", QuoteDifficulty::Easy),
+        quote("Simplicity is prerequisite for reliability.", "Edsger W. Dijkstra", QuoteDifficulty::Medium),
+        quote("Programs must be written for people to read, and only incidentally for machines to execute.", "Harold Abelson", QuoteDifficulty::Medium),
+        quote("Sphinx of black quartz, judge my vow! (It's 42% faster at 9:30 a.m.)", "Typing Practice", QuoteDifficulty::Hard),
+    ]
+}
@@ -0,0 +1,37 @@
+//! App-wide randomness that can be pinned with `SPRING_KEYS_SEED` for reproducible demos and tests
+
+use once_cell::sync::Lazy;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::sync::{Mutex, MutexGuard};
+
+/// Environment variable holding the seed for all randomness in the app
+pub const SEED_ENV_VAR: &str = "SPRING_KEYS_SEED";
+
+/// Generator shared by code without one of its own, e.g. the moosesay animation
+static GLOBAL_RNG: Lazy<Mutex<StdRng>> = Lazy::new(|| Mutex::new(new_rng()));
+
+/// The seed `SPRING_KEYS_SEED` asks for, if it's set to a number
+pub fn seed_from_env() -> Option<u64> {
+    std::env::var(SEED_ENV_VAR).ok()?.trim().parse().ok()
+}
+
+/// A fresh generator for a subsystem that keeps its own, like quote selection. Seeded
+/// from `SPRING_KEYS_SEED` when set, so each one replays the same sequence, otherwise from entropy.
+pub fn new_rng() -> StdRng {
+    match seed_from_env() {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// The shared generator, seeded from `SPRING_KEYS_SEED` on first use. Don't hold the
+/// guard while calling into code that may lock it again.
+pub fn global_rng() -> MutexGuard<'static, StdRng> {
+    GLOBAL_RNG.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Restart the shared generator from `seed`
+pub fn reseed_global(seed: u64) {
+    *global_rng() = StdRng::seed_from_u64(seed);
+}
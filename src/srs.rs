@@ -0,0 +1,90 @@
+//! Spaced-repetition scheduling for `spring-keys memorize`: short quotes come back
+//! sooner when they were typed slowly or with errors, and later once they're known
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Quotes longer than this many characters aren't served for memorization
+pub const MEMORIZE_MAX_CHARS: usize = 80;
+/// Ease a quote starts with; each review multiplies the interval by it
+pub const DEFAULT_EASE: f64 = 2.5;
+/// Ease never drops below this, so hard quotes still get spaced out eventually
+pub const MIN_EASE: f64 = 1.3;
+/// Delay before a new or failed quote comes back
+pub const FIRST_INTERVAL_SECS: i64 = 10 * 60;
+/// WPM that counts as typing a quote fluently from memory
+pub const FLUENT_WPM: f64 = 40.0;
+
+/// Review state of one quote, keyed by its quote index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SrsCard {
+    pub ease: f64,
+    /// Seconds between the last review and the next
+    pub interval_secs: i64,
+    /// Unix time the quote is due again
+    pub due: i64,
+    /// Reviews passed in a row; a failed review resets it
+    pub streak: u32,
+}
+
+impl SrsCard {
+    pub fn new(now: i64) -> Self {
+        Self { ease: DEFAULT_EASE, interval_secs: 0, due: now, streak: 0 }
+    }
+
+    /// Reschedule after a review of `quality` (0-5, see `grade`), SM-2 style
+    pub fn review(&mut self, quality: u8, now: i64) {
+        let quality = quality.min(5);
+        let miss = f64::from(5 - quality);
+        self.ease = (self.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(MIN_EASE);
+
+        if quality < 3 {
+            self.streak = 0;
+            self.interval_secs = FIRST_INTERVAL_SECS;
+        } else {
+            self.streak += 1;
+            self.interval_secs = match self.streak {
+                1 => FIRST_INTERVAL_SECS,
+                2 => FIRST_INTERVAL_SECS * 6,
+                _ => (self.interval_secs as f64 * self.ease).round() as i64,
+            };
+        }
+        self.due = now + self.interval_secs;
+    }
+}
+
+/// Review quality from 0 (forgotten) to 5 (perfect): mostly accuracy, with a point off
+/// for typing well under `FLUENT_WPM`
+pub fn grade(wpm: f64, accuracy: f64) -> u8 {
+    let quality: u8 = match accuracy {
+        a if a >= 98.0 => 5,
+        a if a >= 95.0 => 4,
+        a if a >= 90.0 => 3,
+        a if a >= 80.0 => 2,
+        a if a >= 60.0 => 1,
+        _ => 0,
+    };
+    if wpm < FLUENT_WPM / 2.0 {
+        quality.saturating_sub(1)
+    } else {
+        quality
+    }
+}
+
+/// Record a review of quote `index` completed at `now`
+pub fn record_review(cards: &mut BTreeMap<usize, SrsCard>, index: usize, wpm: f64, accuracy: f64, now: i64) {
+    cards.entry(index).or_insert_with(|| SrsCard::new(now)).review(grade(wpm, accuracy), now);
+}
+
+/// Quote to serve next from `candidates`: the most overdue card, then the first quote
+/// never reviewed, then whichever card comes due soonest
+pub fn next_quote(cards: &BTreeMap<usize, SrsCard>, candidates: &[usize], now: i64) -> Option<usize> {
+    let scheduled = || candidates.iter().filter_map(|index| cards.get(index).map(|card| (*index, card.due)));
+    if let Some((index, _)) = scheduled().filter(|(_, due)| *due <= now).min_by_key(|(_, due)| *due) {
+        return Some(index);
+    }
+    if let Some(index) = candidates.iter().find(|index| !cards.contains_key(index)) {
+        return Some(*index);
+    }
+    scheduled().min_by_key(|(_, due)| *due).map(|(index, _)| index)
+}
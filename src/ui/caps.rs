@@ -0,0 +1,89 @@
+//! Terminal color capability detection, so basic terminals aren't sent truecolor escapes
+
+use crossterm::style::Color;
+use std::env;
+
+/// How many colors the terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    /// 24-bit RGB colors
+    Truecolor,
+    /// The 16 standard ANSI colors
+    Ansi16,
+}
+
+/// Approximate RGB values of the 16 ANSI colors, for picking the nearest one
+const ANSI16_PALETTE: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::DarkRed, (128, 0, 0)),
+    (Color::DarkGreen, (0, 128, 0)),
+    (Color::DarkYellow, (128, 128, 0)),
+    (Color::DarkBlue, (0, 0, 128)),
+    (Color::DarkMagenta, (128, 0, 128)),
+    (Color::DarkCyan, (0, 128, 128)),
+    (Color::Grey, (192, 192, 192)),
+    (Color::DarkGrey, (128, 128, 128)),
+    (Color::Red, (255, 0, 0)),
+    (Color::Green, (0, 255, 0)),
+    (Color::Yellow, (255, 255, 0)),
+    (Color::Blue, (0, 0, 255)),
+    (Color::Magenta, (255, 0, 255)),
+    (Color::Cyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+impl ColorMode {
+    /// Names accepted by `--color-mode`
+    pub const NAMES: [&'static str; 2] = ["truecolor", "16"];
+
+    /// Parse a `--color-mode` value (case-insensitive)
+    pub fn from_name(name: &str) -> Option<ColorMode> {
+        match name.to_lowercase().as_str() {
+            "truecolor" | "24bit" => Some(ColorMode::Truecolor),
+            "16" | "ansi16" | "basic" => Some(ColorMode::Ansi16),
+            _ => None,
+        }
+    }
+
+    /// Convert `color` to one this mode can display
+    pub fn adapt(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorMode::Ansi16, Color::Rgb { r, g, b }) => nearest_ansi16(r, g, b),
+            _ => color,
+        }
+    }
+}
+
+/// Detect the color mode from the environment
+pub fn detect() -> ColorMode {
+    detect_from(env::var("COLORTERM").ok().as_deref(), env::var("TERM").ok().as_deref())
+}
+
+/// Truecolor only when `$COLORTERM` or `$TERM` advertises it, otherwise the 16 basic colors
+pub fn detect_from(colorterm: Option<&str>, term: Option<&str>) -> ColorMode {
+    let colorterm = colorterm.unwrap_or_default().to_lowercase();
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        return ColorMode::Truecolor;
+    }
+
+    let term = term.unwrap_or_default().to_lowercase();
+    if term.ends_with("-direct") || term.contains("truecolor") || term.contains("24bit") {
+        ColorMode::Truecolor
+    } else {
+        ColorMode::Ansi16
+    }
+}
+
+fn nearest_ansi16(r: u8, g: u8, b: u8) -> Color {
+    let distance = |(pr, pg, pb): (u8, u8, u8)| {
+        let dr = r as i32 - pr as i32;
+        let dg = g as i32 - pg as i32;
+        let db = b as i32 - pb as i32;
+        dr * dr + dg * dg + db * db
+    };
+    ANSI16_PALETTE
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::White)
+}
@@ -0,0 +1,24 @@
+//! Frame pacing for the animated screens (moosesay, screensaver, VGA test)
+
+use std::time::Duration;
+
+/// Lowest frame rate accepted by `--fps`
+pub const MIN_FPS: u32 = 1;
+/// Highest frame rate accepted by `--fps`
+pub const MAX_FPS: u32 = 60;
+
+/// Keep a requested frame rate within `MIN_FPS..=MAX_FPS`
+pub fn clamp_fps(fps: u32) -> u32 {
+    fps.clamp(MIN_FPS, MAX_FPS)
+}
+
+/// Time budget of one frame at `fps` (clamped)
+pub fn frame_duration(fps: u32) -> Duration {
+    Duration::from_secs(1) / clamp_fps(fps)
+}
+
+/// How long to wait after a frame whose drawing took `work`, so frames start
+/// on schedule instead of sleeping a full frame on top of the work
+pub fn frame_delay(fps: u32, work: Duration) -> Duration {
+    frame_duration(fps).saturating_sub(work)
+}
@@ -6,8 +6,11 @@ use crossterm::{
 use std::io::{self, Write};
 use std::time::{Duration, Instant};
 use std::collections::HashMap;
-use crate::core::metrics::{ExtendedStats, TypingMetrics, Finger};
+use crate::core::metrics::{TypingMetrics, Finger, KeyboardRow};
 use crate::ui::color_spectrum::{value_to_spectrum, get_contrasting_text_color};
+use crate::ui::caps::ColorMode;
+#[cfg(feature = "draw-stats")]
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Mutex;
 use once_cell::sync::Lazy;
 
@@ -39,10 +42,6 @@ impl KeyAnimation {
         }
     }
 
-    fn needs_redraw(&self) -> bool {
-        // Only redraw if within fade period of 1 second
-        self.last_press.elapsed() < Duration::from_secs(1)
-    }
 }
 
 // Track key press animations using a thread-safe global
@@ -54,28 +53,50 @@ fn get_animations() -> std::sync::MutexGuard<'static, HashMap<char, KeyAnimation
     KEY_ANIMATIONS.lock().unwrap()
 }
 
+/// Everything that determines how a key looks, to tell whether it changed since the last frame
+#[derive(Clone, PartialEq)]
+struct KeyFrame {
+    bg_color: Color,
+    hits: u32,
+    content: Vec<String>,
+    glow: u8,
+}
+
 // Cache for previous frame's key states
-static PREVIOUS_FRAME: Lazy<Mutex<HashMap<char, (Color, u32)>>> = Lazy::new(|| {
+static PREVIOUS_FRAME: Lazy<Mutex<HashMap<char, KeyFrame>>> = Lazy::new(|| {
     Mutex::new(HashMap::new())
 });
 
-fn should_redraw_key(key: char, bg_color: Color, hits: u32) -> bool {
+/// Number of keyboard keys actually drawn, for checking that unchanged keys are skipped
+#[cfg(feature = "draw-stats")]
+static KEY_DRAWS: AtomicUsize = AtomicUsize::new(0);
+
+/// Total keyboard keys drawn by `draw_unified_keyboard_heatmap` so far
+#[cfg(feature = "draw-stats")]
+pub fn key_draw_count() -> usize {
+    KEY_DRAWS.load(Ordering::Relaxed)
+}
+
+/// Forget the previous frame so the next render redraws every key, e.g. after the screen was cleared
+pub fn invalidate_heatmap_cache() {
+    PREVIOUS_FRAME.lock().unwrap().clear();
+}
+
+/// Current border glow of a key, 0 once its press animation has faded
+fn key_glow(key: char) -> u8 {
+    let intensity = get_animations().get(&key).map(|a| a.glow_intensity()).unwrap_or(0.0);
+    (255.0 * intensity) as u8
+}
+
+fn should_redraw_key(key: char, frame: KeyFrame) -> bool {
     let mut prev_frame = PREVIOUS_FRAME.lock().unwrap();
-    let prev_state = prev_frame.get(&key);
-    
-    // Check if key state changed
-    let should_redraw = match prev_state {
-        Some(&(prev_color, prev_hits)) => {
-            prev_color != bg_color || prev_hits != hits || get_animations().get(&key).map_or(false, |anim| anim.needs_redraw())
-        }
-        None => true // First time seeing this key
-    };
-    
+    let should_redraw = prev_frame.get(&key) != Some(&frame);
+
     // Update cache if redrawing
     if should_redraw {
-        prev_frame.insert(key, (bg_color, hits));
+        prev_frame.insert(key, frame);
     }
-    
+
     should_redraw
 }
 
@@ -84,18 +105,23 @@ fn get_speed_color(speed_ms: f64) -> Color {
     value_to_spectrum(speed_ms)
 }
 
-/// Draw a bordered key with content
-fn draw_key(
-    stdout: &mut impl Write,
+/// A bordered key for `draw_key`: where it goes, its lines of text and their colors
+struct KeyBox<'a> {
     x: u16,
     y: u16,
+    /// Width including both borders
     width: usize,
-    content: &[String],
+    content: &'a [String],
     bg_color: Color,
-    text_colors: &[Color],
-    _is_active: bool, // Prefix with _ to indicate intentionally unused
-) -> io::Result<()> {
+    /// One color per line of `content`
+    text_colors: &'a [Color],
+}
+
+/// Draw a bordered key with content
+fn draw_key(stdout: &mut impl Write, key: &KeyBox, color_mode: ColorMode) -> io::Result<()> {
+    let KeyBox { x, y, width, content, text_colors, .. } = *key;
     let content_height = content.len();
+    let bg_color = color_mode.adapt(key.bg_color);
     
     // Determine dynamic border color (fade from bright purple to black)
     let mut border_color = Color::Black;
@@ -109,7 +135,7 @@ fn draw_key(
             if intensity > 0.0 {
                 // Bright purple RGB(255,0,255) fades to black
                 let v = (255.0 * intensity) as u8;
-                border_color = Color::Rgb { r: v, g: 0, b: v };
+                border_color = color_mode.adapt(Color::Rgb { r: v, g: 0, b: v });
             }
         }
     }
@@ -122,7 +148,7 @@ fn draw_key(
             SetForegroundColor(border_color),
             Print("│"),
             SetBackgroundColor(bg_color),
-            SetForegroundColor(text_colors[i]),
+            SetForegroundColor(color_mode.adapt(text_colors[i])),
             Print(format!("{:^width$}", line, width = width - 2)),
             SetBackgroundColor(Color::Reset),
             SetForegroundColor(border_color),
@@ -157,11 +183,74 @@ fn find_speed_range(heat_map: &HashMap<char, f64>) -> (f64, f64) {
     (min, max)
 }
 
-/// Unified keyboard visualization with large keys, hit counts, and color temperature
+/// Keys of each heatmap row, number row first
+const KEY_ROWS: [&str; 4] = [
+    "1234567890-=",
+    "qwertyuiop[]\\",
+    "asdfghjkl;'",
+    "zxcvbnm,./",
+];
+
+/// How far each row is shifted right, in quarters of a key, as on a real staggered keyboard
+const ROW_STAGGER_QUARTERS: [u16; 4] = [0, 2, 3, 5];
+
+/// Columns from one key's left edge to the next when there's room
+const KEY_PITCH: u16 = 10;
+
+/// Closest the keys get when squeezed into a narrow terminal
+const MIN_KEY_PITCH: u16 = 8;
+
+/// Width of a key, borders included, when there's room
+const KEY_WIDTH: u16 = 8;
+
+/// Where one key of the heatmap goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyPlacement {
+    pub key: char,
+    /// Keyboard row, 0 for the number row
+    pub row: usize,
+    /// Column of the key's left border
+    pub x: u16,
+    /// Width including both borders
+    pub width: u16,
+}
+
+/// Lay out the heatmap keys in staggered rows, shrinking the spacing until the longest
+/// row fits in `width` columns. Below the narrowest layout's width keys run off the right.
+pub fn key_layout(width: u16) -> Vec<KeyPlacement> {
+    let layout_at = |pitch: u16| -> Vec<KeyPlacement> {
+        let key_width = KEY_WIDTH.min(pitch - 1);
+        KEY_ROWS
+            .iter()
+            .zip(ROW_STAGGER_QUARTERS)
+            .enumerate()
+            .flat_map(|(row, (keys, stagger))| {
+                keys.chars().enumerate().map(move |(i, key)| KeyPlacement {
+                    key,
+                    row,
+                    x: stagger * pitch / 4 + i as u16 * pitch,
+                    width: key_width,
+                })
+            })
+            .collect()
+    };
+    let fits = |layout: &[KeyPlacement]| layout.iter().all(|k| k.x + k.width <= width);
+
+    (MIN_KEY_PITCH..=KEY_PITCH)
+        .rev()
+        .map(layout_at)
+        .find(|layout| fits(layout))
+        .unwrap_or_else(|| layout_at(MIN_KEY_PITCH))
+}
+
+/// Unified keyboard visualization with large keys, hit counts, and color temperature,
+/// fitted to a terminal `width` columns wide
 pub fn draw_unified_keyboard_heatmap(
     stdout: &mut impl Write,
     metrics: &TypingMetrics,
-    y_offset: u16
+    y_offset: u16,
+    width: u16,
+    color_mode: ColorMode,
 ) -> io::Result<()> {
     let heat_map = metrics.get_heat_map();
     let geometric_avgs = metrics.get_key_geometric_averages();
@@ -169,66 +258,54 @@ pub fn draw_unified_keyboard_heatmap(
     // Find speed range for color normalization
     let (fastest, slowest) = find_speed_range(&heat_map);
     
-    // Define keyboard layout
-    let rows = [
-        "1234567890-=",
-        "qwertyuiop[]\\",
-        "asdfghjkl;'",
-        "zxcvbnm,./",
-    ];
-    
-    let indents = [0, 0, 1, 2]; // Number of spaces to indent each row
-    
-    // Draw each row of the keyboard
-    for (row_idx, (row, indent)) in rows.iter().zip(indents.iter()).enumerate() {
-        let y = y_offset + (row_idx as u16 * 6); // 6 units per row (4 lines + 2 borders)
+    // Draw each key of the keyboard
+    for KeyPlacement { key: c, row, x, width: key_width } in key_layout(width) {
+        let y = y_offset + (row as u16 * 6); // 6 units per row (4 lines + 2 borders)
         
-        // Draw each key in the row
-        for (key_idx, c) in row.chars().enumerate() {
-            let x = (*indent * 2 + key_idx * 10) as u16; // 10 units per key, 2 units per indent
-            
-            // Get per-key metrics: count, geometric average, and last speed
-            let timings = metrics.key_timings.get(&c).map(|v| v.as_slice()).unwrap_or(&[]);
-            let count = timings.len();
-            let geo_avg = geometric_avgs.get(&c).copied().unwrap_or(0.0);
-            let last_speed = timings.last().copied().unwrap_or(0.0);
-            
-            // Calculate normalized speed (0.0 to 1.0)
-            let normalized_speed = if geo_avg > 0.0 && slowest > fastest {
-                (geo_avg - fastest) / (slowest - fastest)
-            } else {
-                0.0
-            };
-            
-            // Calculate background color based on normalized speed and text colors
-            let bg_color = value_to_spectrum(normalized_speed);
-            let text_colors = vec![
-                get_contrasting_text_color(bg_color),
-                get_contrasting_text_color(bg_color),
-                get_contrasting_text_color(bg_color),
-                get_contrasting_text_color(bg_color),
-            ];
-            
-            // Format key content: char, count, geo avg, last speed
-            let content = vec![
-                c.to_string(),
-                format!("{} hits", count),
-                if geo_avg > 0.0 { format!("{:.0}ms", geo_avg) } else { "---".to_string() },
-                if last_speed > 0.0 { format!("{:.0}ms", last_speed) } else { "---".to_string() },
-            ];
-            
-            // Draw the key with all its information
-            draw_key(
-                stdout,
-                x,
-                y,
-                8, // key width
-                &content,
-                bg_color,
-                &text_colors,
-                !timings.is_empty(),
-            )?;
+        // Get per-key metrics: count, geometric average, and last speed
+        let timings = metrics.key_timings.get(&c).map(|v| v.as_slice()).unwrap_or(&[]);
+        let count = timings.len();
+        let geo_avg = geometric_avgs.get(&c).copied().unwrap_or(0.0);
+        let last_speed = timings.last().copied().unwrap_or(0.0);
+        
+        // Calculate normalized speed (0.0 to 1.0)
+        let normalized_speed = if geo_avg > 0.0 && slowest > fastest {
+            (geo_avg - fastest) / (slowest - fastest)
+        } else {
+            0.0
+        };
+        
+        // Calculate background color based on normalized speed and text colors
+        let bg_color = value_to_spectrum(normalized_speed);
+        let text_colors = vec![
+            get_contrasting_text_color(bg_color),
+            get_contrasting_text_color(bg_color),
+            get_contrasting_text_color(bg_color),
+            get_contrasting_text_color(bg_color),
+        ];
+        
+        // Format key content: char, count, geo avg, last speed
+        let content = vec![
+            c.to_string(),
+            format!("{} hits", count),
+            if geo_avg > 0.0 { format!("{:.0}ms", geo_avg) } else { "---".to_string() },
+            if last_speed > 0.0 { format!("{:.0}ms", last_speed) } else { "---".to_string() },
+        ];
+        
+        // Skip keys that look exactly like they did last frame
+        let frame = KeyFrame { bg_color, hits: count as u32, content: content.clone(), glow: key_glow(c) };
+        if !should_redraw_key(c, frame) {
+            continue;
         }
+        #[cfg(feature = "draw-stats")]
+        KEY_DRAWS.fetch_add(1, Ordering::Relaxed);
+
+        // Draw the key with all its information
+        draw_key(
+            stdout,
+            &KeyBox { x, y, width: key_width as usize, content: &content, bg_color, text_colors: &text_colors },
+            color_mode,
+        )?;
     }
 
     // Calculate base Y position for finger metrics (4 rows * 5 units + 2 units padding)
@@ -266,13 +343,15 @@ pub fn draw_unified_keyboard_heatmap(
             // Draw mini bordered metric
             draw_key(
                 stdout,
-                x,
-                finger_metrics_y,
-                9,
-                &[label.to_string(), format!("{:3.0}ms", stats.current)],
-                bg_color,
-                &[text_color, text_color],
-                stats.current > 0.0,
+                &KeyBox {
+                    x,
+                    y: finger_metrics_y,
+                    width: 9,
+                    content: &[label.to_string(), format!("{:3.0}ms", stats.current)],
+                    bg_color,
+                    text_colors: &[text_color, text_color],
+                },
+                color_mode,
             )?;
         }
     }
@@ -287,16 +366,147 @@ pub fn draw_unified_keyboard_heatmap(
         )?;
     }
 
+    // Draw the per-row breakdown so a slow number row stands out
+    let row_summary = metrics.row_summary();
+    let rows: Vec<String> = KeyboardRow::ALL
+        .iter()
+        .filter_map(|row| row_summary.get(row).map(|(wpm, accuracy)| format!("{} {:.0} WPM {:.0}%", row.name(), wpm, accuracy)))
+        .collect();
+    if !rows.is_empty() {
+        queue!(
+            stdout,
+            MoveTo(0, finger_metrics_y + 5),
+            SetForegroundColor(Color::White),
+            Print(format!("{:<100}", format!("Rows: {}", rows.join(" | "))))
+        )?;
+    }
+
     // Draw legend at the bottom
-    draw_legend(stdout, finger_metrics_y + 6)?;
+    draw_legend(stdout, finger_metrics_y + 6, color_mode)?;
+
+    // Draw finger load balance below the legend
+    draw_finger_load(stdout, metrics, finger_metrics_y + 8)?;
     
     Ok(())
 }
 
+/// Braille cells from empty to full, filling from the bottom up like a bar
+const BRAILLE_LEVELS: [char; 5] = ['⠀', '⣀', '⣤', '⣶', '⣿'];
+
+/// Columns per key in the compact heatmap: the key, its Braille cell and a gap
+const COMPACT_KEY_PITCH: u16 = 3;
+
+/// Rows the compact heatmap takes up, one per keyboard row
+pub const COMPACT_HEATMAP_HEIGHT: u16 = KEY_ROWS.len() as u16;
+
+/// Columns the compact heatmap takes up, staggered rows included
+pub const COMPACT_HEATMAP_WIDTH: u16 = 2 + 13 * COMPACT_KEY_PITCH;
+
+/// Braille cell for a key's normalized speed, from 0.0 (fastest, one row of dots)
+/// to 1.0 (slowest, all eight dots)
+pub fn speed_to_braille(normalized_speed: f64) -> char {
+    let filled = 1 + (normalized_speed.clamp(0.0, 1.0) * 3.0).round() as usize;
+    BRAILLE_LEVELS[filled]
+}
+
+/// One-line-per-row keyboard heatmap for terminals too short for the full one. Each key
+/// is followed by a Braille cell whose dots and color show how slow it is; keys not yet
+/// typed get an empty cell.
+pub fn draw_compact(
+    stdout: &mut impl Write,
+    metrics: &TypingMetrics,
+    origin: (u16, u16),
+    color_mode: ColorMode,
+) -> io::Result<()> {
+    let heat_map = metrics.get_heat_map();
+    let (fastest, slowest) = find_speed_range(&heat_map);
+
+    for (row, (keys, stagger)) in KEY_ROWS.iter().zip(ROW_STAGGER_QUARTERS).enumerate() {
+        queue!(stdout, MoveTo(origin.0 + stagger / 2, origin.1 + row as u16))?;
+        for key in keys.chars() {
+            let (cell, color) = match heat_map.get(&key) {
+                Some(&avg) => {
+                    let normalized_speed = if slowest > fastest { (avg - fastest) / (slowest - fastest) } else { 0.0 };
+                    (speed_to_braille(normalized_speed), value_to_spectrum(normalized_speed))
+                }
+                None => (BRAILLE_LEVELS[0], Color::DarkGrey),
+            };
+            queue!(
+                stdout,
+                SetForegroundColor(Color::White),
+                Print(key),
+                SetForegroundColor(color_mode.adapt(color)),
+                Print(cell),
+                ResetColor,
+                Print(' ')
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Combined index-finger share above which the load chart warns about over-reliance
+const INDEX_OVERLOAD_SHARE: f64 = 0.5;
+
+/// Draw a one-line bar chart of the share of keystrokes typed by each finger
+fn draw_finger_load(
+    stdout: &mut impl Write,
+    metrics: &TypingMetrics,
+    y: u16,
+) -> io::Result<()> {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let load = metrics.finger_load();
+    let share = |finger: Finger| load.get(&finger).copied().unwrap_or(0.0);
+    let index_share = share(Finger::LeftIndex) + share(Finger::RightIndex);
+    let overloaded = index_share > INDEX_OVERLOAD_SHARE;
+
+    let fingers = [
+        ("LP", share(Finger::LeftPinky)),
+        ("LR", share(Finger::LeftRing)),
+        ("LM", share(Finger::LeftMiddle)),
+        ("LI", share(Finger::LeftIndex)),
+        ("TH", share(Finger::LeftThumb) + share(Finger::RightThumb)),
+        ("RI", share(Finger::RightIndex)),
+        ("RM", share(Finger::RightMiddle)),
+        ("RR", share(Finger::RightRing)),
+        ("RP", share(Finger::RightPinky)),
+    ];
+
+    queue!(
+        stdout,
+        MoveTo(0, y),
+        SetForegroundColor(Color::White),
+        Print("Finger Load: ")
+    )?;
+
+    for (label, value) in fingers {
+        let bar = BARS[((value * (BARS.len() - 1) as f64).round() as usize).min(BARS.len() - 1)];
+        let is_index = label == "LI" || label == "RI";
+        let color = if overloaded && is_index { Color::Yellow } else { Color::White };
+        queue!(
+            stdout,
+            SetForegroundColor(color),
+            Print(format!("{} {} {:3.0}%  ", label, bar, value * 100.0))
+        )?;
+    }
+
+    let warning = if overloaded { "Index-heavy!" } else { "" };
+    queue!(
+        stdout,
+        SetForegroundColor(Color::Yellow),
+        Print(format!("{:<12}", warning)),
+        ResetColor
+    )?;
+
+    Ok(())
+}
+
 /// Draw a legend explaining the key information layout
 fn draw_legend(
     stdout: &mut impl Write,
     y_offset: u16,
+    color_mode: ColorMode,
 ) -> io::Result<()> {
     // Draw color spectrum explanation
     queue!(
@@ -311,7 +521,7 @@ fn draw_legend(
     let values = [0.0, 0.25, 0.5, 0.75, 1.0];
     
     for (i, (&value, label)) in values.iter().zip(labels.iter()).enumerate() {
-        let color = value_to_spectrum(value);
+        let color = color_mode.adapt(value_to_spectrum(value));
         queue!(
             stdout,
             MoveTo(13 + (i * 12) as u16, y_offset),
@@ -369,27 +579,6 @@ fn get_heat_color(normalized: f64) -> String {
     format!("\x1b[38;2;{};{};{}m█\x1b[0m", r, g, b)
 }
 
-fn draw_finger_metrics(
-    stdout: &mut io::Stdout,
-    x: u16,
-    y: u16,
-    label: &str,
-    stats: &ExtendedStats,
-    text_color: Color,
-    bg_color: Color,
-) -> io::Result<()> {
-    draw_key(
-        stdout,
-        x,
-        y,
-        9,
-        &[label.to_string(), format!("{:3.0}ms", stats.current)],
-        bg_color,
-        &[text_color, text_color],
-        stats.current > 0.0,
-    )
-}
-
 // Public API to register a key press for animation
 pub fn register_key_press(key: char) {
     let mut animations = KEY_ANIMATIONS.lock().unwrap();
@@ -0,0 +1,59 @@
+//! Input-to-render latency instrumentation for tuning the UI loop
+
+use std::time::Duration;
+
+/// Environment variable that turns on latency measurement in `TerminalUI::run`
+pub const PERF_ENV_VAR: &str = "SPRING_KEYS_PERF";
+
+/// Whether `SPRING_KEYS_PERF` asks for latency measurement ("1" or "true")
+pub fn perf_enabled_from_env() -> bool {
+    matches!(std::env::var(PERF_ENV_VAR).as_deref(), Ok("1") | Ok("true"))
+}
+
+/// Collected key-to-flush latencies
+#[derive(Debug, Clone, Default)]
+pub struct LatencyStats {
+    samples: Vec<Duration>,
+}
+
+impl LatencyStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, latency: Duration) {
+        self.samples.push(latency);
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Nearest-rank percentile, `p` from 0 to 100. `None` without samples.
+    pub fn percentile(&self, p: f64) -> Option<Duration> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut sorted = self.samples.clone();
+        sorted.sort();
+        let rank = (p.clamp(0.0, 100.0) / 100.0 * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.max(1) - 1])
+    }
+
+    /// One-line p50/p99 report for the log
+    pub fn summary(&self) -> String {
+        match (self.percentile(50.0), self.percentile(99.0)) {
+            (Some(p50), Some(p99)) => format!(
+                "Input-to-render latency over {} keys: p50 {:.2}ms, p99 {:.2}ms",
+                self.len(),
+                p50.as_secs_f64() * 1000.0,
+                p99.as_secs_f64() * 1000.0
+            ),
+            _ => "Input-to-render latency: no keys recorded".to_string(),
+        }
+    }
+}
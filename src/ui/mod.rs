@@ -1,56 +1,261 @@
 use crossterm::{
-    event::{self, Event, KeyCode, KeyModifiers},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers, MouseButton, MouseEventKind},
     terminal::{self, enable_raw_mode, disable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, Clear, ClearType},
-    style::{Color, Print, SetForegroundColor, ResetColor, SetBackgroundColor},
+    style::{Attribute, Color, Print, SetAttribute, SetForegroundColor, ResetColor, SetBackgroundColor},
     cursor::{MoveTo, Hide, Show},
     queue,
     execute,
 };
-use std::io::{self, Write, Stdout};
+use std::io::{self, Write};
+use std::ops::Range;
 use crate::SpringKeys;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use log::info;
 use crate::quotes::CategoryCycle;
+use crate::core::state::GameType;
+use crate::input::{char_diff, CharState};
+use crate::config::{CursorStyle, DisplayFormat, RenderMode};
+use theme::Theme;
+use caps::ColorMode;
 
+pub mod caps;
+pub mod frame_rate;
 pub mod heatmap;
+pub mod latency;
+pub mod results;
+pub mod theme;
 pub mod color_spectrum;
 
+/// Smallest terminal width that fits the full keyboard heatmap
+pub const MIN_TERMINAL_WIDTH: u16 = 128;
+/// Smallest terminal height that fits the heatmap, typing area and category indicators
+pub const MIN_TERMINAL_HEIGHT: u16 = 43;
+/// How long the cursor flashes red after a wrong key in stop-on-error mode
+const REJECTION_FLASH_DURATION: Duration = Duration::from_millis(300);
+
+/// First row of the typing area below the heatmap; the input line is three rows further down
+const TYPING_AREA_Y: u16 = 35;
+/// Offset of the input line within the typing area
+const INPUT_ROW_OFFSET: u16 = 3;
+/// First row of the typing area in minimal mode, right under the stat line
+const MINIMAL_TYPING_AREA_Y: u16 = 2;
+/// Rows from the top of the typing area down to the compact heatmap
+const COMPACT_HEATMAP_OFFSET: u16 = 7;
+/// Smallest terminal width that fits the compact heatmap
+pub const MIN_COMPACT_TERMINAL_WIDTH: u16 = heatmap::COMPACT_HEATMAP_WIDTH;
+/// Smallest terminal height that fits the typing area and the compact heatmap under it
+pub const MIN_COMPACT_TERMINAL_HEIGHT: u16 = MINIMAL_TYPING_AREA_Y + COMPACT_HEATMAP_OFFSET + heatmap::COMPACT_HEATMAP_HEIGHT;
+
+/// Width of the results card shown after each quote
+const RESULTS_CARD_WIDTH: u16 = 60;
+/// Row the results card starts on, over the heatmap
+const RESULTS_CARD_Y: u16 = 6;
+
+/// Columns reserved after the header for the goal banner or a notice
+const HEADER_BANNER_WIDTH: usize = 40;
+
+/// Number of expected→typed substitutions listed next to the error counts
+const TOP_CONFUSIONS_SHOWN: usize = 3;
+
+/// Placeholder shown for each typed character in blind mode
+pub const BLIND_PLACEHOLDER: char = '•';
+
+/// Text to display for the user's input; in blind mode every character is masked
+/// so the typist can't look at their output
+pub fn render_input(text: &str, blind: bool) -> String {
+    if blind {
+        text.chars().map(|_| BLIND_PLACEHOLDER).collect()
+    } else {
+        text.to_string()
+    }
+}
+
+/// Format a WPM or accuracy value with the configured precision and grouping
+pub fn format_metric(value: f64, fmt: DisplayFormat) -> String {
+    let formatted = format!("{:.*}", fmt.precision, value);
+    if !fmt.thousands_separator {
+        return formatted;
+    }
+
+    let (sign, unsigned) = match formatted.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", formatted.as_str()),
+    };
+    let (integer, fraction) = match unsigned.find('.') {
+        Some(dot) => unsigned.split_at(dot),
+        None => (unsigned, ""),
+    };
+    let mut grouped = String::new();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    format!("{}{}{}", sign, grouped, fraction)
+}
+
+/// Map a click to a character index in an input line that starts at `first_row` and
+/// wraps every `width` columns. Clicks past the end land after the last character;
+/// clicks outside the rows the input occupies return `None`.
+pub fn click_to_char_index(column: u16, row: u16, first_row: u16, width: u16, len: usize) -> Option<usize> {
+    if width == 0 || row < first_row {
+        return None;
+    }
+    let width = width as usize;
+    // One extra slot for the cursor after the last character
+    let rows = len / width + 1;
+    let line = (row - first_row) as usize;
+    if line >= rows {
+        return None;
+    }
+    Some((line * width + column as usize).min(len))
+}
+
+/// Split `text` into lines of at most `width` chars for the typing area, breaking after
+/// the last space that fits, or mid-word when a word is longer than a line. Returns each
+/// line's char range; together they cover the whole text.
+pub fn wrap_lines(text: &str, width: usize) -> Vec<Range<usize>> {
+    let chars: Vec<char> = text.chars().collect();
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut start = 0;
+    while chars.len() - start > width {
+        let end = start + width;
+        let split = chars[start..end].iter().rposition(|c| *c == ' ').map_or(end, |i| start + i + 1);
+        lines.push(start..split);
+        start = split;
+    }
+    lines.push(start..chars.len());
+    lines
+}
+
+/// The line of `lines` holding char `cursor`. Past the end of the text that's the last
+/// line, where the cursor sits after the final character.
+pub fn line_at(lines: &[Range<usize>], cursor: usize) -> Range<usize> {
+    lines
+        .iter()
+        .find(|line| line.contains(&cursor))
+        .or(lines.last())
+        .cloned()
+        .unwrap_or(0..0)
+}
+
+/// Text shown for typing: word by word (or by sentence) only the word or sentence being
+/// typed, otherwise the whole quote
+fn displayed_text(app: &SpringKeys) -> &str {
+    let Some(session) = &app.typing_session else {
+        return "";
+    };
+    if app.game_state.current_game == GameType::WordByWord {
+        session.current_word().unwrap_or_default()
+    } else if app.game_state.current_game == GameType::SentenceBySentence {
+        session.current_sentence().unwrap_or_default()
+    } else {
+        &session.quote_text
+    }
+}
+
+/// Foreground and optional background for one quote character: green when typed
+/// correctly, red when wrong, dim when not typed yet, with the cursor position highlighted
+pub fn quote_char_colors(state: CharState, at_cursor: bool, theme: &Theme) -> (Color, Option<Color>) {
+    if at_cursor {
+        return (theme.cursor_fg, Some(theme.cursor_bg));
+    }
+    let fg = match state {
+        CharState::Correct => theme.correct,
+        CharState::Wrong => theme.error,
+        CharState::Pending => theme.muted,
+    };
+    (fg, None)
+}
+
+/// How to draw the input cursor cell and the markers above and below it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CursorRender {
+    pub fg: Color,
+    pub bg: Option<Color>,
+    pub attribute: Option<Attribute>,
+    /// Markers on the rows above and below the cursor; a space draws nothing
+    pub markers: (char, char),
+}
+
+/// Cursor look for `style`, in the error color right after a strict-mode rejection
+pub fn render_cursor(style: CursorStyle, rejected: bool, theme: &Theme) -> CursorRender {
+    let accent = if rejected { theme.error } else { theme.cursor_bg };
+    match style {
+        CursorStyle::Block => CursorRender { fg: theme.cursor_fg, bg: Some(accent), attribute: None, markers: ('▼', '▲') },
+        CursorStyle::Underline => CursorRender { fg: accent, bg: None, attribute: Some(Attribute::Underlined), markers: (' ', ' ') },
+        CursorStyle::Bar => CursorRender { fg: accent, bg: None, attribute: Some(Attribute::Bold), markers: ('│', '│') },
+        CursorStyle::Bracket => CursorRender { fg: accent, bg: None, attribute: None, markers: ('⎴', '⎵') },
+    }
+}
+
+/// First row of the typing area, which moves up when the heatmap is hidden
+fn typing_area_y(mode: RenderMode) -> u16 {
+    match mode {
+        RenderMode::Full => TYPING_AREA_Y,
+        RenderMode::Minimal | RenderMode::Compact => MINIMAL_TYPING_AREA_Y,
+    }
+}
+
+/// The layout actually drawn for `mode`: the full layout falls back to the compact
+/// heatmap in a terminal too small for it
+pub fn effective_render_mode(mode: RenderMode, width: u16, height: u16) -> RenderMode {
+    if mode == RenderMode::Full && !terminal_fits_layout(width, height) {
+        RenderMode::Compact
+    } else {
+        mode
+    }
+}
+
+/// Check whether a terminal of the given size can hold the full layout
+pub fn terminal_fits_layout(width: u16, height: u16) -> bool {
+    width >= MIN_TERMINAL_WIDTH && height >= MIN_TERMINAL_HEIGHT
+}
+
 pub struct TerminalUI {
-    stdout: Stdout,
+    stdout: Box<dyn Write>,
     should_quit: bool,
     terminal_size: (u16, u16),
+    color_mode: ColorMode,
+    alternate_screen: bool,
 }
 
 impl TerminalUI {
     pub fn new() -> io::Result<Self> {
-        Ok(Self {
-            stdout: io::stdout(),
+        Ok(Self::with_output(Box::new(io::stdout()), terminal::size()?))
+    }
+
+    /// Draw to `out` as if it were a terminal of `size`
+    fn with_output(out: Box<dyn Write>, size: (u16, u16)) -> Self {
+        Self {
+            stdout: out,
             should_quit: false,
-            terminal_size: terminal::size()?,
-        })
+            terminal_size: size,
+            color_mode: caps::detect(),
+            alternate_screen: true,
+        }
+    }
+
+    /// Override the detected color mode, e.g. from `--color-mode`
+    pub fn set_color_mode(&mut self, mode: ColorMode) {
+        self.color_mode = mode;
+    }
+
+    /// Draw inline instead of on the alternate screen, e.g. from `--no-altscreen`
+    pub fn set_alternate_screen(&mut self, enabled: bool) {
+        self.alternate_screen = enabled;
     }
 
     pub fn init(&mut self) -> io::Result<()> {
         // Enable raw mode
         enable_raw_mode()?;
-        
-        // Switch to alternate screen and hide cursor
-        execute!(
-            self.stdout,
-            EnterAlternateScreen,
-            Hide,
-            Clear(ClearType::All)
-        )?;
-
-        Ok(())
+        self.enter_screen()
     }
 
     pub fn cleanup(&mut self) -> io::Result<()> {
-        // Show cursor and leave alternate screen
-        execute!(
-            self.stdout,
-            Show,
-            LeaveAlternateScreen
-        )?;
+        self.leave_screen()?;
         
         // Disable raw mode
         disable_raw_mode()?;
@@ -58,9 +263,60 @@ impl TerminalUI {
         Ok(())
     }
 
+    /// Get a blank screen to draw on and hide the cursor
+    fn enter_screen(&mut self) -> io::Result<()> {
+        if self.alternate_screen {
+            execute!(
+                self.stdout,
+                EnterAlternateScreen,
+                EnableMouseCapture,
+                Hide,
+                Clear(ClearType::All)
+            )?;
+        } else {
+            // Scroll whatever is on screen into scrollback instead of clearing it, so the
+            // layout's absolute rows start out blank without losing the shell history
+            let height = self.terminal_size.1;
+            execute!(
+                self.stdout,
+                Print("\r\n".repeat(usize::from(height))),
+                EnableMouseCapture,
+                Hide,
+                MoveTo(0, 0)
+            )?;
+        }
+        // The screen is blank, so every heatmap key needs drawing again
+        heatmap::invalidate_heatmap_cache();
+
+        Ok(())
+    }
+
+    /// Show the cursor again and hand the terminal back
+    fn leave_screen(&mut self) -> io::Result<()> {
+        if self.alternate_screen {
+            execute!(
+                self.stdout,
+                Show,
+                DisableMouseCapture,
+                LeaveAlternateScreen
+            )
+        } else {
+            // Leave the last frame in place and put the shell prompt below it
+            let bottom = self.terminal_size.1.saturating_sub(1);
+            execute!(
+                self.stdout,
+                MoveTo(0, bottom),
+                Print("\r\n"),
+                Show,
+                DisableMouseCapture
+            )
+        }
+    }
+
     pub fn run(&mut self, app: &mut SpringKeys) -> io::Result<()> {
         // Initialize with a random typing text from the quotes database
         app.start_typing_session(None);
+        app.show_results_card = true;
         
         // Check if demo heatmap mode is enabled via an environment variable
         let demo_heatmap = std::env::var("SPRING_KEYS_DEMO_HEATMAP").is_ok();
@@ -73,19 +329,63 @@ impl TerminalUI {
             }
         }
         
+        // With SPRING_KEYS_PERF set, time each key from being read until its frame is flushed
+        let mut latency = latency::perf_enabled_from_env().then(latency::LatencyStats::new);
+        let mut key_read_at: Option<Instant> = None;
+        let mut results_pause = results::ResultsPause::default();
+
         while !self.should_quit {
             self.draw_ui(app)?;
+            if let (Some(stats), Some(read_at)) = (latency.as_mut(), key_read_at.take()) {
+                stats.record(read_at.elapsed());
+            }
+            let pause = Duration::from_millis(app.config.preferences.completion_pause_ms);
+            if results_pause.tick(app.pending_results.is_some(), pause, Instant::now()) {
+                self.close_results(app)?;
+                continue;
+            }
+            app.autosave_if_due(crate::AUTOSAVE_INTERVAL);
+            self.end_session_if_idle(app)?;
             
             if event::poll(Duration::from_millis(100))? {
-                if let Event::Key(key_event) = event::read()? {
+                let event = event::read()?;
+                if matches!(event, Event::Key(_)) {
+                    key_read_at = Some(Instant::now());
+                }
+                if let Event::Resize(width, height) = event {
+                    self.handle_resize(width, height)?;
+                    continue;
+                }
+                // Clicking in the input line moves the cursor there
+                if let Event::Mouse(mouse) = event {
+                    if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
+                        // Only the line of input under the quote line being typed is shown
+                        let input_row = typing_area_y(self.render_mode(app)) + INPUT_ROW_OFFSET;
+                        let (_, shown_input) = self.typing_line(app);
+                        if let Some(index) = click_to_char_index(mouse.column, mouse.row, input_row, self.terminal_size.0, shown_input.len()) {
+                            app.input_processor.cursor_position = shown_input.start + index;
+                        }
+                    }
+                    continue;
+                }
+                if let Event::Key(key_event) = event {
                     // Process exit command (Ctrl+C or Esc)
                     if key_event.code == KeyCode::Char('c') && key_event.modifiers == KeyModifiers::CONTROL 
                         || key_event.code == KeyCode::Esc {
                         self.should_quit = true;
                         continue;
                     }
+
+                    // Any key closes the results card and starts the next quote
+                    if app.pending_results.is_some() {
+                        self.close_results(app)?;
+                        continue;
+                    }
                     
                     match key_event.code {
+                        KeyCode::Enter if app.game_state.current_game == GameType::WordByWord => {
+                            app.submit_word();
+                        },
                         KeyCode::Enter => {
                             // Clear input and load new quote without resetting stats
                             app.input_processor.clear();
@@ -97,23 +397,29 @@ impl TerminalUI {
                         },
                         KeyCode::F(6) => {
                             // Switch to typewriter quotes
-                            app.quote_db.set_active_category(CategoryCycle::Typewriter);
-                            app.start_typing_session(None);
+                            app.select_category(CategoryCycle::Typewriter);
                         },
                         KeyCode::F(7) => {
                             // Switch to programming quotes
-                            app.quote_db.set_active_category(CategoryCycle::Programming);
-                            app.start_typing_session(None);
+                            app.select_category(CategoryCycle::Programming);
                         },
                         KeyCode::F(8) => {
-                            // TODO: investigate quote transition on F8 key (Literature category)
                             // Switch to literature quotes
-                            app.quote_db.set_active_category(CategoryCycle::Literature);
-                            app.start_typing_session(None);
+                            app.select_category(CategoryCycle::Literature);
+                        },
+                        KeyCode::F(9) => {
+                            // Bookmark the current quote for `practice --favorites`
+                            app.toggle_favorite();
                         },
-                        KeyCode::Backspace => {
-                            // Remove the last character from input
-                            app.input_processor.backspace();
+                        KeyCode::F(12) => {
+                            // Pick up edits to the config file; redraw everything in case the theme changed
+                            let _ = app.reload_config();
+                            heatmap::invalidate_heatmap_cache();
+                            execute!(self.stdout, Clear(ClearType::All))?;
+                        },
+                        KeyCode::Backspace if !key_event.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::SHIFT) => {
+                            // Remove the last character from input; Ctrl/Shift combos go to the input processor
+                            app.backspace();
                         },
                         _ => {
                             app.process_input(key_event.code, key_event.modifiers);
@@ -122,10 +428,43 @@ impl TerminalUI {
                 }
             }
         }
-        
+
+        // Save whatever progress was made since the last autosave
+        app.autosave_if_due(Duration::ZERO);
+
+        if let Some(stats) = &latency {
+            info!("{}", stats.summary());
+        }
+
         Ok(())
     }
 
+    /// Take the results card down and clear the screen for the next quote
+    fn close_results(&mut self, app: &mut SpringKeys) -> io::Result<()> {
+        app.dismiss_results();
+        heatmap::invalidate_heatmap_cache();
+        execute!(self.stdout, Clear(ClearType::All))
+    }
+
+    /// Past `inactivity_timeout_secs` without a key, drop the half-typed quote and show
+    /// the screensaver until a key is pressed, then start over on a fresh quote
+    fn end_session_if_idle(&mut self, app: &mut SpringKeys) -> io::Result<()> {
+        let Some(timeout) = app.config.preferences.inactivity_timeout_secs.map(Duration::from_secs) else {
+            return Ok(());
+        };
+        if app.input_processor.idle_duration().is_none_or(|idle| idle < timeout) {
+            return Ok(());
+        }
+
+        info!("No input for {}s, ending the session", timeout.as_secs());
+        app.dismiss_results();
+        app.start_typing_session(None);
+        crate::moosesay::animate_moose_quote(None, crate::moosesay::DEFAULT_FPS, false, false)?;
+        // The screensaver restores the terminal when it exits, so set it up again
+        self.terminal_size = terminal::size()?;
+        self.init()
+    }
+
     pub fn render_frame(&mut self, app: &SpringKeys) -> io::Result<()> {
         self.draw_ui(app)
     }
@@ -134,89 +473,138 @@ impl TerminalUI {
         self.should_quit
     }
 
-    fn draw_ui(&mut self, app: &SpringKeys) -> io::Result<()> {
-        // Instead of clearing the whole screen, we'll just reset cursor
-        queue!(self.stdout, MoveTo(0, 0))?;
-        
-        // Draw active categories
-        let active_categories = format!(
-            "Active: Type:{:?} Prog:{:?} Lit:{:?}",
-            app.quote_db.get_active_category(),
-            app.quote_db.get_active_category(),
-            app.quote_db.get_active_category(),
+    /// Update the cached terminal size and force a full redraw
+    pub fn handle_resize(&mut self, width: u16, height: u16) -> io::Result<()> {
+        self.terminal_size = (width, height);
+        heatmap::invalidate_heatmap_cache();
+        execute!(self.stdout, Clear(ClearType::All))
+    }
+
+    /// Layout to draw `app` in at the current terminal size
+    fn render_mode(&self, app: &SpringKeys) -> RenderMode {
+        effective_render_mode(app.config.ui.render_mode, self.terminal_size.0, self.terminal_size.1)
+    }
+
+    fn draw_too_small_message(&mut self, theme: &Theme) -> io::Result<()> {
+        let (width, height) = self.terminal_size;
+        let message = format!(
+            "Terminal too small: need {}x{}, have {}x{}. Please resize the window.",
+            MIN_COMPACT_TERMINAL_WIDTH, MIN_COMPACT_TERMINAL_HEIGHT, width, height
         );
         queue!(
             self.stdout,
             MoveTo(0, 0),
-            SetForegroundColor(Color::Yellow),
-            Print(&active_categories),
+            SetForegroundColor(theme.status),
+            Print(message),
             ResetColor
         )?;
+        self.stdout.flush()
+    }
 
-        // Draw metrics if there's an active session
-        if let Some(session) = &app.typing_session {
-            let metrics_text = format!(
-                "Current WPM: {:.1} | Acc: {:.1}% | All-time WPM: {:.1} | All-time Acc: {:.1}% | Total Quotes: {}",
-                session.metrics.wpm,
-                session.metrics.accuracy,
-                app.accumulated_stats.avg_wpm,
-                app.accumulated_stats.avg_accuracy,
-                app.accumulated_stats.total_quotes
-            );
-            queue!(
+    /// Char ranges of the displayed text and of the input shown under it. Long text is
+    /// wrapped to the terminal width and only the line holding the cursor is drawn, with
+    /// the input cut to the same span so the two stay in step. A column is kept free for
+    /// the cursor after the last character.
+    fn typing_line(&self, app: &SpringKeys) -> (Range<usize>, Range<usize>) {
+        let text = displayed_text(app);
+        let width = (self.terminal_size.0 as usize).saturating_sub(1);
+        let cursor = app.input_processor.cursor_position;
+        let line = line_at(&wrap_lines(text, width), cursor);
+        let input_len = app.input_processor.char_len();
+        // Text typed past the end of the quote shows on its last line
+        let input_end = if line.end == text.chars().count() { input_len } else { line.end.min(input_len) };
+        let input = line.start.min(input_len)..input_end.min(line.start + width);
+        (line, input)
+    }
+
+    /// Column and character of the goal-speed pacer on the quote line being shown, if a
+    /// goal is set and the pacer is on that line. Word and sentence modes only show part
+    /// of the quote, so they go without a pacer.
+    fn pacer_cell(&self, app: &SpringKeys) -> Option<(u16, char)> {
+        let goal_wpm = app.config.preferences.goal_wpm?;
+        let session = app.typing_session.as_ref()?;
+        if matches!(app.game_state.current_game, GameType::WordByWord | GameType::SentenceBySentence) {
+            return None;
+        }
+        let pacer = session.pacer_position(goal_wpm);
+        let (line, _) = self.typing_line(app);
+        if !line.contains(&pacer) {
+            return None;
+        }
+        let c = session.quote_text.chars().nth(pacer)?;
+        Some(((pacer - line.start) as u16, c))
+    }
+
+    /// Draw the line of the quote being typed with per-character feedback. Blind mode
+    /// keeps the quote plain, since coloring it would give the typed text away.
+    fn draw_quote(&mut self, app: &SpringKeys, theme: &Theme, y: u16) -> io::Result<()> {
+        if app.typing_session.is_none() {
+            return Ok(());
+        }
+        let text = displayed_text(app);
+        let (line, _) = self.typing_line(app);
+        queue!(self.stdout, MoveTo(0, y))?;
+        if app.config.ui.blind_mode {
+            let shown: String = text.chars().skip(line.start).take(line.len()).collect();
+            return queue!(
                 self.stdout,
-                MoveTo(0, 1),
-                SetForegroundColor(Color::Green),
-                Print(&metrics_text),
+                SetForegroundColor(theme.quote_text),
+                Print(shown),
                 ResetColor
-            )?;
+            );
+        }
 
-            // Draw unified keyboard heatmap with color temperature and hit counts
-            heatmap::draw_unified_keyboard_heatmap(&mut self.stdout, &session.metrics, 3)?;
+        let states = char_diff(text, &app.input_processor.current_text);
+        let cursor = app.input_processor.cursor_position;
+        for (i, (c, state)) in text.chars().zip(states).enumerate().skip(line.start).take(line.len()) {
+            let (fg, bg) = quote_char_colors(state, i == cursor, theme);
+            if let Some(bg) = bg {
+                queue!(self.stdout, SetBackgroundColor(bg))?;
+            }
+            queue!(self.stdout, SetForegroundColor(fg), Print(c), ResetColor)?;
+        }
+        Ok(())
+    }
 
-            // Draw typing area at a position below the visualization
-            let typing_area_y = 35;
-            
-            // Get error count
-            let error_count = session.metrics.errors.len();
+    fn draw_ui(&mut self, app: &SpringKeys) -> io::Result<()> {
+        let theme = Theme::from_name(&app.config.ui.theme).unwrap_or_default().adapted(self.color_mode);
+        let mode = self.render_mode(app);
+        let minimal = mode != RenderMode::Full;
 
-            // Clear the entire typing area first (5 lines: errors, top cursor, quote, input, bottom cursor)
-            for y in typing_area_y..typing_area_y+6 {
+        // Drawing even the compact heatmap into a smaller terminal just produces garbage
+        let (width, height) = self.terminal_size;
+        if mode == RenderMode::Compact && (width < MIN_COMPACT_TERMINAL_WIDTH || height < MIN_COMPACT_TERMINAL_HEIGHT) {
+            return self.draw_too_small_message(&theme);
+        }
+
+        // Instead of clearing the whole screen, we'll just reset cursor
+        queue!(self.stdout, MoveTo(0, 0))?;
+        
+        if !minimal {
+            self.draw_header(app, &theme)?;
+        }
+
+        // Draw metrics if there's an active session
+        if let Some(session) = &app.typing_session {
+            if minimal {
+                let fmt = app.config.ui.display_format;
                 queue!(
                     self.stdout,
-                    MoveTo(0, y),
-                    Print(" ".repeat(self.terminal_size.0 as usize))
+                    MoveTo(0, 0),
+                    SetForegroundColor(theme.metrics),
+                    Print(format!(
+                        "WPM: {} | Acc: {}%",
+                        format_metric(app.current_quote_wpm(), fmt),
+                        format_metric(session.metrics.accuracy, fmt)
+                    )),
+                    ResetColor
                 )?;
+            } else {
+                self.draw_stats(app, &theme)?;
             }
 
-            // Draw speed range and error counts
-            let speed_range = format!(
-                "Speed Range: {}ms (fastest) to {}ms (slowest) | Errors: {} (Session: {}, Total: {})",
-                session.metrics.key_histogram.min as u64,
-                session.metrics.key_histogram.max as u64,
-                error_count,
-                app.accumulated_stats.session_errors,
-                app.accumulated_stats.total_errors
-            );
-            queue!(
-                self.stdout,
-                MoveTo(0, typing_area_y),
-                SetForegroundColor(Color::White),
-                Print(&speed_range),
-                ResetColor
-            )?;
-
-            // Draw the quote text
-            queue!(
-                self.stdout,
-                MoveTo(0, typing_area_y + 2),
-                SetForegroundColor(Color::White),
-                Print(&session.quote_text),
-                ResetColor
-            )?;
-
             // Draw typing area at a position below the visualization
-            let typing_area_y = 35;
+            let typing_area_y = typing_area_y(mode);
             
             // Get error count
             let error_count = session.metrics.errors.len();
@@ -232,92 +620,293 @@ impl TerminalUI {
             }
 
             // Draw error counts and total keystrokes
-            queue!(
-                self.stdout,
-                MoveTo(0, typing_area_y),
-                SetForegroundColor(Color::White),
-                Print(format!("Errors: {} (Total Keys: {})", error_count, total_keystrokes)),
-                ResetColor
-            )?;
+            if !minimal {
+                queue!(
+                    self.stdout,
+                    MoveTo(0, typing_area_y),
+                    SetForegroundColor(theme.quote_text),
+                    Print(format!("Errors: {} (Total Keys: {})", error_count, total_keystrokes)),
+                    ResetColor
+                )?;
+            }
 
-            // Draw the quote text
-            queue!(
-                self.stdout,
-                MoveTo(0, typing_area_y + 2),
-                SetForegroundColor(Color::White),
-                Print(&session.quote_text),
-                ResetColor
-            )?;
+            // Draw the quote text, colored by what has been typed so far
+            self.draw_quote(app, &theme, typing_area_y + 2)?;
 
-            // Draw the input text with cursor
-            let input_text = &app.input_processor.current_text;
-            let cursor_pos = app.input_processor.cursor_position;
+            // Draw the goal-speed pacer over the quote for the user to race
+            if let Some((column, c)) = self.pacer_cell(app) {
+                queue!(
+                    self.stdout,
+                    MoveTo(column, typing_area_y + 2),
+                    SetBackgroundColor(theme.highlight),
+                    SetForegroundColor(theme.cursor_fg),
+                    Print(c),
+                    ResetColor
+                )?;
+            }
+
+            // Draw the input text with cursor (masked in blind mode), for the quote line shown above
+            let (line, shown_input) = self.typing_line(app);
+            let input_text = &render_input(&app.input_processor.current_text, app.config.ui.blind_mode);
+            let input_text: String = input_text.chars().skip(shown_input.start).take(shown_input.len()).collect();
+            let cursor_pos = app.input_processor.cursor_position.saturating_sub(shown_input.start);
             
             // Draw input text
             queue!(
                 self.stdout,
-                MoveTo(0, typing_area_y + 3),
-                SetForegroundColor(Color::Cyan)
+                MoveTo(0, typing_area_y + INPUT_ROW_OFFSET),
+                SetForegroundColor(theme.input)
             )?;
 
-            // Draw text before cursor
+            // Draw text before cursor (cursor position is in chars, not bytes)
             if cursor_pos > 0 {
-                queue!(self.stdout, Print(&input_text[..cursor_pos]))?;
+                let before: String = input_text.chars().take(cursor_pos).collect();
+                queue!(self.stdout, Print(before))?;
             }
 
-            // Draw cursor
+            // Draw cursor, flashing red right after a strict-mode rejection
+            let rejected = app.input_processor.rejected_at
+                .is_some_and(|at| at.elapsed() < REJECTION_FLASH_DURATION);
+            let cursor = render_cursor(app.config.ui.cursor_style, rejected, &theme);
+            if let Some(bg) = cursor.bg {
+                queue!(self.stdout, SetBackgroundColor(bg))?;
+            }
+            if let Some(attribute) = cursor.attribute {
+                queue!(self.stdout, SetAttribute(attribute))?;
+            }
             queue!(
                 self.stdout,
-                SetBackgroundColor(Color::White),
-                SetForegroundColor(Color::Black),
-                Print(if cursor_pos < input_text.len() {
-                    input_text[cursor_pos..=cursor_pos].to_string()
-                } else {
-                    " ".to_string()
-                }),
+                SetForegroundColor(cursor.fg),
+                Print(input_text.chars().nth(cursor_pos).unwrap_or(' ')),
+                SetAttribute(Attribute::Reset),
                 ResetColor
             )?;
 
             // Draw text after cursor
-            if cursor_pos < input_text.len() {
+            if cursor_pos < shown_input.len() {
+                let after: String = input_text.chars().skip(cursor_pos + 1).collect();
                 queue!(
                     self.stdout,
-                    SetForegroundColor(Color::Cyan),
-                    Print(&input_text[cursor_pos + 1..]),
+                    SetForegroundColor(theme.input),
+                    Print(after),
                     ResetColor
                 )?;
             }
 
             // Draw cursors at the current position
-            let cursor_x = app.input_processor.cursor_position as u16;
+            let cursor_x = cursor_pos as u16;
             
             // Top cursor
             queue!(
                 self.stdout,
                 MoveTo(cursor_x, typing_area_y + 1),
-                Print("▼")
+                Print(cursor.markers.0)
             )?;
 
             // Bottom cursor
             queue!(
                 self.stdout,
                 MoveTo(cursor_x, typing_area_y + 4),
-                Print("▲")
+                Print(cursor.markers.1)
             )?;
 
             // Add the underline
             queue!(
                 self.stdout,
                 MoveTo(0, typing_area_y + 5),
-                Print("─".repeat(session.quote_text.len()))
+                Print("─".repeat(line.len()))
             )?;
+
+            if mode == RenderMode::Compact {
+                heatmap::draw_compact(&mut self.stdout, &session.metrics, (0, typing_area_y + COMPACT_HEATMAP_OFFSET), self.color_mode)?;
+            }
+        }
+
+        if !minimal {
+            self.draw_category_indicators(app, &theme)?;
         }
 
+        // Cover the screen with the results card until a key is pressed
+        if let Some(metrics) = &app.pending_results {
+            let card = results::render_results_card(metrics, RESULTS_CARD_WIDTH);
+            let x = self.terminal_size.0.saturating_sub(RESULTS_CARD_WIDTH) / 2;
+            for (i, line) in card.iter().enumerate() {
+                queue!(
+                    self.stdout,
+                    MoveTo(x, RESULTS_CARD_Y + i as u16),
+                    SetForegroundColor(theme.highlight),
+                    Print(line),
+                    ResetColor
+                )?;
+            }
+        }
+
+        // Flush all queued changes to the terminal
+        self.stdout.flush()?;
+        Ok(())
+    }
+
+    /// Active categories (or the current lesson) and the goal banner on the top row
+    fn draw_header(&mut self, app: &SpringKeys, theme: &Theme) -> io::Result<()> {
+        // Draw active categories, or the current lesson while learning
+        let active_categories = if app.game_state.current_game == GameType::Learn {
+            match app.lesson_stage() {
+                Some(stage) => format!(
+                    "Lesson {}/{}: {} (pass: {:.0} WPM, {:.0}% acc)",
+                    app.config.preferences.lesson_stage + 1,
+                    crate::curriculum::curriculum().len(),
+                    stage.name,
+                    stage.min_wpm,
+                    stage.min_accuracy,
+                ),
+                None => "All lessons passed!".to_string(),
+            }
+        } else {
+            format!(
+                "Active: Type:{:?} Prog:{:?} Lit:{:?}",
+                app.quote_db.get_active_category(),
+                app.quote_db.get_active_category(),
+                app.quote_db.get_active_category(),
+            )
+        };
+        queue!(
+            self.stdout,
+            MoveTo(0, 0),
+            SetForegroundColor(theme.status),
+            Print(&active_categories),
+            ResetColor
+        )?;
+
+        // Flash a banner right after a quote meets the user's goals, or a pending notice
+        if let Some(notice) = app.notice() {
+            queue!(
+                self.stdout,
+                MoveTo(active_categories.len() as u16 + 2, 0),
+                SetForegroundColor(theme.error),
+                Print(format!("{:<width$}", notice, width = HEADER_BANNER_WIDTH)),
+                ResetColor
+            )?;
+        } else if app.goal_banner_active() {
+            queue!(
+                self.stdout,
+                MoveTo(active_categories.len() as u16 + 2, 0),
+                SetForegroundColor(theme.highlight),
+                Print("*** Goals met! Well done! ***"),
+                ResetColor
+            )?;
+        } else {
+            queue!(
+                self.stdout,
+                MoveTo(active_categories.len() as u16 + 2, 0),
+                Print(" ".repeat(HEADER_BANNER_WIDTH))
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Both stat lines, the last quote's sparkline and the keyboard heatmap
+    fn draw_stats(&mut self, app: &SpringKeys, theme: &Theme) -> io::Result<()> {
+        let Some(session) = &app.typing_session else {
+            return Ok(());
+        };
+        let fmt = app.config.ui.display_format;
+        let metrics_text = format!(
+            "Score: {} | Combo: {} (x{}) | Quote WPM: {} | Session WPM: {} ({} quotes) | Acc: {}% | All-time WPM: {} | All-time Acc: {}% | Total Quotes: {}",
+            session.score,
+            session.combo,
+            crate::core::scoring::combo_multiplier(session.combo),
+            format_metric(app.current_quote_wpm(), fmt),
+            format_metric(app.session_aggregate.wpm(app.config.preferences.wpm_mode), fmt),
+            app.session_aggregate.quotes_completed,
+            format_metric(session.metrics.accuracy, fmt),
+            format_metric(app.accumulated_stats.avg_wpm, fmt),
+            format_metric(app.accumulated_stats.avg_accuracy, fmt),
+            app.accumulated_stats.total_quotes
+        );
+        queue!(
+            self.stdout,
+            MoveTo(0, 1),
+            SetForegroundColor(theme.metrics),
+            Print(&metrics_text),
+            ResetColor
+        )?;
+
+        // Draw the speed graph of the last completed quote
+        if let Some(sparkline) = &app.last_sparkline {
+            queue!(
+                self.stdout,
+                MoveTo(0, 2),
+                SetForegroundColor(theme.muted),
+                Print("Last quote WPM: "),
+                SetForegroundColor(theme.correct),
+                Print(sparkline),
+                ResetColor
+            )?;
+            if let Some(rhythm) = app.last_rhythm {
+                queue!(
+                    self.stdout,
+                    SetForegroundColor(theme.muted),
+                    Print(format!("  Rhythm: {:.2} (lower is steadier)", rhythm)),
+                    ResetColor
+                )?;
+            }
+        }
+
+        // Draw unified keyboard heatmap with color temperature and hit counts
+        heatmap::draw_unified_keyboard_heatmap(&mut self.stdout, &session.metrics, 3, self.terminal_size.0, self.color_mode)?;
+
+        // Draw typing area at a position below the visualization
+        let typing_area_y = TYPING_AREA_Y;
+        
+        // Get error count
+        let error_count = session.metrics.errors.len();
+
+        // Clear the entire typing area first (5 lines: errors, top cursor, quote, input, bottom cursor)
+        for y in typing_area_y..typing_area_y+6 {
+            queue!(
+                self.stdout,
+                MoveTo(0, y),
+                Print(" ".repeat(self.terminal_size.0 as usize))
+            )?;
+        }
+
+        // Draw speed range and error counts, plus the most common mix-ups
+        let mut speed_range = format!(
+            "Speed Range: {}ms (fastest) to {}ms (slowest) | Errors: {} (Session: {}, Total: {})",
+            session.metrics.key_histogram.min as u64,
+            session.metrics.key_histogram.max as u64,
+            error_count,
+            app.accumulated_stats.session_errors,
+            app.accumulated_stats.total_errors
+        );
+        let confusions = session.metrics.top_confusions(TOP_CONFUSIONS_SHOWN);
+        if !confusions.is_empty() {
+            let mix_ups: Vec<String> = confusions
+                .iter()
+                .map(|((expected, actual), count)| format!("{}→{} ×{}", expected, actual, count))
+                .collect();
+            speed_range.push_str(&format!(" | Mix-ups: {}", mix_ups.join(", ")));
+        }
+        queue!(
+            self.stdout,
+            MoveTo(0, typing_area_y),
+            SetForegroundColor(theme.quote_text),
+            Print(&speed_range),
+            ResetColor
+        )?;
+
+        // Draw the quote text, colored by what has been typed so far
+        self.draw_quote(app, theme, typing_area_y + 2)?;
+        Ok(())
+    }
+
+    /// Category symbols on the second-to-last row, the active one highlighted
+    fn draw_category_indicators(&mut self, app: &SpringKeys, theme: &Theme) -> io::Result<()> {
         // Draw category indicators
         queue!(
             self.stdout,
             MoveTo(0, self.terminal_size.1 - 2),
-            SetForegroundColor(Color::DarkGrey)
+            SetForegroundColor(theme.muted)
         )?;
 
         let active_category = app.quote_db.get_active_category();
@@ -331,9 +920,9 @@ impl TerminalUI {
             if *category == active_category {
                 queue!(
                     self.stdout,
-                    SetForegroundColor(Color::White),
+                    SetForegroundColor(theme.quote_text),
                     Print(symbol),
-                    SetForegroundColor(Color::DarkGrey),
+                    SetForegroundColor(theme.muted),
                     Print(" ")
                 )?;
             } else {
@@ -344,9 +933,208 @@ impl TerminalUI {
                 )?;
             }
         }
-
-        // Flush all queued changes to the terminal
-        self.stdout.flush()?;
         Ok(())
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_terminal_fits_layout() {
+        assert!(terminal_fits_layout(MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT));
+        assert!(terminal_fits_layout(200, 60));
+        assert!(!terminal_fits_layout(80, 24));
+        assert!(!terminal_fits_layout(MIN_TERMINAL_WIDTH - 1, MIN_TERMINAL_HEIGHT));
+        assert!(!terminal_fits_layout(MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT - 1));
+    }
+
+    #[test]
+    fn test_short_terminal_falls_back_to_compact_heatmap() {
+        assert_eq!(effective_render_mode(RenderMode::Full, MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT), RenderMode::Full);
+        assert_eq!(effective_render_mode(RenderMode::Full, 80, 24), RenderMode::Compact);
+        assert_eq!(effective_render_mode(RenderMode::Minimal, 80, 24), RenderMode::Minimal);
+
+        let compact = render(RenderMode::Compact);
+        assert!(!compact.contains("hits"));
+        assert!(compact.contains('⠀'), "Untyped keys should get an empty Braille cell");
+    }
+
+    #[test]
+    fn test_blind_mode_hides_typed_input() {
+        let typed = "The quick";
+        let rendered = render_input(typed, true);
+        assert_eq!(rendered.chars().count(), typed.chars().count());
+        assert!(typed.chars().all(|c| !rendered.contains(c)));
+        assert_eq!(render_input(typed, false), typed);
+    }
+
+    #[test]
+    fn test_cursor_render_for_each_style() {
+        let theme = Theme::default();
+
+        let block = render_cursor(CursorStyle::Block, false, &theme);
+        assert_eq!(block, CursorRender { fg: theme.cursor_fg, bg: Some(theme.cursor_bg), attribute: None, markers: ('▼', '▲') });
+
+        let underline = render_cursor(CursorStyle::Underline, false, &theme);
+        assert_eq!(underline.bg, None);
+        assert_eq!(underline.attribute, Some(Attribute::Underlined));
+        assert_eq!(underline.markers, (' ', ' '));
+
+        let bar = render_cursor(CursorStyle::Bar, false, &theme);
+        assert_eq!(bar.bg, None);
+        assert_eq!(bar.markers, ('│', '│'));
+
+        let bracket = render_cursor(CursorStyle::Bracket, false, &theme);
+        assert_eq!((bracket.fg, bracket.bg), (theme.cursor_bg, None));
+        assert_eq!(bracket.markers, ('⎴', '⎵'));
+
+        // A rejection turns the accent red whatever the style
+        assert_eq!(render_cursor(CursorStyle::Block, true, &theme).bg, Some(theme.error));
+        assert_eq!(render_cursor(CursorStyle::Bar, true, &theme).fg, theme.error);
+    }
+
+    #[test]
+    fn test_quote_chars_are_colored_by_typed_state() {
+        let theme = Theme::default();
+        let states = char_diff("cat", "cx");
+        assert_eq!(states, vec![CharState::Correct, CharState::Wrong, CharState::Pending]);
+
+        let colors: Vec<_> = states.iter().map(|&state| quote_char_colors(state, false, &theme)).collect();
+        assert_eq!(colors, vec![(theme.correct, None), (theme.error, None), (theme.muted, None)]);
+        // The cursor highlight wins over the typed state
+        assert_eq!(quote_char_colors(CharState::Pending, true, &theme), (theme.cursor_fg, Some(theme.cursor_bg)));
+    }
+
+    #[test]
+    fn test_format_metric_follows_precision() {
+        let whole = DisplayFormat { precision: 0, thousands_separator: false };
+        let fine = DisplayFormat { precision: 2, thousands_separator: false };
+        assert_eq!(format_metric(87.456, whole), "87");
+        assert_eq!(format_metric(87.456, fine), "87.46");
+        assert_eq!(format_metric(1234.5, DisplayFormat { precision: 1, thousands_separator: true }), "1,234.5");
+        assert_eq!(format_metric(-1234567.0, DisplayFormat { precision: 0, thousands_separator: true }), "-1,234,567");
+    }
+
+    /// Writer whose output stays readable after the UI takes ownership of it
+    #[derive(Clone, Default)]
+    struct SharedBuffer(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn render(mode: RenderMode) -> String {
+        let mut app = SpringKeys::new_silent();
+        app.config.ui.render_mode = mode;
+        app.start_typing_session(Some("focus".to_string()));
+        app.process_token("f");
+
+        let buffer = SharedBuffer::default();
+        let mut ui = TerminalUI::with_output(Box::new(buffer.clone()), (MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT));
+        heatmap::invalidate_heatmap_cache();
+        ui.draw_ui(&app).unwrap();
+        let output = String::from_utf8_lossy(&buffer.0.borrow()).into_owned();
+        output
+    }
+
+    #[test]
+    fn test_minimal_mode_hides_heatmap() {
+        let full = render(RenderMode::Full);
+        assert!(full.contains("hits"));
+
+        let minimal = render(RenderMode::Minimal);
+        assert!(!minimal.contains("hits"));
+        assert!(minimal.contains("WPM: ") && minimal.contains("| Acc: "));
+        assert!(!minimal.contains("Score:"));
+    }
+
+    #[test]
+    fn test_alternate_screen_can_be_skipped() {
+        // Only the escape sequences are checked; raw mode isn't touched, so this runs headless
+        let enter_alternate = "\x1b[?1049h";
+        let leave_alternate = "\x1b[?1049l";
+        let screen_output = |alternate: bool| {
+            let buffer = SharedBuffer::default();
+            let mut ui = TerminalUI::with_output(Box::new(buffer.clone()), (MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT));
+            ui.set_alternate_screen(alternate);
+            ui.enter_screen().unwrap();
+            ui.leave_screen().unwrap();
+            let output = String::from_utf8_lossy(&buffer.0.borrow()).into_owned();
+            output
+        };
+
+        let fullscreen = screen_output(true);
+        assert!(fullscreen.contains(enter_alternate) && fullscreen.contains(leave_alternate));
+
+        let inline = screen_output(false);
+        assert!(!inline.contains(enter_alternate) && !inline.contains(leave_alternate));
+        // Nothing is cleared, so the shell history scrolls up instead of disappearing
+        assert!(!inline.contains("\x1b[2J"));
+        assert!(inline.matches("\r\n").count() > usize::from(MIN_TERMINAL_HEIGHT));
+    }
+
+    #[test]
+    fn test_click_maps_to_char_index_in_wrapped_input() {
+        // 25 characters wrapped at 10 columns: rows 5, 6 and 7 (holding the last 5 and the cursor slot)
+        assert_eq!(click_to_char_index(3, 5, 5, 10, 25), Some(3));
+        assert_eq!(click_to_char_index(0, 6, 5, 10, 25), Some(10));
+        assert_eq!(click_to_char_index(4, 7, 5, 10, 25), Some(24));
+        // Past the end of the text the cursor goes after the last character
+        assert_eq!(click_to_char_index(9, 7, 5, 10, 25), Some(25));
+        // Outside the input rows
+        assert_eq!(click_to_char_index(3, 4, 5, 10, 25), None);
+        assert_eq!(click_to_char_index(3, 8, 5, 10, 25), None);
+        assert_eq!(click_to_char_index(3, 5, 5, 0, 25), None);
+    }
+
+    #[test]
+    fn test_long_quote_wraps_at_word_boundaries() {
+        let lines = wrap_lines("the quick brown fox jumps", 10);
+        assert_eq!(lines, vec![0..10, 10..20, 20..25]);
+        // A word longer than a line is split
+        assert_eq!(wrap_lines("abcdefghijkl", 5), vec![0..5, 5..10, 10..12]);
+
+        assert_eq!(line_at(&lines, 10), 10..20);
+        // The cursor after the last character stays on the last line
+        assert_eq!(line_at(&lines, 25), 20..25);
+    }
+
+    #[test]
+    fn test_typing_line_keeps_quote_and_input_in_step() {
+        let mut app = SpringKeys::new_silent();
+        app.start_typing_session(Some("the quick brown fox jumps over".to_string()));
+        let ui = TerminalUI::with_output(Box::new(io::sink()), (11, MIN_TERMINAL_HEIGHT));
+        assert_eq!(ui.typing_line(&app), (0..10, 0..0));
+
+        // Finishing the first line moves both rows on to the second
+        app.feed_str("the quick b");
+        assert_eq!(ui.typing_line(&app), (10..20, 10..11));
+    }
+
+    #[test]
+    fn test_pacer_is_drawn_on_the_line_shown() {
+        let mut app = SpringKeys::new_silent();
+        app.config.preferences.goal_wpm = Some(60.0);
+        app.start_typing_session(Some("the quick brown fox jumps over".to_string()));
+        let ui = TerminalUI::with_output(Box::new(io::sink()), (11, MIN_TERMINAL_HEIGHT));
+
+        // 60 WPM is 5 characters a second, so 2.4 seconds in the pacer is on the 'o' of "brown"
+        let session = app.typing_session.as_mut().unwrap();
+        session.start_time = Instant::now() - Duration::from_millis(2400);
+        assert_eq!(ui.pacer_cell(&app), None, "The pacer is on the second line, the first is shown");
+        app.feed_str("the quick ");
+        assert_eq!(ui.pacer_cell(&app), Some((2, 'o')));
+
+        // Sentence mode only shows part of the quote
+        app.game_state.current_game = GameType::SentenceBySentence;
+        assert_eq!(ui.pacer_cell(&app), None);
+    }
+}
@@ -0,0 +1,88 @@
+use std::time::{Duration, Instant};
+use crate::core::metrics::TypingMetrics;
+
+/// Narrowest card that still fits its labels and values
+pub const MIN_CARD_WIDTH: u16 = 36;
+/// Number of slowest keys listed on the card
+const WORST_KEYS_SHOWN: usize = 3;
+/// Number of mistakes reviewed on the card before the rest are just counted
+const MISTAKES_SHOWN: usize = 4;
+
+/// Summary box shown after a quote is completed: WPM, peak WPM, accuracy, time, rhythm
+/// consistency, slowest keys, a WPM sparkline and a review of the quote's mistakes,
+/// framed like the heatmap keys
+pub fn render_results_card(metrics: &TypingMetrics, width: u16) -> Vec<String> {
+    let inner = width.max(MIN_CARD_WIDTH) as usize - 2;
+    let elapsed = metrics.current_time.saturating_duration_since(metrics.start_time).as_secs();
+
+    let mut slowest: Vec<(char, f64)> = metrics.get_heat_map().into_iter().collect();
+    slowest.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal).then(a.0.cmp(&b.0)));
+    let worst_keys: Vec<String> = slowest
+        .iter()
+        .take(WORST_KEYS_SHOWN)
+        .map(|(key, avg_ms)| format!("{} {:.0}ms", if *key == ' ' { '␣' } else { *key }, avg_ms))
+        .collect();
+
+    let rows = [
+        format!("WPM          {:.1}", metrics.wpm),
+        format!("Accuracy     {:.1}%", metrics.accuracy),
+        format!("Peak         {:.1} WPM @ {:.0}s", metrics.peak_wpm, metrics.peak_wpm_at),
+        format!("Time         {}:{:02}", elapsed / 60, elapsed % 60),
+        format!("Consistency  {:.2} (lower is steadier)", metrics.rhythm_consistency()),
+        format!("Slowest keys {}", if worst_keys.is_empty() { "-".to_string() } else { worst_keys.join(", ") }),
+        metrics.render_sparkline(inner - 2),
+    ];
+
+    let mistakes = metrics.mistake_report();
+    let mut review: Vec<String> = mistakes
+        .iter()
+        .take(MISTAKES_SHOWN)
+        .map(|m| format!("@{:<4} typed '{}' for '{}' in \"{}\"", m.position, m.actual, m.expected, m.expected_word))
+        .collect();
+    if mistakes.is_empty() {
+        review.push("No mistakes".to_string());
+    } else if mistakes.len() > MISTAKES_SHOWN {
+        review.push(format!("...and {} more", mistakes.len() - MISTAKES_SHOWN));
+    }
+
+    let mut card = Vec::with_capacity(rows.len() + review.len() + 6);
+    card.push(format!("╭{}╮", "─".repeat(inner)));
+    card.push(format!("│{:^inner$}│", "Results"));
+    card.push(format!("├{}┤", "─".repeat(inner)));
+    for row in rows {
+        let row: String = row.chars().take(inner - 2).collect();
+        card.push(format!("│ {:<width$} │", row, width = inner - 2));
+    }
+    card.push(format!("├{:─^inner$}┤", " Mistakes "));
+    for row in review {
+        let row: String = row.chars().take(inner - 2).collect();
+        card.push(format!("│ {:<width$} │", row, width = inner - 2));
+    }
+    card.push(format!("│{:^inner$}│", "Press any key for the next quote"));
+    card.push(format!("╰{}╯", "─".repeat(inner)));
+    card
+}
+
+/// Times how long the results card has been up, so it can move on to the next quote
+/// after `completion_pause_ms` without a key press
+#[derive(Debug, Default)]
+pub struct ResultsPause {
+    shown_at: Option<Instant>,
+}
+
+impl ResultsPause {
+    /// Call once per frame with whether the card is showing. Returns true once it has been
+    /// showing for `pause`; a zero pause never advances, leaving the card up for a key.
+    pub fn tick(&mut self, showing: bool, pause: Duration, now: Instant) -> bool {
+        if !showing {
+            self.shown_at = None;
+            return false;
+        }
+        let shown_at = *self.shown_at.get_or_insert(now);
+        if pause.is_zero() || now.duration_since(shown_at) < pause {
+            return false;
+        }
+        self.shown_at = None;
+        true
+    }
+}
@@ -0,0 +1,112 @@
+use crossterm::style::Color;
+use super::caps::ColorMode;
+
+/// Colors for each logical role in the terminal UI
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// Status lines such as the active category header and resize warning
+    pub status: Color,
+    /// Live WPM/accuracy line
+    pub metrics: Color,
+    /// Labels and inactive indicators
+    pub muted: Color,
+    /// Attention-grabbing notices such as the goals banner
+    pub highlight: Color,
+    /// Primary text: the quote being typed, info lines and the active category marker
+    pub quote_text: Color,
+    /// The user's typed input
+    pub input: Color,
+    /// Character under the cursor
+    pub cursor_fg: Color,
+    /// Cursor block
+    pub cursor_bg: Color,
+    /// Correct input and positive feedback
+    pub correct: Color,
+    /// Errors and rejected keystrokes
+    pub error: Color,
+}
+
+impl Theme {
+    /// Names accepted by `from_name`, for the `ui.theme` config setting
+    pub const NAMES: [&'static str; 3] = ["classic", "solarized", "high-contrast"];
+
+    /// Look up a built-in theme by name (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Theme> {
+        match name.to_lowercase().as_str() {
+            "classic" => Some(Self::classic()),
+            "solarized" => Some(Self::solarized()),
+            "high-contrast" | "high_contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// This theme with every color converted to one `mode` can display
+    pub fn adapted(self, mode: ColorMode) -> Theme {
+        Theme {
+            status: mode.adapt(self.status),
+            metrics: mode.adapt(self.metrics),
+            muted: mode.adapt(self.muted),
+            highlight: mode.adapt(self.highlight),
+            quote_text: mode.adapt(self.quote_text),
+            input: mode.adapt(self.input),
+            cursor_fg: mode.adapt(self.cursor_fg),
+            cursor_bg: mode.adapt(self.cursor_bg),
+            correct: mode.adapt(self.correct),
+            error: mode.adapt(self.error),
+        }
+    }
+
+    /// The original SpringKeys colors
+    pub fn classic() -> Theme {
+        Theme {
+            status: Color::Yellow,
+            metrics: Color::Green,
+            muted: Color::DarkGrey,
+            highlight: Color::Magenta,
+            quote_text: Color::White,
+            input: Color::Cyan,
+            cursor_fg: Color::Black,
+            cursor_bg: Color::White,
+            correct: Color::Green,
+            error: Color::Red,
+        }
+    }
+
+    /// Solarized dark palette
+    pub fn solarized() -> Theme {
+        Theme {
+            status: Color::Rgb { r: 181, g: 137, b: 0 },
+            metrics: Color::Rgb { r: 133, g: 153, b: 0 },
+            muted: Color::Rgb { r: 88, g: 110, b: 117 },
+            highlight: Color::Rgb { r: 211, g: 54, b: 130 },
+            quote_text: Color::Rgb { r: 147, g: 161, b: 161 },
+            input: Color::Rgb { r: 42, g: 161, b: 152 },
+            cursor_fg: Color::Rgb { r: 0, g: 43, b: 54 },
+            cursor_bg: Color::Rgb { r: 238, g: 232, b: 213 },
+            correct: Color::Rgb { r: 133, g: 153, b: 0 },
+            error: Color::Rgb { r: 220, g: 50, b: 47 },
+        }
+    }
+
+    /// Maximum contrast for low-vision users and poor displays
+    pub fn high_contrast() -> Theme {
+        Theme {
+            status: Color::White,
+            metrics: Color::White,
+            muted: Color::Grey,
+            highlight: Color::Yellow,
+            quote_text: Color::White,
+            input: Color::Yellow,
+            cursor_fg: Color::Black,
+            cursor_bg: Color::Yellow,
+            correct: Color::Green,
+            error: Color::Red,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
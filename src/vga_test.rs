@@ -7,8 +7,10 @@ use crossterm::{
     style::{Color, SetForegroundColor, SetBackgroundColor},
     event::{poll, read, Event},
 };
+use crate::ui::frame_rate;
 
-const FRAME_TIME: u64 = 10; // Animation frame time in milliseconds
+/// Frame rate of the test screen when `--fps` isn't given
+pub const DEFAULT_FPS: u32 = 30;
 
 // Reduced symbol set that works well at any size
 const SYMBOLS: &[char] = &[
@@ -63,11 +65,11 @@ impl Pattern {
     }
 }
 
-pub fn run_test_screen() -> io::Result<()> {
-    run_test_screen_with_duration(None)
+pub fn run_test_screen(fps: u32) -> io::Result<()> {
+    run_test_screen_with_duration(None, fps)
 }
 
-fn run_test_screen_with_duration(max_duration: Option<Duration>) -> io::Result<()> {
+fn run_test_screen_with_duration(max_duration: Option<Duration>, fps: u32) -> io::Result<()> {
     enable_raw_mode()?;
     let mut stdout = stdout();
     
@@ -94,7 +96,8 @@ fn run_test_screen_with_duration(max_duration: Option<Duration>) -> io::Result<(
             }
         }
 
-        if poll(Duration::from_millis(FRAME_TIME))? {
+        let frame_start = Instant::now();
+        if poll(Duration::ZERO)? {
             if let Event::Key(_) = read()? {
                 break;
             }
@@ -107,6 +110,8 @@ fn run_test_screen_with_duration(max_duration: Option<Duration>) -> io::Result<(
         
         // Update color phase
         color_phase = (color_phase + color_speed) % (2.0 * std::f64::consts::PI);
+
+        std::thread::sleep(frame_rate::frame_delay(fps, frame_start.elapsed()));
     }
     
     // Cleanup
@@ -168,7 +173,7 @@ mod tests {
     #[test]
     fn test_vga_animation_can_run_and_exit() {
         // Run the test screen with a short duration
-        let result = run_test_screen_with_duration(Some(Duration::from_millis(500)));
+        let result = run_test_screen_with_duration(Some(Duration::from_millis(500)), DEFAULT_FPS);
         assert!(result.is_ok(), "VGA test screen should run and exit cleanly");
     }
 
@@ -0,0 +1,24 @@
+use spring_keys::{AccumulatedStats, SpringKeys};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn test_autosave_writes_at_most_once_per_interval() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let stats_path = dir.path().join("stats").join("accumulated.json");
+    let mut app = SpringKeys::new();
+    app.load_stats_from(&stats_path);
+    assert_eq!(app.accumulated_stats.total_quotes, 0);
+    let interval = Duration::from_millis(50);
+
+    // Nothing is due right after startup
+    assert!(!app.autosave_if_due(interval));
+
+    thread::sleep(interval);
+    app.accumulated_stats.total_quotes = 3;
+    assert!(app.autosave_if_due(interval));
+    assert!(!app.autosave_if_due(interval));
+
+    let saved = AccumulatedStats::load_from_file(&stats_path).unwrap();
+    assert_eq!(saved.total_quotes, 3);
+}
@@ -0,0 +1,25 @@
+use crossterm::style::Color;
+use spring_keys::ui::caps::{detect_from, ColorMode};
+
+#[test]
+fn test_colorterm_truecolor_is_detected() {
+    assert_eq!(detect_from(Some("truecolor"), Some("xterm-256color")), ColorMode::Truecolor);
+    assert_eq!(detect_from(Some("24bit"), None), ColorMode::Truecolor);
+}
+
+#[test]
+fn test_missing_colorterm_falls_back_to_16_colors() {
+    assert_eq!(detect_from(None, Some("xterm-256color")), ColorMode::Ansi16);
+    assert_eq!(detect_from(None, None), ColorMode::Ansi16);
+    // Terminals that advertise direct color through $TERM still get truecolor
+    assert_eq!(detect_from(None, Some("xterm-direct")), ColorMode::Truecolor);
+}
+
+#[test]
+fn test_ansi16_maps_rgb_to_nearest_basic_color() {
+    let mode = ColorMode::Ansi16;
+    assert_eq!(mode.adapt(Color::Rgb { r: 250, g: 10, b: 5 }), Color::Red);
+    assert_eq!(mode.adapt(Color::Rgb { r: 128, g: 0, b: 128 }), Color::DarkMagenta);
+    assert_eq!(mode.adapt(Color::Cyan), Color::Cyan);
+    assert_eq!(ColorMode::Truecolor.adapt(Color::Rgb { r: 1, g: 2, b: 3 }), Color::Rgb { r: 1, g: 2, b: 3 });
+}
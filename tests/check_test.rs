@@ -0,0 +1,49 @@
+use spring_keys::check::run_checks;
+use std::fs;
+use std::process::Command;
+
+const GOOD_QUOTES: &str = r#"[{"text": "The quick brown fox.", "source": "Typing", "difficulty": "Easy", "category": "General", "origin": "English"}]"#;
+
+fn quote_dir(files: &[(&str, &str)]) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let quotes = dir.path().join("quotes/categories");
+    fs::create_dir_all(&quotes).unwrap();
+    for (name, content) in files {
+        fs::write(quotes.join(name), content).unwrap();
+    }
+    dir
+}
+
+fn run_check(dir: &tempfile::TempDir) -> Option<i32> {
+    Command::new(env!("CARGO_BIN_EXE_spring-keys"))
+        .args(["check", "--quiet"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap()
+        .status
+        .code()
+}
+
+#[test]
+fn test_check_command_exit_code() {
+    let good = quote_dir(&[("good.json", GOOD_QUOTES)]);
+    assert_eq!(run_check(&good), Some(0));
+
+    let bad = quote_dir(&[("good.json", GOOD_QUOTES), ("bad.json", "[{\"text\": ")]);
+    assert_eq!(run_check(&bad), Some(1));
+}
+
+#[test]
+fn test_run_checks_reports_each_problem() {
+    let dir = quote_dir(&[("good.json", GOOD_QUOTES), ("bad.json", "not json"), ("empty.json", "[]")]);
+    fs::write(dir.path().join("springkeys.toml"), "[ui]\nrender_mode = 3\n").unwrap();
+
+    let report = run_checks(&dir.path().join("springkeys.toml"), &dir.path().join("quotes/categories"), &dir.path().join("stats"));
+    assert_eq!(report.config, "invalid");
+    assert_eq!(report.quote_files, 3);
+    assert_eq!(report.quotes, 1);
+    assert!(report.stats_dir_writable);
+    assert_eq!(report.problems.len(), 3, "{:?}", report.problems);
+    assert!(report.problems.iter().any(|p| p.contains("bad.json")));
+    assert!(report.problems.iter().any(|p| p.contains("empty.json")));
+}
@@ -0,0 +1,396 @@
+use spring_keys::{AccumulatedStats, AppEvent, GameType, GoalResult, SpringKeys, TypingSession, WpmMode};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::{Duration, Instant};
+
+fn type_chars(app: &mut SpringKeys, text: &str) {
+    for c in text.chars() {
+        app.process_input(KeyCode::Char(c), KeyModifiers::NONE);
+    }
+}
+
+#[test]
+fn test_exact_completion_keeps_session_open_on_trailing_mismatch() {
+    let mut app = SpringKeys::new();
+    app.config.preferences.require_exact_completion = true;
+    app.start_typing_session(Some("abc".to_string()));
+    let quotes_before = app.accumulated_stats.total_quotes;
+
+    // Full length typed, but the last character is wrong
+    type_chars(&mut app, "abx");
+    assert_eq!(app.accumulated_stats.total_quotes, quotes_before);
+    assert_eq!(app.typing_session.as_ref().unwrap().quote_text, "abc");
+    assert_eq!(app.input_processor.current_text, "abx");
+
+    // Correcting the mismatch completes the quote
+    app.process_input(KeyCode::Backspace, KeyModifiers::NONE);
+    type_chars(&mut app, "c");
+    assert_eq!(app.accumulated_stats.total_quotes, quotes_before + 1);
+    assert!(app.input_processor.current_text.is_empty());
+}
+
+#[test]
+fn test_exact_completion_completes_on_exact_match() {
+    let mut app = SpringKeys::new();
+    app.config.preferences.require_exact_completion = true;
+    app.start_typing_session(Some("hi there".to_string()));
+    let quotes_before = app.accumulated_stats.total_quotes;
+
+    type_chars(&mut app, "hi there");
+    assert_eq!(app.accumulated_stats.total_quotes, quotes_before + 1);
+}
+
+#[test]
+fn test_session_exceeding_goals_is_met() {
+    let mut app = SpringKeys::new();
+    app.config.preferences.goal_wpm = Some(1.0);
+    app.config.preferences.goal_accuracy = Some(90.0);
+    app.start_typing_session(Some("goal".to_string()));
+    assert!(app.last_goal_result().is_none());
+
+    // Typed instantly and without mistakes, so both goals are comfortably beaten
+    type_chars(&mut app, "goal");
+    match app.last_goal_result() {
+        Some(GoalResult::Met { wpm_delta, accuracy_delta }) => {
+            assert!(wpm_delta.unwrap() > 0.0);
+            assert!(accuracy_delta.unwrap() >= 0.0);
+        }
+        other => panic!("expected goals to be met, got {:?}", other),
+    }
+    assert!(app.goal_banner_active());
+}
+
+#[test]
+fn test_goal_is_graded_on_the_completed_quote_alone() {
+    let mut app = SpringKeys::new();
+    app.config.preferences.goal_accuracy = Some(100.0);
+    app.start_typing_session(Some("ab".to_string()));
+
+    // A corrected mistake misses the goal for the first quote...
+    type_chars(&mut app, "ax");
+    app.process_input(KeyCode::Backspace, KeyModifiers::NONE);
+    type_chars(&mut app, "b");
+    assert!(matches!(app.last_goal_result(), Some(GoalResult::Missed { .. })));
+
+    // ...but doesn't count against a clean second one
+    app.start_typing_session(Some("cd".to_string()));
+    type_chars(&mut app, "cd");
+    assert_eq!(app.last_goal_result(), Some(GoalResult::Met { wpm_delta: None, accuracy_delta: Some(0.0) }));
+}
+
+#[test]
+fn test_goal_result_missed_and_unset() {
+    assert_eq!(GoalResult::evaluate(None, None, 50.0, 99.0), None);
+    let result = GoalResult::evaluate(Some(60.0), Some(95.0), 50.0, 99.0).unwrap();
+    assert_eq!(result, GoalResult::Missed { wpm_delta: Some(-10.0), accuracy_delta: Some(4.0) });
+    assert!(!result.is_met());
+}
+
+#[test]
+fn test_process_token_reports_completion() {
+    let mut app = SpringKeys::new();
+    app.start_typing_session(Some("Hi yo".to_string()));
+
+    let tokens = ["H", "i", "<space>", "y"];
+    assert!(tokens.iter().all(|token| !app.process_token(token)));
+    assert_eq!(app.input_processor.current_text, "Hi y");
+    assert!(!app.process_token("<bogus>"));
+    assert!(app.process_token("o"));
+}
+
+#[test]
+fn test_stop_on_error_rejects_wrong_characters() {
+    let mut app = SpringKeys::new();
+    app.config.preferences.stop_on_error = true;
+    app.start_typing_session(Some("cat".to_string()));
+    let errors_before = app.accumulated_stats.total_errors;
+
+    type_chars(&mut app, "cx");
+    assert_eq!(app.input_processor.current_text, "c");
+    assert!(app.input_processor.rejected_at.is_some());
+    assert_eq!(app.accumulated_stats.total_errors, errors_before + 1);
+
+    // The wrong key still counts against accuracy
+    let metrics = &app.typing_session.as_ref().unwrap().metrics;
+    assert_eq!(metrics.keystrokes, 2);
+    assert_eq!(metrics.correct_keystrokes, 1);
+
+    // The correct key is accepted as usual
+    type_chars(&mut app, "a");
+    assert_eq!(app.input_processor.current_text, "ca");
+}
+
+#[test]
+fn test_session_aggregate_spans_completed_quotes() {
+    let mut app = SpringKeys::new();
+    app.start_typing_session(Some("ab".to_string()));
+    type_chars(&mut app, "ab");
+    assert_eq!(app.session_aggregate.quotes_completed, 1);
+    assert_eq!(app.session_aggregate.keystrokes, 2);
+
+    // Replace the random follow-up quote with a known one in the same session
+    app.start_typing_session(Some("cde".to_string()));
+    type_chars(&mut app, "cde");
+
+    let aggregate = &app.session_aggregate;
+    assert_eq!(aggregate.quotes_completed, 2);
+    assert_eq!(aggregate.keystrokes, 5);
+    assert_eq!(aggregate.correct_keystrokes, aggregate.keystrokes);
+    assert_eq!(aggregate.accuracy(), 100.0);
+    assert!(aggregate.wpm(WpmMode::default()) > 0.0);
+}
+
+#[test]
+fn test_session_wpm_counts_words_the_configured_way() {
+    let mut app = SpringKeys::new();
+    app.config.preferences.wpm_mode = WpmMode::ActualWords;
+    app.start_typing_session(Some("a b c".to_string()));
+
+    // Two spaces are two words in the second the five keystrokes take, not one word of five characters
+    let summary = app.type_text("a b c");
+    assert!(summary.completed);
+    assert!((summary.wpm - 120.0).abs() < 1e-9, "WPM was {}", summary.wpm);
+
+    let aggregate = &app.session_aggregate;
+    assert_eq!(aggregate.correct_spaces, 2);
+    let minutes = aggregate.typing_time.as_secs_f64() / 60.0;
+    assert!((aggregate.wpm(WpmMode::ActualWords) - 2.0 / minutes).abs() < 1e-6);
+    assert!((aggregate.wpm(WpmMode::default()) - 1.0 / minutes).abs() < 1e-6);
+}
+
+#[test]
+fn test_accumulated_stats_count_each_quote_of_a_run_once() {
+    let mut app = SpringKeys::new();
+    app.accumulated_stats = AccumulatedStats::new();
+    app.start_typing_session(Some("ab".to_string()));
+    type_chars(&mut app, "ab");
+
+    // The second quote of the same session has one wrong key
+    app.start_typing_session(Some("cd".to_string()));
+    type_chars(&mut app, "cx");
+    app.process_input(KeyCode::Backspace, KeyModifiers::NONE);
+    type_chars(&mut app, "d");
+
+    // Totals match the quotes typed, not the session's running counts added up per quote
+    let stats = &app.accumulated_stats;
+    assert_eq!(stats.total_quotes, 2);
+    assert_eq!(stats.total_keystrokes, app.session_aggregate.keystrokes);
+    assert_eq!(stats.total_correct_keystrokes, app.session_aggregate.correct_keystrokes);
+    // Only the second quote's mistakes, once each
+    let session_errors = app.typing_session.as_ref().unwrap().metrics.errors.len();
+    assert!(session_errors > 0);
+    assert_eq!(app.session_aggregate.errors, session_errors);
+
+    // The second quote's accuracy is its own, not the session's so far
+    let second_keystrokes = (stats.total_keystrokes - 2) as f64;
+    let second_accuracy = (stats.total_correct_keystrokes - 2) as f64 / second_keystrokes * 100.0;
+    assert!(second_accuracy < 100.0);
+    assert!((stats.avg_accuracy - (0.95 * 100.0 + 0.05 * second_accuracy)).abs() < 1e-9);
+}
+
+#[test]
+fn test_avg_wpm_weighs_each_quote_of_a_run_by_its_own_keystrokes() {
+    let mut app = SpringKeys::new();
+    app.accumulated_stats = AccumulatedStats::new();
+    app.start_typing_session(Some("ab".to_string()));
+    type_chars(&mut app, "ab");
+    app.start_typing_session(Some("cdefgh".to_string()));
+    type_chars(&mut app, "cdefgh");
+
+    // Weighted by 2 and 6 keystrokes, not 2 and the session's running total of 8
+    let stats = &app.accumulated_stats;
+    assert_eq!(stats.weighted_wpm_denominator, 8.0);
+    assert!(stats.avg_wpm > 0.0);
+}
+
+#[test]
+fn test_feed_str_types_and_completes_quote() {
+    let mut app = SpringKeys::new();
+    app.start_typing_session(Some("Hi yo".to_string()));
+
+    // Uppercase is sent with SHIFT, so it matches the quote
+    assert_eq!(app.feed_str("Hi y"), 0);
+    assert_eq!(app.input_processor.current_text, "Hi y");
+    assert_eq!(app.feed_str("o"), 1);
+}
+
+#[test]
+fn test_type_text_exact_quote_is_perfect_and_complete() {
+    let mut app = SpringKeys::new();
+    app.start_typing_session(Some("Type me".to_string()));
+
+    let summary = app.type_text("Type me");
+    assert!(summary.completed);
+    assert_eq!(summary.accuracy, 100.0);
+    assert_eq!(summary.errors, 0);
+    assert_eq!(summary.keystrokes, 7);
+    // Every key is correct and typed at the simulated interval, so this is exactly its WPM
+    assert!((summary.wpm - 60.0).abs() < 1e-9, "WPM was {}", summary.wpm);
+}
+
+#[test]
+fn test_type_text_wpm_counts_only_correct_keys() {
+    let mut app = SpringKeys::new();
+    app.start_typing_session(Some("abcde".to_string()));
+
+    // Two correct keys are 2/5 of a word in the 0.6 seconds the three keystrokes take
+    let summary = app.type_text("abx");
+    assert!(!summary.completed);
+    assert_eq!(summary.keystrokes, 3);
+    assert_eq!(summary.errors, 1);
+    assert!((summary.wpm - 40.0).abs() < 1e-9, "WPM was {}", summary.wpm);
+}
+
+#[test]
+fn test_type_tokens_replays_input_tokens() {
+    let mut app = SpringKeys::new();
+    app.start_typing_session(Some("a b".to_string()));
+
+    let summary = app.type_tokens("a <space> b");
+    assert!(summary.completed);
+    assert_eq!(summary.keystrokes, 3);
+    assert_eq!(summary.accuracy, 100.0);
+    assert!((summary.wpm - 60.0).abs() < 1e-9, "WPM was {}", summary.wpm);
+}
+
+#[test]
+fn test_pacer_position_follows_goal_wpm() {
+    let mut session = TypingSession::new("x".repeat(100));
+    assert_eq!(session.pacer_position(50.0), 0);
+
+    // 50 WPM is 250 characters a minute, so 6 seconds in the pacer is 25 characters along
+    session.start_time = Instant::now() - Duration::from_secs(6);
+    assert_eq!(session.pacer_position(50.0), 25);
+
+    // It stops at the end of the quote
+    session.start_time = Instant::now() - Duration::from_secs(600);
+    assert_eq!(session.pacer_position(50.0), 100);
+}
+
+#[test]
+fn test_repeat_mode_reloads_quote_until_accuracy_goal_is_met() {
+    let mut app = SpringKeys::new();
+    app.repeat_until_passed = true;
+    app.config.preferences.goal_accuracy = Some(100.0);
+    app.start_typing_session(Some("ab".to_string()));
+
+    // A sloppy attempt completes the quote but brings the same text back
+    type_chars(&mut app, "ax");
+    app.process_input(KeyCode::Backspace, KeyModifiers::NONE);
+    type_chars(&mut app, "b");
+    assert_eq!(app.session_aggregate.quotes_completed, 1);
+    assert_eq!(app.typing_session.as_ref().unwrap().quote_text, "ab");
+
+    // A clean attempt passes and moves on
+    type_chars(&mut app, "ab");
+    assert_eq!(app.session_aggregate.quotes_completed, 2);
+    assert_ne!(app.typing_session.as_ref().unwrap().quote_text, "ab");
+}
+
+#[test]
+fn test_tab_indented_quote_is_typed_with_tab_key() {
+    let mut app = SpringKeys::new();
+    app.start_typing_session(Some("if x:\treturn".to_string()));
+
+    // The tab expands to the next stop, so cursor math stays per column
+    assert_eq!(app.typing_session.as_ref().unwrap().quote_text, "if x:   return");
+    type_chars(&mut app, "if x:");
+    app.process_input(KeyCode::Tab, KeyModifiers::NONE);
+    assert_eq!(app.input_processor.current_text, "if x:   ");
+    assert_eq!(app.input_processor.cursor_position, 8);
+
+    let quotes_before = app.accumulated_stats.total_quotes;
+    let summary = app.type_text("return");
+    assert!(summary.completed);
+    assert_eq!(summary.errors, 0);
+    assert_eq!(app.accumulated_stats.total_quotes, quotes_before + 1);
+}
+
+#[test]
+fn test_event_callback_sees_keystrokes_and_quote_completion() {
+    let mut app = SpringKeys::new();
+    app.config.preferences.goal_accuracy = Some(50.0);
+    app.start_typing_session(Some("ok".to_string()));
+
+    let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = events.clone();
+    app.on_event(Box::new(move |event| sink.lock().unwrap().push(event.clone())));
+
+    type_chars(&mut app, "ok");
+    let events = events.lock().unwrap();
+    assert_eq!(events[0], AppEvent::KeystrokeRecorded { code: KeyCode::Char('o'), correct: true });
+    assert!(events.iter().any(|e| matches!(e, AppEvent::QuoteCompleted { .. })));
+    assert!(events.iter().any(|e| matches!(e, AppEvent::GoalMet(result) if result.is_met())));
+}
+
+#[test]
+fn test_quote_completed_event_reports_only_that_quote() {
+    let mut app = SpringKeys::new();
+    let completions = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = completions.clone();
+    app.on_event(Box::new(move |event| {
+        if let AppEvent::QuoteCompleted { accuracy, .. } = event {
+            sink.lock().unwrap().push(*accuracy);
+        }
+    }));
+
+    // Half the keys of the first quote are wrong, none of the second
+    app.start_typing_session(Some("ab".to_string()));
+    type_chars(&mut app, "ax");
+    app.process_input(KeyCode::Backspace, KeyModifiers::NONE);
+    type_chars(&mut app, "b");
+    app.start_typing_session(Some("cd".to_string()));
+    type_chars(&mut app, "cd");
+
+    assert_eq!(*completions.lock().unwrap(), vec![50.0, 100.0]);
+}
+
+#[test]
+fn test_rhythm_is_measured_on_the_completed_quote_alone() {
+    let mut app = SpringKeys::new();
+    // An uneven first quote
+    app.start_typing_session(Some("abcd".to_string()));
+    for (c, pause) in "abcd".chars().zip([0, 2, 40, 2]) {
+        std::thread::sleep(Duration::from_millis(pause));
+        app.process_input(KeyCode::Char(c), KeyModifiers::NONE);
+    }
+    assert!(app.last_rhythm.unwrap() > 0.0);
+
+    // The second has a single interval, too few to measure, whatever came before it
+    app.start_typing_session(Some("cd".to_string()));
+    type_chars(&mut app, "cd");
+    assert_eq!(app.last_rhythm, Some(0.0));
+}
+
+#[test]
+fn test_word_by_word_advances_only_on_correct_words() {
+    let mut app = SpringKeys::new();
+    app.change_game(GameType::WordByWord);
+    app.start_typing_session(Some("the cat sat".to_string()));
+    assert_eq!(app.typing_session.as_ref().unwrap().words(), vec!["the", "cat", "sat"]);
+    let quotes_before = app.accumulated_stats.total_quotes;
+
+    type_chars(&mut app, "the");
+    app.process_input(KeyCode::Enter, KeyModifiers::NONE);
+    assert_eq!(app.typing_session.as_ref().unwrap().current_word(), Some("cat"));
+    assert!(app.input_processor.current_text.is_empty());
+
+    // A wrong word is an error and stays put until it's fixed
+    type_chars(&mut app, "cot");
+    let errors_before = app.accumulated_stats.session_errors;
+    app.process_input(KeyCode::Enter, KeyModifiers::NONE);
+    assert_eq!(app.typing_session.as_ref().unwrap().current_word(), Some("cat"));
+    assert_eq!(app.input_processor.current_text, "cot");
+    assert_eq!(app.accumulated_stats.session_errors, errors_before + 1);
+
+    app.process_input(KeyCode::Backspace, KeyModifiers::NONE);
+    app.process_input(KeyCode::Backspace, KeyModifiers::NONE);
+    type_chars(&mut app, "at");
+    app.process_input(KeyCode::Enter, KeyModifiers::NONE);
+    assert_eq!(app.typing_session.as_ref().unwrap().current_word(), Some("sat"));
+
+    type_chars(&mut app, "sat");
+    assert_eq!(app.accumulated_stats.total_quotes, quotes_before);
+    app.process_input(KeyCode::Enter, KeyModifiers::NONE);
+    assert_eq!(app.accumulated_stats.total_quotes, quotes_before + 1);
+    assert_eq!(app.last_word_wpms.len(), 3);
+}
@@ -0,0 +1,182 @@
+use spring_keys::config::{Config, ConfigOverrides, DifficultyLevel, RenderMode};
+use spring_keys::quotes::quote_hash;
+use spring_keys::{GameType, KeyCode, KeyModifiers, QuoteDatabase, SpringKeys};
+use std::fs;
+
+#[test]
+fn test_minimal_config_is_migrated_with_defaults() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path = dir.path().join("springkeys.toml");
+    fs::write(&path, "[preferences]\nusername = \"Tester\"\n").unwrap();
+
+    let config = Config::load(&path).expect("minimal config should load");
+    let defaults = Config::default();
+
+    assert_eq!(config.preferences.username, "Tester");
+    assert_eq!(config.preferences.difficulty, DifficultyLevel::Beginner);
+    assert_eq!(config.preferences.sound_enabled, defaults.preferences.sound_enabled);
+    assert_eq!(config.ui.theme, defaults.ui.theme);
+    assert_eq!(config.ui.font_size, defaults.ui.font_size);
+    assert_eq!(config.version, env!("CARGO_PKG_VERSION"));
+
+    // The migrated config is written back with the full set of fields
+    let saved = fs::read_to_string(&path).unwrap();
+    assert!(saved.contains("version"));
+    assert!(saved.contains("[ui]"));
+    assert!(saved.contains("Tester"));
+}
+
+#[test]
+fn test_current_config_needs_no_migration() {
+    let mut config = Config::default();
+    assert!(!config.migrate());
+
+    config.version = "0.0.1".to_string();
+    assert!(config.migrate());
+    assert_eq!(config.version, env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn test_wpm_mode_round_trips_through_toml() {
+    use spring_keys::WpmMode;
+
+    let mut config = Config::default();
+    config.preferences.wpm_mode = WpmMode::ActualWords;
+    let toml = toml::to_string(&config).unwrap();
+    let loaded: Config = toml::from_str(&toml).unwrap();
+    assert_eq!(loaded.preferences.wpm_mode, WpmMode::ActualWords);
+
+    config.preferences.wpm_mode = WpmMode::CharsPerWord(6);
+    let toml = toml::to_string(&config).unwrap();
+    let loaded: Config = toml::from_str(&toml).unwrap();
+    assert_eq!(loaded.preferences.wpm_mode, WpmMode::CharsPerWord(6));
+}
+
+#[test]
+fn test_difficulty_map_can_route_expert_to_medium() {
+    use spring_keys::QuoteDifficulty;
+
+    let mut config = Config::default();
+    assert_eq!(config.quote_difficulty_for(DifficultyLevel::Expert), QuoteDifficulty::Hard);
+
+    config.preferences.difficulty_map.insert(DifficultyLevel::Expert, QuoteDifficulty::Medium);
+    let toml = toml::to_string(&config).unwrap();
+    let loaded: Config = toml::from_str(&toml).unwrap();
+    assert_eq!(loaded.quote_difficulty_for(DifficultyLevel::Expert), QuoteDifficulty::Medium);
+
+    // Levels missing from the map keep their default
+    config.preferences.difficulty_map.clear();
+    assert_eq!(config.quote_difficulty_for(DifficultyLevel::Beginner), QuoteDifficulty::Easy);
+}
+
+#[test]
+fn test_reload_config_picks_up_edits_on_disk() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path = dir.path().join("springkeys.toml");
+    Config::default().save(&path).unwrap();
+
+    let mut app = SpringKeys::new_silent();
+    app.load_config_from(&path).unwrap();
+    app.start_typing_session(Some("reload me".to_string()));
+
+    let mut edited = Config::default();
+    edited.preferences.goal_wpm = Some(55.0);
+    edited.ui.theme = "ocean".to_string();
+    edited.ui.render_mode = RenderMode::Minimal;
+    edited.save(&path).unwrap();
+
+    app.reload_config().unwrap();
+    assert_eq!(app.config.preferences.goal_wpm, Some(55.0));
+    assert_eq!(app.config.ui.theme, "ocean");
+    // The layout only changes with the next quote
+    assert_eq!(app.config.ui.render_mode, RenderMode::Full);
+    app.start_typing_session(None);
+    assert_eq!(app.config.ui.render_mode, RenderMode::Minimal);
+}
+
+#[test]
+fn test_reload_config_keeps_command_line_overrides() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path = dir.path().join("springkeys.toml");
+    Config::default().save(&path).unwrap();
+
+    let mut app = SpringKeys::new_silent();
+    app.load_config_from(&path).unwrap();
+    app.set_config_overrides(ConfigOverrides {
+        max_sessions: Some(5),
+        render_mode: Some(RenderMode::Minimal),
+        blind_mode: Some(true),
+        alternate_screen: Some(false),
+        ..Default::default()
+    });
+    app.start_typing_session(Some("reload me".to_string()));
+
+    let mut edited = Config::default();
+    edited.preferences.goal_wpm = Some(55.0);
+    edited.save(&path).unwrap();
+
+    app.reload_config().unwrap();
+    assert_eq!(app.config.preferences.goal_wpm, Some(55.0));
+    assert_eq!(app.config.preferences.max_sessions, 5);
+    assert_eq!(app.config.ui.render_mode, RenderMode::Minimal);
+    assert!(app.config.ui.blind_mode);
+    assert!(!app.config.ui.alternate_screen);
+
+    // The overrides stay out of the file
+    let on_disk = Config::load(&path).unwrap();
+    assert!(!on_disk.ui.blind_mode);
+    assert_eq!(on_disk.ui.render_mode, RenderMode::Full);
+}
+
+#[test]
+fn test_lesson_progress_is_saved_to_the_loaded_config_alone() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path = dir.path().join("springkeys.toml");
+    Config::default().save(&path).unwrap();
+
+    let mut app = SpringKeys::new_silent();
+    app.load_config_from(&path).unwrap();
+    app.set_config_overrides(ConfigOverrides { blind_mode: Some(true), ..Default::default() });
+    app.change_game(GameType::Learn);
+
+    // A sloppy first exercise doesn't hold back a clean second one
+    app.start_typing_session(Some("ab".to_string()));
+    app.feed_str("axy");
+    for _ in 0..2 {
+        app.process_input(KeyCode::Backspace, KeyModifiers::NONE);
+    }
+    app.feed_str("b");
+    assert_eq!(app.config.preferences.lesson_stage, 0);
+    app.start_typing_session(Some("cd".to_string()));
+    app.feed_str("cd");
+    assert_eq!(app.config.preferences.lesson_stage, 1);
+
+    let on_disk = Config::load(&path).unwrap();
+    assert_eq!(on_disk.preferences.lesson_stage, 1);
+    assert!(!on_disk.ui.blind_mode, "Command line overrides stay out of the file");
+}
+
+#[test]
+fn test_favorite_survives_config_round_trip() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path = dir.path().join("springkeys.toml");
+    Config::default().save(&path).unwrap();
+
+    let mut app = SpringKeys::new_silent();
+    app.load_config_from(&path).unwrap();
+    let quote = app.quote_db.next_random();
+    assert_eq!(app.quote_db.find_by_text(&quote.text).unwrap().source, quote.source);
+    app.start_typing_session(Some(quote.text.clone()));
+
+    assert_eq!(app.toggle_favorite(), Some(true));
+    let saved = Config::load(&path).unwrap();
+    assert_eq!(saved.preferences.favorite_quotes, vec![quote_hash(&quote.text)]);
+
+    // Only the favorite (and any copies of it in other files) is left to practice
+    let mut db = QuoteDatabase::new_silent();
+    assert!(db.favorites_only(&saved.preferences.favorite_quotes) >= 1);
+    assert_eq!(db.next_random().text, quote.text);
+
+    assert_eq!(app.toggle_favorite(), Some(false));
+    assert!(Config::load(&path).unwrap().preferences.favorite_quotes.is_empty());
+}
@@ -217,4 +217,23 @@ fn test_consume_mode_ui_initialization() {
         assert!(heatmap.contains_key(&key),
             "Letter key {} should be initialized with demo data", key);
     }
-} 
\ No newline at end of file
+}
+
+#[test]
+fn test_consume_mode_token_replay_reports_stats() {
+    let output = Command::new(env!("CARGO_BIN_EXE_spring-keys"))
+        .arg("consume")
+        .arg("H e l l o")
+        .env("SPRING_KEYS_TEST_MODE", "1")
+        .stderr(Stdio::null())
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stats_line = stdout.lines().last().expect("Expected a final stats line");
+    let stats: serde_json::Value = serde_json::from_str(stats_line).expect("Final line should be JSON");
+    assert_eq!(stats["tokens"], 5);
+    assert_eq!(stats["keystrokes"], 5);
+    assert_eq!(stats["accuracy"], 100.0);
+}
@@ -0,0 +1,37 @@
+use spring_keys::curriculum::{advance, curriculum};
+use spring_keys::QuoteDatabase;
+
+#[test]
+fn test_advance_through_two_stages() {
+    let stages = curriculum();
+    assert!(stages.len() >= 2);
+
+    // Falling short of the first lesson keeps the user on it
+    let stage = advance(0, stages[0].min_wpm - 1.0, 100.0);
+    assert_eq!(stage, 0);
+
+    let stage = advance(stage, stages[0].min_wpm, stages[0].min_accuracy);
+    assert_eq!(stage, 1);
+
+    let stage = advance(stage, stages[1].min_wpm, stages[1].min_accuracy - 1.0);
+    assert_eq!(stage, 1);
+
+    let stage = advance(stage, stages[1].min_wpm + 10.0, 100.0);
+    assert_eq!(stage, 2);
+}
+
+#[test]
+fn test_advance_stops_at_end_of_curriculum() {
+    let end = curriculum().len();
+    assert_eq!(advance(end - 1, 500.0, 100.0), end);
+    assert_eq!(advance(end, 500.0, 100.0), end);
+}
+
+#[test]
+fn test_every_lesson_has_quotes() {
+    let mut db = QuoteDatabase::new_silent();
+    for stage in curriculum() {
+        let quote = db.next_in_category(stage.category);
+        assert!(quote.is_some(), "no quotes for lesson '{}'", stage.name);
+    }
+}
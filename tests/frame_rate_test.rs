@@ -0,0 +1,19 @@
+use spring_keys::ui::frame_rate::{clamp_fps, frame_delay, frame_duration, MAX_FPS, MIN_FPS};
+use std::time::Duration;
+
+#[test]
+fn test_fps_is_clamped() {
+    assert_eq!(clamp_fps(0), MIN_FPS);
+    assert_eq!(clamp_fps(30), 30);
+    assert_eq!(clamp_fps(500), MAX_FPS);
+}
+
+#[test]
+fn test_frame_delay_subtracts_work_time() {
+    assert_eq!(frame_duration(10), Duration::from_millis(100));
+    assert_eq!(frame_delay(10, Duration::from_millis(30)), Duration::from_millis(70));
+    // A frame that overran its budget starts the next one immediately
+    assert_eq!(frame_delay(10, Duration::from_millis(150)), Duration::ZERO);
+    // Out-of-range rates use the clamped budget
+    assert_eq!(frame_delay(0, Duration::ZERO), Duration::from_secs(1));
+}
@@ -0,0 +1,35 @@
+use spring_keys::TypingMetrics;
+use spring_keys::ui::caps::ColorMode;
+use spring_keys::ui::MIN_TERMINAL_WIDTH;
+use spring_keys::ui::heatmap::{draw_unified_keyboard_heatmap, invalidate_heatmap_cache, key_draw_count};
+
+// Kept in its own test binary: the frame cache and draw counter are process-wide.
+// Needs the `draw-stats` feature for the counter, e.g. `cargo test --all-features`.
+
+#[test]
+fn test_identical_render_redraws_no_keys() {
+    let mut metrics = TypingMetrics::new();
+    metrics.key_timings.insert('a', vec![120.0, 140.0]);
+    metrics.key_timings.insert('s', vec![250.0]);
+
+    let render = |metrics: &TypingMetrics| {
+        let before = key_draw_count();
+        draw_unified_keyboard_heatmap(&mut Vec::new(), metrics, 0, MIN_TERMINAL_WIDTH, ColorMode::Truecolor).unwrap();
+        key_draw_count() - before
+    };
+
+    // The first frame draws every key on the keyboard
+    assert!(render(&metrics) > 40);
+
+    // Nothing changed, so nothing is re-emitted
+    assert_eq!(render(&metrics), 0);
+
+    // A new timing changes only that key (and others whose shade shifts with the speed range)
+    metrics.key_timings.get_mut(&'a').unwrap().push(130.0);
+    let changed = render(&metrics);
+    assert!((1..40).contains(&changed), "redrew {} keys", changed);
+
+    // After the screen is cleared everything is drawn again
+    invalidate_heatmap_cache();
+    assert!(render(&metrics) > 40);
+}
@@ -1,6 +1,12 @@
 #[cfg(test)]
 mod tests {
-    use spring_keys::{InputProcessor, TypingSession};
+    use spring_keys::{InputProcessor, KeyCode, KeyModifiers, TypingSession};
+    use std::time::{Duration, Instant};
+
+    fn press(processor: &mut InputProcessor, key: KeyCode, modifiers: KeyModifiers) {
+        processor.process_key_event(key, modifiers, None);
+        processor.process_queued_events();
+    }
     
 
     #[test]
@@ -82,4 +88,355 @@ mod tests {
         let result = processor.validate_input(&session.quote_text);
         assert!(!result.is_valid);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_navigation_and_function_key_tokens() {
+        let mut processor = InputProcessor::new();
+        let mut session = TypingSession::new("abcd".to_string());
+
+        processor.process_token_sequence("a b d", Some(&mut session));
+        assert_eq!(processor.cursor_position, 3);
+
+        // Arrow keys move the cursor without touching the text
+        assert!(processor.process_token("<left>", Some(&mut session)));
+        assert_eq!(processor.cursor_position, 2);
+        assert!(processor.process_token("<right>", Some(&mut session)));
+        assert_eq!(processor.cursor_position, 3);
+        assert!(processor.process_token("<left>", Some(&mut session)));
+        assert!(processor.process_token("c", Some(&mut session)));
+        assert_eq!(processor.current_text, "abcd");
+
+        // Delete removes the character at the cursor
+        assert!(processor.process_token("<del>", Some(&mut session)));
+        assert_eq!(processor.current_text, "abc");
+        assert_eq!(processor.cursor_position, 3);
+
+        // Up/down, home/end and function keys are recognized tokens
+        for token in ["<up>", "<down>", "<home>", "<end>", "<f5>", "<f12>"] {
+            assert!(processor.process_token(token, Some(&mut session)), "{} should be accepted", token);
+        }
+        assert_eq!(processor.current_text, "abc");
+
+        // Out-of-range function keys are unknown tokens
+        assert!(!processor.process_token("<f13>", Some(&mut session)));
+        assert!(!processor.process_token("<fx>", Some(&mut session)));
+    }
+
+    #[test]
+    fn test_home_end_navigation() {
+        let mut processor = InputProcessor::new();
+
+        // Empty buffer: both ends are position 0
+        assert!(processor.process_token("<home>", None));
+        assert_eq!(processor.cursor_position, 0);
+        assert!(processor.process_token("<end>", None));
+        assert_eq!(processor.cursor_position, 0);
+
+        // Populated buffer with a multi-byte character; cursor is counted in chars
+        processor.process_token_sequence("c a f é", None);
+        assert_eq!(processor.cursor_position, 4);
+        assert!(processor.process_token("<home>", None));
+        assert_eq!(processor.cursor_position, 0);
+        assert!(processor.process_token("<end>", None));
+        assert_eq!(processor.cursor_position, 4);
+
+        // Ctrl+A / Ctrl+E are aliases and don't insert characters
+        assert!(processor.process_token("<ctrl+a>", None));
+        assert_eq!(processor.cursor_position, 0);
+        assert!(processor.process_token("x", None));
+        assert_eq!(processor.current_text, "xcafé");
+        assert!(processor.process_token("<ctrl+e>", None));
+        assert_eq!(processor.cursor_position, 5);
+        assert!(processor.process_token("<backspace>", None));
+        assert_eq!(processor.current_text, "xcaf");
+    }
+
+    #[test]
+    fn test_space_tokens_versus_separators() {
+        // Each <space> types one space; runs of separators collapse to nothing
+        let mut processor = InputProcessor::new();
+        assert_eq!(processor.process_token_sequence("a <space> <space> b", None), 4);
+        assert_eq!(processor.current_text, "a  b");
+
+        let mut processor = InputProcessor::new();
+        processor.process_token_sequence("a   b", None);
+        assert_eq!(processor.current_text, "ab");
+
+        let mut processor = InputProcessor::new();
+        assert_eq!(processor.process_token_sequence_with("a|<space>|<space>||b", "|", None), 4);
+        assert_eq!(processor.current_text, "a  b");
+
+        // With another separator, whitespace stays part of the token, so `<ctrl+w> ` is unknown
+        let mut processor = InputProcessor::new();
+        assert_eq!(processor.process_token_sequence_with("x,<ctrl+w> ,y", ",", None), 2);
+        assert_eq!(processor.current_text, "xy");
+    }
+
+    #[test]
+    fn test_text_from_tokens() {
+        assert_eq!(InputProcessor::text_from_tokens("H i <space> y o u"), "Hi you");
+        assert_eq!(InputProcessor::text_from_tokens("a b x <bs> c"), "abc");
+        assert_eq!(InputProcessor::text_from_tokens(""), "");
+    }
+
+    #[test]
+    fn test_word_delete_and_clear_line() {
+        let mut processor = InputProcessor::new();
+        processor.process_token_sequence("h e l l o <space> w o r l d", None);
+
+        // Ctrl+W removes the word before the cursor, keeping the separating space
+        assert!(processor.process_token("<ctrl+w>", None));
+        assert_eq!(processor.current_text, "hello ");
+        assert_eq!(processor.cursor_position, 6);
+
+        // A second Ctrl+W skips the trailing space and removes the previous word
+        assert!(processor.process_token("<ctrl+w>", None));
+        assert_eq!(processor.current_text, "");
+
+        // Ctrl+U from the end clears the whole line
+        processor.process_token_sequence("a b <space> c d", None);
+        assert!(processor.process_token("<ctrl+u>", None));
+        assert_eq!(processor.current_text, "");
+        assert_eq!(processor.cursor_position, 0);
+
+        // Ctrl+U from the middle keeps text after the cursor
+        processor.process_token_sequence("a b c", None);
+        processor.process_token("<left>", None);
+        processor.process_token("<ctrl+u>", None);
+        assert_eq!(processor.current_text, "c");
+        assert_eq!(processor.cursor_position, 0);
+    }
+
+    #[test]
+    fn test_ctrl_and_shift_backspace_combos() {
+        let mut processor = InputProcessor::new();
+        for c in "hello big world".chars() {
+            press(&mut processor, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+
+        press(&mut processor, KeyCode::Backspace, KeyModifiers::CONTROL);
+        assert_eq!(processor.current_text, "hello big ");
+        assert_eq!(processor.cursor_position, 10);
+
+        press(&mut processor, KeyCode::Backspace, KeyModifiers::SHIFT);
+        assert_eq!(processor.current_text, "");
+        assert_eq!(processor.cursor_position, 0);
+
+        // The combos are edits, so undo brings the text back
+        assert!(processor.undo());
+        assert_eq!(processor.current_text, "hello big ");
+
+        // Plain backspace still erases one character, and the tokens map to the combos
+        press(&mut processor, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(processor.current_text, "hello big");
+        assert!(processor.process_token("<ctrl+bs>", None));
+        assert_eq!(processor.current_text, "hello ");
+        assert!(processor.process_token("<shift+backspace>", None));
+        assert_eq!(processor.current_text, "");
+    }
+
+    #[test]
+    fn test_backspace_combos_are_not_recorded_as_keystrokes() {
+        let mut session = TypingSession::new("abc def".to_string());
+        let mut processor = InputProcessor::new();
+        processor.process_key_event(KeyCode::Char('a'), KeyModifiers::NONE, Some(&mut session));
+        processor.process_key_event(KeyCode::Backspace, KeyModifiers::CONTROL, Some(&mut session));
+        processor.process_key_event(KeyCode::Backspace, KeyModifiers::SHIFT, Some(&mut session));
+        processor.process_queued_events();
+        assert_eq!(session.metrics.keystrokes, 1);
+
+        processor.process_key_event(KeyCode::Backspace, KeyModifiers::NONE, Some(&mut session));
+        assert_eq!(session.metrics.keystrokes, 2);
+    }
+
+    #[test]
+    fn test_undo_and_redo_restore_edits() {
+        let mut processor = InputProcessor::new();
+        processor.process_token_sequence("a b c", None);
+
+        assert!(processor.process_token("<ctrl+z>", None));
+        assert!(processor.process_token("<ctrl+z>", None));
+        assert_eq!(processor.current_text, "a");
+        assert_eq!(processor.cursor_position, 1);
+
+        assert!(processor.process_token("<ctrl+y>", None));
+        assert_eq!(processor.current_text, "ab");
+        assert_eq!(processor.cursor_position, 2);
+
+        // A new edit drops the redo history
+        processor.process_token("x", None);
+        assert!(!processor.redo());
+        assert_eq!(processor.current_text, "abx");
+    }
+
+    #[test]
+    fn test_undo_does_not_unrecord_keystrokes() {
+        let mut processor = InputProcessor::new();
+        let mut session = TypingSession::new("abc".to_string());
+        processor.process_token_sequence("a b", Some(&mut session));
+        let keystrokes = session.metrics.keystrokes;
+
+        assert!(processor.process_token("<ctrl+z>", Some(&mut session)));
+        assert_eq!(processor.current_text, "a");
+        assert_eq!(session.metrics.keystrokes, keystrokes);
+    }
+
+    #[test]
+    fn test_caps_lock_tracking_sets_case_by_default() {
+        let mut processor = InputProcessor::new();
+        // Without caps lock, an unshifted character is lowercased
+        press(&mut processor, KeyCode::Char('A'), KeyModifiers::NONE);
+        press(&mut processor, KeyCode::CapsLock, KeyModifiers::NONE);
+        press(&mut processor, KeyCode::Char('b'), KeyModifiers::NONE);
+        press(&mut processor, KeyCode::Char('c'), KeyModifiers::SHIFT);
+        assert_eq!(processor.current_text, "aBC");
+    }
+
+    #[test]
+    fn test_respect_shift_only_ignores_caps_lock_tracking() {
+        let mut processor = InputProcessor::new();
+        processor.respect_shift_only = true;
+        // The character comes through as the terminal sent it
+        press(&mut processor, KeyCode::Char('A'), KeyModifiers::NONE);
+        press(&mut processor, KeyCode::CapsLock, KeyModifiers::NONE);
+        press(&mut processor, KeyCode::Char('b'), KeyModifiers::NONE);
+        press(&mut processor, KeyCode::Char('c'), KeyModifiers::SHIFT);
+        assert_eq!(processor.current_text, "AbC");
+    }
+
+    #[test]
+    fn test_idle_duration_counts_from_last_key() {
+        let mut processor = InputProcessor::new();
+        assert_eq!(processor.idle_duration(), None);
+
+        processor.last_key_time = Some(Instant::now() - Duration::from_secs(30));
+        let idle = processor.idle_duration().unwrap();
+        assert!(idle >= Duration::from_secs(30) && idle < Duration::from_secs(31));
+
+        // A key press resets it, and clearing forgets it
+        processor.process_token("a", None);
+        assert!(processor.idle_duration().unwrap() < Duration::from_secs(1));
+        processor.clear();
+        assert_eq!(processor.idle_duration(), None);
+    }
+
+    #[test]
+    fn test_delete_forward_removes_char_under_cursor() {
+        let mut processor = InputProcessor::new();
+        processor.process_token_sequence("n a ï v e", None);
+
+        // At the end there is nothing under the cursor
+        assert!(!processor.delete_forward());
+        assert_eq!(processor.current_text, "naïve");
+        assert_eq!(processor.cursor_position, 5);
+
+        // In the middle the multi-byte character under the cursor goes, and the cursor stays put
+        processor.process_token_sequence("<left> <left> <left>", None);
+        assert!(processor.delete_forward());
+        assert_eq!(processor.current_text, "nave");
+        assert_eq!(processor.cursor_position, 2);
+        assert!(processor.delete_forward());
+        assert_eq!(processor.current_text, "nae");
+    }
+
+    #[test]
+    fn test_validate_combining_accent_as_one_character() {
+        // "Café" spelled with 'e' followed by a combining acute accent
+        let quote = "Cafe\u{301} au lait";
+        let mut processor = InputProcessor::new();
+
+        // The base letter alone is a partial match for the accented grapheme
+        processor.current_text = "Cafe".to_string();
+        assert!(processor.validate_input(quote).is_valid);
+
+        processor.current_text = "Cafe\u{301} ".to_string();
+        assert!(processor.validate_input(quote).is_valid);
+
+        // Skipping the accent is an error at the accented grapheme, not at a code point
+        processor.current_text = "Cafe a".to_string();
+        let result = processor.validate_input(quote);
+        assert!(!result.is_valid);
+        assert_eq!(result.position, 3);
+
+        // A precomposed 'é' is a different grapheme from 'e' plus the combining accent
+        processor.current_text = "Caf\u{e9}".to_string();
+        let result = processor.validate_input(quote);
+        assert!(!result.is_valid);
+        assert_eq!(result.position, 3);
+    }
+
+    #[test]
+    fn test_rolled_over_keys_insert_in_press_order() {
+        use spring_keys::input::KeyboardEvent;
+
+        // 'b' was pressed 2ms after 'a' but its event was queued first
+        let now = Instant::now();
+        let mut a = KeyboardEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+        let mut b = KeyboardEvent::new(KeyCode::Char('b'), KeyModifiers::NONE);
+        a.timestamp = now - Duration::from_millis(2);
+        b.timestamp = now;
+
+        let mut processor = InputProcessor::new();
+        processor.event_queue.push(b);
+        processor.event_queue.push(a);
+        assert_eq!(processor.event_queue.peek().map(|e| e.key), Some(KeyCode::Char('a')));
+        processor.process_queued_events();
+        assert_eq!(processor.current_text, "ab");
+    }
+
+    #[test]
+    fn test_set_text_clamps_cursor() {
+        let mut processor = InputProcessor::new();
+        processor.set_text("Caf\u{e9}", 2);
+        assert_eq!(processor.current_text, "Caf\u{e9}");
+        assert_eq!(processor.cursor_position, 2);
+
+        // Past the end, the cursor lands after the last character rather than a byte offset
+        processor.set_text("Caf\u{e9}", 10);
+        assert_eq!(processor.cursor_position, 4);
+
+        // Typing carries on from the cursor
+        press(&mut processor, KeyCode::Char('!'), KeyModifiers::NONE);
+        assert_eq!(processor.current_text, "Caf\u{e9}!");
+    }
+
+    #[test]
+    fn test_session_tracks_multi_codepoint_graphemes() {
+        // An 'e' with a combining accent and a thumbs up with a skin tone, one grapheme each
+        let quote = "cafe\u{301} \u{1F44D}\u{1F3FD}";
+        let mut session = TypingSession::new(quote.to_string());
+        let mut processor = InputProcessor::new();
+        let type_char = |processor: &mut InputProcessor, session: &mut TypingSession, c: char| {
+            processor.process_key_event(KeyCode::Char(c), KeyModifiers::NONE, Some(session));
+            processor.process_queued_events();
+        };
+
+        for c in "cafe".chars() {
+            type_char(&mut processor, &mut session, c);
+        }
+        // The accented grapheme is started but not finished
+        assert_eq!(session.current_position, 3);
+        assert_eq!(session.grapheme_progress, 1);
+
+        // Strict mode won't move on before the accent is typed
+        processor.stop_on_error = true;
+        let result = processor.validate_next_char(quote, ' ');
+        assert!(!result.is_valid);
+        assert_eq!(result.position, 3);
+        assert!(processor.validate_next_char(quote, '\u{301}').is_valid);
+
+        for c in "\u{301} \u{1F44D}".chars() {
+            type_char(&mut processor, &mut session, c);
+        }
+        assert_eq!(session.current_position, 5);
+        assert!(!session.is_complete);
+        type_char(&mut processor, &mut session, '\u{1F3FD}');
+
+        assert_eq!(processor.current_text, quote);
+        assert!(processor.validate_input(quote).is_valid);
+        assert_eq!(session.current_position, 6);
+        assert!(session.is_complete);
+        assert_eq!(session.snapshot().progress, 1.0);
+        assert!(session.metrics.errors.is_empty());
+    }
+}
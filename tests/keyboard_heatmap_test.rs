@@ -4,6 +4,7 @@ mod tests {
     // Import the required types
     use spring_keys::TypingMetrics;
     use spring_keys::ui::heatmap;
+    use spring_keys::ui::caps::ColorMode;
     
     #[test]
     fn test_unified_heatmap_drawing() {
@@ -37,7 +38,7 @@ mod tests {
         let mut buffer = Vec::new();
         
         // Test the unified keyboard heatmap rendering
-        let result = heatmap::draw_unified_keyboard_heatmap(&mut buffer, &metrics, 1);
+        let result = heatmap::draw_unified_keyboard_heatmap(&mut buffer, &metrics, 1, spring_keys::ui::MIN_TERMINAL_WIDTH, ColorMode::Truecolor);
         
         // Make sure rendering succeeds
         assert!(result.is_ok(), "Unified heatmap rendering failed: {:?}", result.err());
@@ -49,4 +50,41 @@ mod tests {
         let output = String::from_utf8_lossy(&buffer);
         assert!(output.contains("\u{1b}["), "Output doesn't contain ANSI escape sequences");
     }
+
+    #[test]
+    fn test_key_layout_fits_terminal_width() {
+        use spring_keys::ui::MIN_TERMINAL_WIDTH;
+
+        for width in [MIN_TERMINAL_WIDTH, 140, 200] {
+            let layout = heatmap::key_layout(width);
+            assert_eq!(layout.len(), 46);
+            for key in &layout {
+                assert!(key.x + key.width <= width, "'{}' ends at {} past {}", key.key, key.x + key.width, width);
+            }
+        }
+
+        // With room to spare keys keep their full size
+        let wide = heatmap::key_layout(200);
+        let x_of = |layout: &[heatmap::KeyPlacement], c: char| layout.iter().find(|k| k.key == c).unwrap().x;
+        assert!(wide.iter().all(|k| k.width == 8));
+        assert_eq!(x_of(&wide, '2') - x_of(&wide, '1'), 10);
+
+        // Each row starts further right than the one above, like a real keyboard
+        for layout in [wide, heatmap::key_layout(MIN_TERMINAL_WIDTH)] {
+            assert!(x_of(&layout, '1') < x_of(&layout, 'q'));
+            assert!(x_of(&layout, 'q') < x_of(&layout, 'a'));
+            assert!(x_of(&layout, 'a') < x_of(&layout, 'z'));
+        }
+    }
+
+    #[test]
+    fn test_speed_maps_to_braille_fill() {
+        assert_eq!(heatmap::speed_to_braille(0.0), '⣀');
+        assert_eq!(heatmap::speed_to_braille(0.3), '⣤');
+        assert_eq!(heatmap::speed_to_braille(0.7), '⣶');
+        assert_eq!(heatmap::speed_to_braille(1.0), '⣿');
+        // Out-of-range speeds are clamped
+        assert_eq!(heatmap::speed_to_braille(-1.0), '⣀');
+        assert_eq!(heatmap::speed_to_braille(5.0), '⣿');
+    }
 } 
\ No newline at end of file
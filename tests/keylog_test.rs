@@ -0,0 +1,38 @@
+use spring_keys::{InputProcessor, TypingSession};
+use spring_keys::input::KEYLOG_ENV_VAR;
+use std::fs;
+
+// Both scenarios live in one test because they share the process-wide env var
+#[test]
+fn test_keylog_writes_jsonl_per_keystroke() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let log_path = dir.path().join("keys.jsonl");
+    std::env::set_var(KEYLOG_ENV_VAR, &log_path);
+
+    let mut processor = InputProcessor::new();
+    let mut session = TypingSession::new("Hi".to_string());
+    processor.process_token("H", Some(&mut session));
+    processor.process_token("i", Some(&mut session));
+    processor.process_token("<backspace>", Some(&mut session));
+
+    // Each keystroke is flushed immediately, so the file is complete without dropping the processor
+    let content = fs::read_to_string(&log_path).expect("Keylog file should exist");
+    let records: Vec<serde_json::Value> = content
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("Each line should be valid JSON"))
+        .collect();
+
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0]["resulting_char"], "H");
+    assert_eq!(records[1]["keycode"], "Char('i')");
+    assert!(records[2]["resulting_char"].is_null());
+    assert!(records[0]["timestamp_ms"].as_u64().unwrap() > 0);
+
+    // A path that can't be opened disables the log instead of panicking
+    std::env::set_var(KEYLOG_ENV_VAR, dir.path().join("missing").join("keys.jsonl"));
+    let mut processor = InputProcessor::new();
+    assert!(processor.keylog.is_none());
+    assert!(processor.process_token("a", None));
+
+    std::env::remove_var(KEYLOG_ENV_VAR);
+}
@@ -0,0 +1,19 @@
+use spring_keys::ui::latency::LatencyStats;
+use std::time::Duration;
+
+#[test]
+fn test_latency_percentiles_from_known_samples() {
+    let mut stats = LatencyStats::new();
+    assert_eq!(stats.percentile(50.0), None);
+
+    // Record 1..=100ms out of order
+    for ms in (1..=100).rev() {
+        stats.record(Duration::from_millis(ms));
+    }
+    assert_eq!(stats.len(), 100);
+    assert_eq!(stats.percentile(50.0), Some(Duration::from_millis(50)));
+    assert_eq!(stats.percentile(99.0), Some(Duration::from_millis(99)));
+    assert_eq!(stats.percentile(100.0), Some(Duration::from_millis(100)));
+    assert_eq!(stats.percentile(0.0), Some(Duration::from_millis(1)));
+    assert!(stats.summary().contains("p50 50.00ms, p99 99.00ms"));
+}
@@ -0,0 +1,51 @@
+use log::LevelFilter;
+use spring_keys::logger::{self, LoggerOptions, LOG_LEVEL_ENV_VAR};
+use std::fs;
+
+#[test]
+fn test_env_level_overrides_default() {
+    let options = LoggerOptions {
+        level: LevelFilter::Error,
+        ..Default::default()
+    };
+
+    std::env::set_var(LOG_LEVEL_ENV_VAR, "hyper=trace,spring_keys=debug");
+    assert_eq!(options.effective_level(), LevelFilter::Debug);
+
+    std::env::set_var(LOG_LEVEL_ENV_VAR, "WARN");
+    assert_eq!(options.effective_level(), LevelFilter::Warn);
+
+    // Unparsable values fall back to the default
+    std::env::set_var(LOG_LEVEL_ENV_VAR, "loud");
+    assert_eq!(options.effective_level(), LevelFilter::Error);
+
+    std::env::set_var(LOG_LEVEL_ENV_VAR, "off");
+    let ignore_env = LoggerOptions {
+        level_from_env: false,
+        ..options
+    };
+    assert_eq!(ignore_env.effective_level(), LevelFilter::Error);
+
+    std::env::remove_var(LOG_LEVEL_ENV_VAR);
+    assert_eq!(ignore_env.effective_level(), LevelFilter::Error);
+}
+
+#[test]
+fn test_prune_daily_logs_keeps_today_within_cap() {
+    let dir = tempfile::tempdir().unwrap();
+    let base = dir.path().join("spring-keys.log");
+    let days = ["2024-05-01", "2024-05-02", "2024-05-03"];
+    for day in days {
+        fs::write(dir.path().join(format!("spring-keys-{}.log", day)), vec![b'x'; 100]).unwrap();
+    }
+    fs::write(dir.path().join("other.log"), vec![b'x'; 500]).unwrap();
+
+    let today = logger::daily_log_path(&base, chrono::NaiveDate::from_ymd_opt(2024, 5, 3).unwrap());
+    assert_eq!(today, dir.path().join("spring-keys-2024-05-03.log"));
+
+    let removed = logger::prune_daily_logs(&base, &today, 150).unwrap();
+    assert_eq!(removed, 2);
+    assert!(today.exists());
+    assert!(!dir.path().join("spring-keys-2024-05-01.log").exists());
+    assert!(dir.path().join("other.log").exists());
+}
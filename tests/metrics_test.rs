@@ -0,0 +1,370 @@
+use spring_keys::{ExtendedStats, TypingMetrics};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_long_pause_is_not_recorded_in_histogram() {
+    let mut metrics = TypingMetrics::new();
+
+    // A normal interval is recorded
+    metrics.last_keystroke_time = Some(Instant::now() - Duration::from_millis(150));
+    metrics.record_keystroke('a', 'a', 0);
+    assert_eq!(metrics.key_timings.get(&'a').map(|t| t.len()), Some(1));
+
+    // A 10-second reading pause is dropped from both timings and histogram
+    metrics.last_keystroke_time = Some(Instant::now() - Duration::from_secs(10));
+    metrics.record_keystroke('b', 'b', 1);
+    assert!(!metrics.key_timings.contains_key(&'b'));
+    assert!(metrics.key_histogram.max < 2000.0, "Histogram max was {}", metrics.key_histogram.max);
+    assert_eq!(metrics.keystrokes, 2);
+}
+
+#[test]
+fn test_interval_cap_is_configurable() {
+    let mut metrics = TypingMetrics::new();
+    metrics.max_recorded_interval_ms = 100.0;
+
+    metrics.last_keystroke_time = Some(Instant::now() - Duration::from_millis(500));
+    metrics.record_keystroke('a', 'a', 0);
+    assert!(metrics.key_timings.is_empty());
+}
+
+#[test]
+fn test_heatmap_to_json_includes_averages_and_hits() {
+    let mut metrics = TypingMetrics::new();
+    metrics.key_timings.insert('a', vec![100.0, 200.0]);
+    metrics.key_timings.insert('b', vec![50.0]);
+
+    let json = metrics.heatmap_to_json();
+    let parsed: serde_json::Value = serde_json::from_str(&json).expect("Export should be valid JSON");
+
+    assert_eq!(parsed["a"]["avg_ms"], 150.0);
+    assert_eq!(parsed["a"]["hits"], 2);
+    assert_eq!(parsed["b"]["hits"], 1);
+}
+
+#[test]
+fn test_sparkline_matches_width_and_rising_trend() {
+    let mut metrics = TypingMetrics::new();
+    for i in 0..20 {
+        metrics.wpm_samples.push((i as f64, 10.0 + i as f64 * 5.0));
+    }
+
+    let sparkline = metrics.render_sparkline(10);
+    let columns: Vec<char> = sparkline.chars().collect();
+    assert_eq!(columns.len(), 10);
+    assert_eq!(columns[0], '▁');
+    assert_eq!(columns[9], '█');
+    assert!(columns.windows(2).all(|pair| pair[0] <= pair[1]));
+
+    // Fewer samples than columns still fills the requested width
+    assert_eq!(metrics.render_sparkline(50).chars().count(), 50);
+    assert_eq!(TypingMetrics::new().render_sparkline(8).chars().count(), 8);
+}
+
+#[test]
+fn test_merge_sums_counts_and_concatenates_timings() {
+    let mut first = TypingMetrics::new();
+    first.keystrokes = 10;
+    first.correct_keystrokes = 9;
+    first.key_timings.insert('a', vec![100.0, 120.0]);
+    first.start_time = first.current_time - Duration::from_secs(30);
+
+    let mut second = TypingMetrics::new();
+    second.keystrokes = 20;
+    second.correct_keystrokes = 16;
+    second.key_timings.insert('a', vec![140.0]);
+    second.key_timings.insert('b', vec![200.0]);
+    second.start_time = second.current_time - Duration::from_secs(30);
+
+    first.merge(&second);
+
+    assert_eq!(first.keystrokes, 30);
+    assert_eq!(first.correct_keystrokes, 25);
+    assert_eq!(first.key_timings[&'a'], vec![100.0, 120.0, 140.0]);
+    assert_eq!(first.key_timings[&'b'], vec![200.0]);
+
+    // 25 correct keystrokes = 5 words over one minute, not the mean of the two WPMs
+    assert!((first.wpm - 5.0).abs() < 0.1, "wpm was {}", first.wpm);
+    assert!((first.accuracy - 25.0 / 30.0 * 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_diff_heatmap_reports_faster_and_slower_keys() {
+    let mut baseline = TypingMetrics::new();
+    baseline.key_timings.insert('a', vec![300.0, 300.0]);
+    baseline.key_timings.insert('b', vec![150.0]);
+    baseline.key_timings.insert('c', vec![200.0]);
+
+    let mut current = TypingMetrics::new();
+    current.key_timings.insert('a', vec![200.0, 200.0]);
+    current.key_timings.insert('b', vec![250.0]);
+    current.key_timings.insert('z', vec![100.0]);
+
+    let diff = current.diff_heatmap(&baseline);
+    assert!(diff[&'a'] < 0.0, "'a' got faster");
+    assert!(diff[&'b'] > 0.0, "'b' got slower");
+    assert!((diff[&'a'] + 100.0).abs() < 1e-6);
+    assert!(!diff.contains_key(&'c') && !diff.contains_key(&'z'));
+}
+
+#[test]
+fn test_key_averages_from_heatmap_export() {
+    let mut metrics = TypingMetrics::new();
+    metrics.key_timings.insert('q', vec![100.0, 300.0]);
+
+    let averages = spring_keys::core::metrics::key_averages_from_json(&metrics.heatmap_to_json())
+        .expect("heatmap export should parse");
+    assert!((averages[&'q'] - 200.0).abs() < 1e-9);
+    assert!(spring_keys::core::metrics::key_averages_from_json("not json").is_none());
+}
+
+#[test]
+fn test_finger_load_for_home_row() {
+    use spring_keys::Finger;
+
+    let mut metrics = TypingMetrics::new();
+    for (i, c) in "asdfjkl;".chars().enumerate() {
+        metrics.record_keystroke(c, c, i);
+    }
+
+    let load = metrics.finger_load();
+    assert_eq!(load.len(), 8);
+    for finger in [Finger::LeftPinky, Finger::LeftRing, Finger::LeftMiddle, Finger::LeftIndex,
+                   Finger::RightIndex, Finger::RightMiddle, Finger::RightRing, Finger::RightPinky] {
+        assert!((load[&finger] - 0.125).abs() < 1e-9, "{:?} should carry an eighth", finger);
+    }
+
+    // Index-finger keys pile onto the index fingers only
+    let mut metrics = TypingMetrics::new();
+    for (i, c) in "fgHj".chars().enumerate() {
+        metrics.record_keystroke(c, c, i);
+    }
+    let load = metrics.finger_load();
+    assert_eq!(load[&Finger::LeftIndex], 0.5);
+    assert_eq!(load[&Finger::RightIndex], 0.5);
+    assert!(TypingMetrics::new().finger_load().is_empty());
+}
+
+#[test]
+fn test_finger_names_round_trip() {
+    use spring_keys::Finger;
+
+    for finger in Finger::ALL {
+        assert_eq!(finger.to_string().parse::<Finger>(), Ok(finger));
+    }
+    assert_eq!(Finger::LeftIndex.to_string(), "left-index");
+    assert_eq!("Right-Pinky".parse::<Finger>(), Ok(Finger::RightPinky));
+    assert!("left-toe".parse::<Finger>().is_err());
+}
+
+#[test]
+fn test_keyboard_row_names_round_trip() {
+    use spring_keys::core::metrics::KeyboardRow;
+
+    for row in KeyboardRow::ALL {
+        assert_eq!(row.to_string().parse::<KeyboardRow>(), Ok(row));
+    }
+    assert_eq!(KeyboardRow::Home.to_string(), "home");
+    assert!("middle".parse::<KeyboardRow>().is_err());
+}
+
+#[test]
+fn test_confusion_matrix_records_substitutions() {
+    let mut metrics = TypingMetrics::new();
+    // Typed 'o' for 'a' twice and 'x' for 'c' once
+    metrics.record_keystroke('o', 'a', 0);
+    metrics.record_keystroke('b', 'b', 1);
+    metrics.record_keystroke('o', 'a', 2);
+    metrics.record_keystroke('x', 'c', 3);
+    metrics.calculate_overall_metrics();
+
+    let matrix = metrics.confusion_matrix();
+    assert_eq!(matrix.get(&('a', 'o')), Some(&2));
+    assert_eq!(matrix.get(&('c', 'x')), Some(&1));
+    assert_eq!(matrix.len(), 2);
+    assert_eq!(metrics.top_confusions(1), vec![(('a', 'o'), 2)]);
+
+    // Unlike `errors`, the summary is part of the saved stats
+    let json: serde_json::Value = serde_json::to_value(&metrics).unwrap();
+    assert!(json.get("errors").is_none());
+    assert_eq!(json["error_summary"][0], serde_json::json!(['a', 'o', 0]));
+
+    // Recalculating loaded metrics doesn't wipe the summary the missing errors were saved as
+    let mut loaded = TypingMetrics::new();
+    loaded.error_summary = metrics.error_summary.clone();
+    loaded.calculate_overall_metrics();
+    assert_eq!(loaded.confusion_matrix(), matrix);
+}
+
+#[test]
+fn test_wpm_modes_count_words_differently() {
+    use spring_keys::WpmMode;
+
+    let wpm_for = |mode| {
+        let mut metrics = TypingMetrics::new();
+        metrics.wpm_mode = mode;
+        for (i, c) in "the quick brown fox".chars().enumerate() {
+            metrics.record_keystroke(c, c, i);
+        }
+        // Exactly one minute of typing
+        metrics.current_time = metrics.start_time + Duration::from_secs(60);
+        metrics.calculate_overall_metrics();
+        metrics.wpm
+    };
+
+    // 19 correct characters at 5 per word vs 3 completed (space-terminated) words
+    assert!((wpm_for(WpmMode::CharsPerWord(5)) - 3.8).abs() < 1e-9);
+    assert!((wpm_for(WpmMode::ActualWords) - 3.0).abs() < 1e-9);
+    assert!((wpm_for(WpmMode::CharsPerWord(4)) - 4.75).abs() < 1e-9);
+}
+
+#[test]
+fn test_rhythm_consistency_separates_steady_from_bursty() {
+    let mut steady = TypingMetrics::new();
+    assert_eq!(steady.rhythm_consistency(), 0.0);
+    steady.key_timings.insert('a', vec![150.0, 150.0, 151.0]);
+    steady.key_timings.insert('s', vec![149.0, 150.0]);
+    assert!(steady.rhythm_consistency() < 0.01);
+
+    let mut bursty = TypingMetrics::new();
+    bursty.key_timings.insert('a', vec![40.0, 900.0, 50.0]);
+    bursty.key_timings.insert('s', vec![1200.0, 30.0]);
+    assert!(bursty.rhythm_consistency() > 0.8);
+}
+
+#[test]
+fn test_row_summary_only_counts_rows_that_were_typed() {
+    use spring_keys::core::metrics::KeyboardRow;
+
+    let mut metrics = TypingMetrics::new();
+    for (i, c) in "asdf jkl;".chars().enumerate() {
+        metrics.last_keystroke_time = Some(Instant::now() - Duration::from_millis(200));
+        metrics.record_keystroke(c, c, i);
+    }
+    // One miss on the home row
+    metrics.record_keystroke('q', 'a', 9);
+
+    let summary = metrics.row_summary();
+    assert_eq!(summary.keys().collect::<Vec<_>>(), vec![&KeyboardRow::Home]);
+    let (wpm, accuracy) = summary[&KeyboardRow::Home];
+    assert!((accuracy - 800.0 / 9.0).abs() < 1e-9);
+    // 200ms a key is 300 keys a minute, or 60 WPM
+    assert!((wpm - 60.0).abs() < 5.0);
+
+    for row in [KeyboardRow::Number, KeyboardRow::Top, KeyboardRow::Bottom] {
+        assert_eq!(metrics.row_metrics(row).count, 0, "{} row should be untouched", row);
+    }
+}
+
+#[test]
+fn test_untimed_keystrokes_do_not_skew_row_speed() {
+    use spring_keys::core::metrics::KeyboardRow;
+
+    // The first key of a quote has no interval to time
+    let mut metrics = TypingMetrics::new();
+    metrics.record_keystroke('a', 'a', 0);
+    metrics.last_keystroke_time = Some(Instant::now() - Duration::from_millis(200));
+    metrics.record_keystroke('s', 's', 1);
+
+    let home = metrics.row_metrics(KeyboardRow::Home);
+    assert_eq!(home.count, 1);
+    assert!((home.avg_time_ms - 200.0).abs() < 20.0, "got {}ms", home.avg_time_ms);
+}
+
+#[test]
+fn test_demo_data_is_varied_and_deterministic() {
+    let mut metrics = TypingMetrics::new();
+    metrics.simulate_demo_data();
+    let averages = metrics.get_heat_map();
+
+    let min = averages.values().copied().fold(f64::INFINITY, f64::min);
+    let max = averages.values().copied().fold(0.0, f64::max);
+    assert!(min < max, "demo averages should span a range, got {}..{}", min, max);
+    // Home row index keys beat the pinky number keys
+    assert!(averages[&'f'] < averages[&'1']);
+
+    let mut again = TypingMetrics::new();
+    again.simulate_demo_data();
+    assert_eq!(again.get_heat_map(), averages);
+}
+
+#[test]
+fn test_peak_wpm_tracks_the_fastest_steady_sample() {
+    let mut metrics = TypingMetrics::new();
+    let start = metrics.start_time;
+
+    // (correct keys so far, seconds elapsed): the first sample is fast but too early to count
+    let samples = [(5, 1), (10, 6), (20, 8), (30, 15)];
+    for (keys, secs) in samples {
+        metrics.keystrokes = keys;
+        metrics.correct_keystrokes = keys;
+        metrics.current_time = start + Duration::from_secs(secs);
+        metrics.calculate_overall_metrics();
+    }
+
+    // 20 keys in 8s is 30 WPM, ahead of 10 keys in 6s (20 WPM) and 30 keys in 15s (24 WPM)
+    assert!((metrics.peak_wpm - 30.0).abs() < 1e-9, "peak was {}", metrics.peak_wpm);
+    assert!((metrics.peak_wpm_at - 8.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_extended_stats_windows_use_sample_times() {
+    let mut stats = ExtendedStats::new();
+    let start = Instant::now();
+    let at = |secs| start + Duration::from_secs(secs);
+
+    stats.update(100.0, at(0));
+    stats.update(200.0, at(30));
+    stats.update(300.0, at(55));
+    // Only the newest sample is within 10 seconds; all three are within a minute
+    assert_eq!(stats.avg_10s, 300.0);
+    assert_eq!(stats.avg_60s, 200.0);
+
+    stats.update(400.0, at(75));
+    assert_eq!(stats.avg_10s, 400.0);
+    assert_eq!(stats.avg_60s, 300.0);
+    // The all-time mean keeps every sample
+    assert_eq!(stats.mean, 250.0);
+    assert_eq!((stats.fastest, stats.slowest), (100.0, 400.0));
+}
+
+#[test]
+fn test_mistake_report_gives_word_and_context() {
+    let mut metrics = TypingMetrics::new();
+    metrics.quote_text = "the quick brown fox".to_string();
+    metrics.record_keystroke('x', 'u', 5);
+    metrics.record_keystroke('v', 'f', 16);
+
+    let report = metrics.mistake_report();
+    assert_eq!(report.len(), 2);
+    assert_eq!((report[0].expected, report[0].actual, report[0].position), ('u', 'x', 5));
+    assert_eq!(report[0].expected_word, "quick");
+    assert_eq!(report[0].context, "the quick brown ");
+    assert_eq!((report[1].expected, report[1].actual, report[1].position), ('f', 'v', 16));
+    assert_eq!(report[1].expected_word, "fox");
+    assert_eq!(report[1].context, "ick brown fox");
+}
+
+#[test]
+fn test_snapshot_round_trips_through_json() {
+    let mut session = spring_keys::TypingSession::new("abcd".to_string());
+    for c in ['a', 'b', 'x'] {
+        session.record_keystroke(c);
+    }
+    // Fixed rates, since the ones measured over a few microseconds are meaningless
+    session.metrics.wpm = 72.5;
+    session.metrics.accuracy = 66.25;
+    session.metrics.peak_wpm = 80.0;
+
+    let snapshot = session.snapshot();
+    assert_eq!(snapshot.keystrokes, 3);
+    assert_eq!(snapshot.errors, 1);
+    assert_eq!(snapshot.progress, 0.5);
+
+    let json = serde_json::to_string(&snapshot).expect("Snapshot should serialize");
+    let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed["errors"], 1);
+    assert_eq!(parsed["wpm"], 72.5);
+    let restored: spring_keys::MetricsSnapshot = serde_json::from_str(&json).expect("Snapshot should deserialize");
+    assert_eq!(restored, snapshot);
+}
@@ -0,0 +1,9 @@
+use spring_keys::moosesay::{animate_moose_quote, DEFAULT_FPS};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_zero_duration_exits_immediately() {
+    let start = Instant::now();
+    animate_moose_quote(Some(0), DEFAULT_FPS, true, false).unwrap();
+    assert!(start.elapsed() < Duration::from_millis(100));
+}
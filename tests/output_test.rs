@@ -0,0 +1,67 @@
+use spring_keys::output::render;
+use spring_keys::{Quote, QuoteDifficulty};
+
+fn sample_quote() -> Quote {
+    Quote {
+        text: "Talk is cheap. Show me the code.".to_string(),
+        source: "Linus Torvalds".to_string(),
+        difficulty: QuoteDifficulty::Easy,
+        category: "Programming".to_string(),
+        origin: String::new(),
+    }
+}
+
+#[test]
+fn test_json_output_parses_back() {
+    let json = render(&sample_quote(), true);
+    let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed["text"], "Talk is cheap. Show me the code.");
+    assert_eq!(parsed["source"], "Linus Torvalds");
+    assert_eq!(parsed["category"], "Programming");
+
+    let quote: Quote = serde_json::from_str(&json).unwrap();
+    assert_eq!(quote.source, "Linus Torvalds");
+}
+
+#[test]
+fn test_table_output_aligns_fields() {
+    let table = render(&sample_quote(), false);
+    assert!(table.lines().any(|line| line == "source      Linus Torvalds"));
+    assert!(table.lines().any(|line| line == "difficulty  Easy"));
+    assert!(serde_json::from_str::<serde_json::Value>(&table).is_err());
+}
+
+#[test]
+fn test_quote_find_json_is_valid_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let quotes = dir.path().join("quotes/categories");
+    std::fs::create_dir_all(&quotes).unwrap();
+    std::fs::write(quotes.join("programming.json"), serde_json::to_string(&vec![sample_quote()]).unwrap()).unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_spring-keys"))
+        .args(["quote", "find", "code", "--json"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).expect("stdout should be a single JSON document");
+    assert_eq!(parsed[0]["source"], "Linus Torvalds");
+    assert_eq!(parsed.as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_single_mode_reports_the_completed_quotes_wpm() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_spring-keys"))
+        .args(["single", "--text", "Hi you", "--input", "H i <space> y o u"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout.lines().find(|line| line.starts_with("Completed!")).expect("a completion line");
+    let wpm: f64 = line.trim_start_matches("Completed! WPM: ").split(' ').next().unwrap().parse().unwrap();
+    // Six keystrokes at the simulated pace, not the instant the replay actually took
+    assert!(wpm.is_finite() && wpm > 0.0 && wpm < 200.0, "{}", line);
+    assert!(line.ends_with("Accuracy: 100.0%"), "{}", line);
+}
@@ -0,0 +1,11 @@
+use spring_keys::presets::Preset;
+
+#[test]
+fn test_every_preset_name_maps_to_text() {
+    for preset in Preset::ALL {
+        assert_eq!(Preset::from_name(preset.name()), Some(preset));
+        assert!(!preset.text().is_empty(), "{} has no text", preset.name());
+    }
+    assert_eq!(Preset::from_name("FoxJump"), Some(Preset::FoxJump));
+    assert_eq!(Preset::from_name("nonsense"), None);
+}
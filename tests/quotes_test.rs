@@ -0,0 +1,494 @@
+use spring_keys::{AccumulatedStats, CategoryCycle, CategoryGroup, DifficultyLevel, KeyboardLayout, Quote, QuoteDatabase, QuoteDifficulty, SpringKeys};
+use spring_keys::quotes::{add_quote_to_directory, category_file_name, default_quotes, quote_hash};
+use std::collections::HashSet;
+
+#[test]
+fn test_empty_database_falls_back_to_default_quotes() {
+    let mut db = QuoteDatabase::from_quotes(Vec::new());
+    assert_eq!(db.total_quotes(), default_quotes().len());
+
+    // Selection must not panic on what would otherwise be an empty database
+    let quote = db.next_random();
+    assert!(default_quotes().iter().any(|q| q.text == quote.text));
+    assert!(db.next_by_difficulty(QuoteDifficulty::Easy).is_some());
+}
+
+#[test]
+fn test_missing_quote_directory_falls_back_to_default_quotes() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let mut db = QuoteDatabase::from_directory(dir.path().join("missing"), true);
+    assert_eq!(db.total_quotes(), default_quotes().len());
+    assert!(!db.next_random().text.is_empty());
+}
+
+#[test]
+fn test_quote_files_load_in_name_order() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    for name in ["b", "c", "a"] {
+        let quote = format!(r#"[{{"text": "From {}", "source": "Test", "difficulty": "Easy"}}]"#, name);
+        std::fs::write(dir.path().join(format!("{}.json", name)), quote).unwrap();
+    }
+    let db = QuoteDatabase::from_directory(dir.path(), true);
+    let texts: Vec<&str> = (0..3).map(|i| db.quote_at(i).unwrap().text.as_str()).collect();
+    assert_eq!(texts, ["From a", "From b", "From c"]);
+}
+
+#[test]
+fn test_quote_deserializes_legacy_format() {
+    let json = r#"{"text": "Hello world", "source": "Test", "difficulty": "Easy"}"#;
+    let quote: Quote = serde_json::from_str(json).expect("legacy quote should parse");
+    assert_eq!(quote.text, "Hello world");
+    assert_eq!(quote.difficulty, QuoteDifficulty::Easy);
+    assert!(quote.category.is_empty());
+    assert!(quote.origin.is_empty());
+}
+
+#[test]
+fn test_quote_deserializes_category_format() {
+    let json = r#"{"text": "fn main() {}", "source": "Rust", "difficulty": "Medium",
+                   "category": "Programming", "origin": "American"}"#;
+    let quote: Quote = serde_json::from_str(json).expect("categorised quote should parse");
+    assert_eq!(quote.category, "Programming");
+    assert_eq!(quote.origin, "American");
+}
+
+fn categorised_quote(text: &str, category: &str) -> Quote {
+    Quote {
+        text: text.to_string(),
+        source: "Test".to_string(),
+        difficulty: QuoteDifficulty::Easy,
+        category: category.to_string(),
+        origin: String::new(),
+    }
+}
+
+#[test]
+fn test_category_names_parse() {
+    assert_eq!(CategoryCycle::from_name("Programming"), Some(CategoryCycle::Programming));
+    assert_eq!(CategoryCycle::from_name("typewriter"), Some(CategoryCycle::Typewriter));
+    assert_eq!(CategoryCycle::from_name("poetry"), None);
+    assert!(CategoryCycle::valid_names().contains("literature"));
+}
+
+#[test]
+fn test_next_by_category_restricts_pool() {
+    let mut db = QuoteDatabase::from_quotes(vec![
+        categorised_quote("fn main() {}", "Programming"),
+        categorised_quote("Call me Ishmael.", "Literature"),
+        categorised_quote("The QWERTY layout dates to 1873.", "Typewriters"),
+    ]);
+
+    for _ in 0..10 {
+        assert_eq!(db.next_by_category(CategoryCycle::Programming).unwrap().text, "fn main() {}");
+        assert_eq!(db.next_by_category(CategoryCycle::Typewriter).unwrap().category, "Typewriters");
+    }
+
+    db.set_active_category(CategoryCycle::Literature);
+    assert_eq!(db.next_random().text, "Call me Ishmael.");
+}
+
+fn quote_from(text: &str, origin: &str) -> Quote {
+    Quote {
+        origin: origin.to_string(),
+        ..categorised_quote(text, "Wisdom")
+    }
+}
+
+#[test]
+fn test_origin_filter_restricts_selection() {
+    let mut db = QuoteDatabase::from_quotes(vec![
+        quote_from("Hello there.", "English"),
+        quote_from("Konnichiwa.", "Japanese"),
+        quote_from("Aloha.", "Hawaii"),
+    ]);
+
+    assert_eq!(db.next_by_origin("english").unwrap().text, "Hello there.");
+    assert!(db.next_by_origin("Klingon").is_none());
+
+    db.set_active_origin(Some("JAPANESE".to_string()));
+    for _ in 0..10 {
+        assert_eq!(db.next_random().text, "Konnichiwa.");
+        assert_eq!(db.next_by_difficulty(QuoteDifficulty::Easy).unwrap().origin, "Japanese");
+    }
+}
+
+#[test]
+fn test_search_matches_text_and_source_ignoring_case() {
+    let db = QuoteDatabase::from_quotes(default_quotes());
+
+    let by_text = db.search("QUARTZ");
+    assert_eq!(by_text.len(), 1);
+    assert!(by_text[0].text.contains("quartz"));
+
+    let by_source = db.search("dijkstra");
+    assert_eq!(by_source.len(), 1);
+    assert_eq!(by_source[0].source, "Edsger W. Dijkstra");
+
+    assert!(db.search("no such passage").is_empty());
+}
+
+#[test]
+fn test_same_seed_gives_same_quote_order() {
+    let texts = |seed| {
+        let mut db = QuoteDatabase::from_quotes(default_quotes());
+        db.set_seed(seed);
+        (0..5).map(|_| db.next_random().text).collect::<Vec<_>>()
+    };
+
+    assert_eq!(texts(42), texts(42));
+}
+
+#[test]
+fn test_indexed_selection_only_returns_matching_quotes() {
+    let mut quotes = default_quotes();
+    quotes[1].category = "Programming".to_string();
+    let mut db = QuoteDatabase::from_quotes(quotes);
+
+    for _ in 0..50 {
+        assert_eq!(db.next_by_difficulty(QuoteDifficulty::Hard).unwrap().difficulty, QuoteDifficulty::Hard);
+        assert_eq!(db.next_by_category(CategoryCycle::Programming).unwrap().text, "Practice makes perfect.");
+    }
+    assert!(db.next_by_category(CategoryCycle::Literature).is_none());
+}
+
+#[test]
+fn test_category_listing_counts_and_groups_loaded_categories() {
+    // Every shipped file, locked categories included
+    let db = QuoteDatabase::from_directory(spring_keys::quotes::QUOTES_DIR, true);
+    let listing = db.category_listing();
+
+    assert!(listing.iter().any(|line| line.starts_with("Programming (")));
+    let sacred = listing.iter().position(|line| line.starts_with("SacredTexts (")).unwrap();
+    // Subcategories follow their group, indented
+    assert!(listing[sacred + 1].starts_with("  SacredTexts"));
+    assert!(!listing.iter().any(|line| line.starts_with("SacredTextsQuran")));
+}
+
+#[test]
+fn test_category_group_count_includes_its_subcategories() {
+    let db = QuoteDatabase::from_quotes(vec![
+        categorised_quote("In the beginning.", "SacredTexts"),
+        categorised_quote("Read in the name.", "SacredTextsQuran"),
+        categorised_quote("Read again.", "SacredTextsQuran"),
+        categorised_quote("The way that can be told.", "SacredTextsTao"),
+        categorised_quote("Let freedom ring.", "HolidayMlkDay"),
+    ]);
+    assert_eq!(
+        db.category_listing(),
+        vec!["Holiday (1)", "  HolidayMlkDay (1)", "SacredTexts (4)", "  SacredTextsQuran (2)", "  SacredTextsTao (1)"]
+    );
+}
+
+#[test]
+fn test_category_groups_partition_loaded_categories() {
+    let db = QuoteDatabase::new_silent();
+    let mut grouped: Vec<String> = CategoryGroup::ALL.iter().flat_map(|group| db.categories_in_group(*group)).collect();
+    let total = grouped.len();
+    grouped.sort();
+    grouped.dedup();
+    // Each category lands in exactly one group
+    assert_eq!(grouped.len(), total);
+
+    let loaded: Vec<String> = db.category_counts().into_keys().filter(|name| !name.starts_with('(')).collect();
+    assert_eq!(grouped, loaded);
+
+    assert_eq!(CategoryGroup::for_category("SacredTextsQuran"), CategoryGroup::Scripture);
+    assert_eq!(CategoryGroup::for_category("Holiday"), CategoryGroup::Holiday);
+    assert_eq!(CategoryGroup::for_category("LessonsHomeRow"), CategoryGroup::Lessons);
+    assert_eq!(CategoryGroup::for_category("Programming"), CategoryGroup::Technical);
+    assert_eq!(CategoryGroup::for_category("Folk Humor"), CategoryGroup::Entertainment);
+    assert_eq!(CategoryGroup::for_category("Stoic Philosophy"), CategoryGroup::Wisdom);
+}
+
+#[test]
+fn test_next_paragraph_joins_count_quotes() {
+    let quotes = ["first quote", "second quote", "third quote"]
+        .iter()
+        .map(|text| Quote {
+            text: text.to_string(),
+            source: "Test".to_string(),
+            difficulty: QuoteDifficulty::Easy,
+            category: String::new(),
+            origin: String::new(),
+        })
+        .collect();
+    let mut db = QuoteDatabase::from_quotes(quotes);
+
+    let paragraph = db.next_paragraph(4, "\n");
+    let parts: Vec<&str> = paragraph.split('\n').collect();
+    assert_eq!(parts.len(), 4);
+    assert!(parts.iter().all(|part| part.ends_with(" quote")));
+    assert_eq!(db.next_paragraph(0, " "), "");
+}
+
+#[test]
+fn test_paragraph_quotes_follow_the_difficulty_and_origin_filters() {
+    let quote = |text: &str, difficulty, origin: &str| Quote {
+        text: text.to_string(),
+        source: "Test".to_string(),
+        difficulty,
+        category: String::new(),
+        origin: origin.to_string(),
+    };
+    let mut app = SpringKeys::new_silent();
+    app.quote_db = QuoteDatabase::from_quotes(vec![
+        quote("easy", QuoteDifficulty::Easy, "English"),
+        quote("hard", QuoteDifficulty::Hard, "English"),
+        quote("hard-latin", QuoteDifficulty::Hard, "Latin"),
+    ]);
+    app.config.preferences.difficulty = DifficultyLevel::Advanced;
+    app.quote_db.set_active_origin(Some("English".to_string()));
+    app.paragraph_length = Some(6);
+
+    app.start_typing_session(None);
+    let paragraph = &app.typing_session.as_ref().unwrap().quote_text;
+    assert_eq!(paragraph.split(' ').count(), 6);
+    assert!(paragraph.split(' ').all(|part| part == "hard"), "{}", paragraph);
+}
+
+#[test]
+fn test_classify_grades_custom_text() {
+    assert_eq!(QuoteDifficulty::classify("the cat sat on the mat"), QuoteDifficulty::Easy);
+    assert_eq!(QuoteDifficulty::classify(""), QuoteDifficulty::Easy);
+    let stock_report = "The $SPY index gained two point five percent (2.5%). NASDAQ fell $3.75 to close at $14,250.50 per share.";
+    assert_eq!(QuoteDifficulty::classify(stock_report), QuoteDifficulty::Hard);
+}
+
+#[test]
+fn test_selecting_empty_category_keeps_current_quote() {
+    let mut app = SpringKeys::new_silent();
+    app.quote_db = QuoteDatabase::from_quotes(vec![Quote {
+        text: "fn main() {}".to_string(),
+        source: "Rust".to_string(),
+        difficulty: QuoteDifficulty::Easy,
+        category: "Programming".to_string(),
+        origin: String::new(),
+    }]);
+    assert!(app.select_category(CategoryCycle::Programming));
+    assert_eq!(app.typing_session.as_ref().unwrap().quote_text, "fn main() {}");
+    assert!(app.notice().is_none());
+
+    assert!(!app.select_category(CategoryCycle::Literature));
+    assert_eq!(app.quote_db.get_active_category(), CategoryCycle::Programming);
+    assert_eq!(app.typing_session.as_ref().unwrap().quote_text, "fn main() {}");
+    assert_eq!(app.notice(), Some("No quotes in literature category"));
+}
+
+#[test]
+fn test_custom_quote_gets_a_classified_difficulty() {
+    let mut app = SpringKeys::new();
+    app.start_typing_session(Some("Q1 EPS: $1.73 (est. $1.65) & +12% YoY".to_string()));
+    assert_eq!(app.quote_difficulty, QuoteDifficulty::Hard);
+}
+
+#[test]
+fn test_added_quote_round_trips_through_database_load() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let quote = |text: &str, category: &str| Quote {
+        text: text.to_string(),
+        source: "Tester".to_string(),
+        difficulty: QuoteDifficulty::Medium,
+        category: category.to_string(),
+        origin: String::new(),
+    };
+
+    let path = add_quote_to_directory(dir.path(), &quote("Ship it when it is ready.", "Programming")).unwrap();
+    assert_eq!(path, dir.path().join("programming.json"));
+    // A second quote of the same category goes to the same file
+    assert_eq!(add_quote_to_directory(dir.path(), &quote("Then ship it again.", "programming")).unwrap(), path);
+
+    let db = QuoteDatabase::from_directory(dir.path(), true);
+    assert_eq!(db.total_quotes(), 2);
+    let found = db.search("ship it when");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].source, "Tester");
+    assert_eq!(found[0].difficulty, QuoteDifficulty::Medium);
+    assert_eq!(found[0].category, "Programming");
+
+    // Invalid categories and empty text are refused without writing anything
+    assert!(add_quote_to_directory(dir.path(), &quote("Nope", "../etc")).is_err());
+    assert!(add_quote_to_directory(dir.path(), &quote("  ", "Programming")).is_err());
+    assert_eq!(QuoteDatabase::from_directory(dir.path(), true).total_quotes(), 2);
+}
+
+#[test]
+fn test_added_quote_goes_to_its_categorys_own_file() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let quote = |text: &str| Quote {
+        text: text.to_string(),
+        source: "Tester".to_string(),
+        difficulty: QuoteDifficulty::Hard,
+        category: "HardStockReports".to_string(),
+        origin: String::new(),
+    };
+    assert_eq!(category_file_name("HardStockReports"), "hard_stock_reports.json");
+    assert_eq!(category_file_name("Programming"), "programming.json");
+
+    // Of two files already holding the category, the first by name is used
+    let existing = serde_json::to_string(&vec![quote("Revenue rose 4%.")]).unwrap();
+    std::fs::write(dir.path().join("z-reports.json"), &existing).unwrap();
+    std::fs::write(dir.path().join("m-reports.json"), &existing).unwrap();
+    assert_eq!(add_quote_to_directory(dir.path(), &quote("Margins fell.")).unwrap(), dir.path().join("m-reports.json"));
+
+    // Once the category's own file exists it wins
+    std::fs::write(dir.path().join("hard_stock_reports.json"), &existing).unwrap();
+    assert_eq!(add_quote_to_directory(dir.path(), &quote("Guidance held.")).unwrap(), dir.path().join("hard_stock_reports.json"));
+
+    let empty = tempfile::tempdir().expect("Failed to create temp dir");
+    let created = add_quote_to_directory(empty.path().join("categories"), &quote("Shares split 2:1.")).unwrap();
+    assert_eq!(created, empty.path().join("categories/hard_stock_reports.json"));
+}
+
+#[test]
+fn test_kanji_quote_is_not_typeable_on_qwerty() {
+    let quote = |text: &str| Quote {
+        text: text.to_string(),
+        source: "Proverb".to_string(),
+        difficulty: QuoteDifficulty::Easy,
+        category: "JapaneseWisdom".to_string(),
+        origin: "Japan".to_string(),
+    };
+    let kanji = quote("Fall seven times, stand up eight (七転び八起き).");
+    let plain = quote("Fall seven times, stand up eight!");
+    assert_eq!(kanji.char_count(), 42);
+    assert!(!kanji.is_typeable(KeyboardLayout::Qwerty));
+    assert!(plain.is_typeable(KeyboardLayout::Qwerty));
+
+    let mut db = QuoteDatabase::from_quotes(vec![kanji, plain]);
+    assert_eq!(db.typeable_only(KeyboardLayout::Qwerty), 1);
+    assert_eq!(db.total_quotes(), 1);
+    assert_eq!(db.next_by_difficulty(QuoteDifficulty::Easy).unwrap().text, "Fall seven times, stand up eight!");
+}
+
+#[test]
+fn test_next_unseen_skips_completed_quotes() {
+    let quotes = ["seen it", "not yet"]
+        .iter()
+        .map(|text| Quote {
+            text: text.to_string(),
+            source: "Test".to_string(),
+            difficulty: QuoteDifficulty::Easy,
+            category: String::new(),
+            origin: String::new(),
+        })
+        .collect();
+    let mut db = QuoteDatabase::from_quotes(quotes);
+
+    let mut seen = HashSet::from([quote_hash("seen it")]);
+    for _ in 0..10 {
+        assert_eq!(db.next_unseen(&seen).unwrap().text, "not yet");
+    }
+    seen.insert(quote_hash("not yet"));
+    assert!(db.next_unseen(&seen).is_none());
+}
+
+#[test]
+fn test_completed_quote_is_not_served_again_with_prefer_unseen() {
+    let mut app = SpringKeys::new_silent();
+    app.quote_db = QuoteDatabase::from_quotes(default_quotes());
+    app.accumulated_stats = AccumulatedStats::new();
+    app.prefer_unseen = true;
+    let total = app.quote_db.total_quotes();
+
+    // Each completed quote loads the next one
+    app.start_typing_session(None);
+    let mut typed = HashSet::new();
+    for _ in 0..total {
+        let text = app.typing_session.as_ref().unwrap().quote_text.clone();
+        assert!(typed.insert(text.clone()), "{:?} was served twice", text);
+        assert_eq!(app.feed_str(&text), 1);
+    }
+}
+
+#[test]
+fn test_hard_categories_stay_locked_until_earned() {
+    use spring_keys::quotes::{is_category_unlocked, LOCKED_CATEGORIES};
+
+    let quote = |text: &str, category: &str| Quote {
+        text: text.to_string(),
+        source: "Test".to_string(),
+        difficulty: QuoteDifficulty::Hard,
+        category: category.to_string(),
+        origin: String::new(),
+    };
+    let quotes = vec![quote("EPS was $1.73.", "HardStockReports"), quote("Keep typing.", "Lessons")];
+    let hard_quote_served = |db: &mut QuoteDatabase| (0..50).any(|_| db.next_random().category == "HardStockReports");
+
+    // A fresh user never gets a stock report
+    let fresh = AccumulatedStats::new();
+    assert!(!is_category_unlocked("HardStockReports", &fresh));
+    assert!(!is_category_unlocked("SacredTextsQuran", &fresh));
+    assert!(is_category_unlocked("Lessons", &fresh));
+    let mut db = QuoteDatabase::from_quotes(quotes.clone());
+    assert_eq!(db.lock_categories(&fresh, &[]), 1);
+    assert!(!hard_quote_served(&mut db));
+
+    // Sustained accuracy over enough quotes unlocks it
+    let mut seasoned = AccumulatedStats::new();
+    seasoned.total_quotes = 100;
+    seasoned.avg_accuracy = 98.0;
+    assert!(is_category_unlocked("HardStockReports", &seasoned));
+    let mut db = QuoteDatabase::from_quotes(quotes.clone());
+    assert_eq!(db.lock_categories(&seasoned, &[]), 0);
+    assert!(hard_quote_served(&mut db));
+
+    // Accuracy alone isn't enough without the quotes behind it
+    seasoned.total_quotes = 10;
+    assert!(!is_category_unlocked("HardStockReports", &seasoned));
+
+    // An unlock remembered in the config sticks, and a held-back category can be released later
+    let mut db = QuoteDatabase::from_quotes(quotes.clone());
+    assert_eq!(db.lock_categories(&fresh, &["hardstockreports".to_string()]), 0);
+    let mut db = QuoteDatabase::from_quotes(quotes);
+    db.lock_categories(&fresh, &[]);
+    let stock = LOCKED_CATEGORIES.iter().find(|unlock| unlock.category == "HardStockReports").unwrap();
+    assert_eq!(db.unlock_category(stock), 1);
+    assert!(hard_quote_served(&mut db));
+}
+
+#[test]
+fn test_shipped_quotes_of_locked_categories_are_held_back() {
+    use spring_keys::quotes::{locked_category, QUOTES_DIR};
+
+    // Every stock report that ships is covered by the HardStockReports lock
+    let content = std::fs::read_to_string(std::path::Path::new(QUOTES_DIR).join("hard-stock-reports.json")).unwrap();
+    let reports: Vec<Quote> = serde_json::from_str(&content).unwrap();
+    assert!(!reports.is_empty());
+    assert!(reports.iter().all(|q| locked_category(&q.category).is_some_and(|unlock| unlock.category == "HardStockReports")));
+
+    let mut db = QuoteDatabase::from_directory(QUOTES_DIR, true);
+    let total = db.total_quotes();
+    let held = db.lock_categories(&AccumulatedStats::new(), &[]);
+    assert!(held >= reports.len());
+    assert!((0..200).all(|_| locked_category(&db.next_random().category).is_none()));
+
+    // --unlock-all brings every one of them back
+    assert_eq!(db.unlock_all(), held);
+    assert_eq!(db.total_quotes(), total);
+}
+
+#[test]
+fn test_locked_categories_are_explained_and_skipped() {
+    use spring_keys::quotes::locked_category;
+
+    let unlock = locked_category("SacredTexts").expect("SacredTexts starts locked");
+    assert_eq!(unlock.category, "SacredTexts");
+    assert_eq!(unlock.requirement(), "complete 25 quotes at 95% average accuracy");
+    assert!(locked_category("Programming").is_none());
+
+    // A category whose quotes are all held back is refused like an empty one
+    let quote = |text: &str, category: &str| Quote {
+        text: text.to_string(),
+        source: "Test".to_string(),
+        difficulty: QuoteDifficulty::Easy,
+        category: category.to_string(),
+        origin: String::new(),
+    };
+    let mut app = SpringKeys::new_silent();
+    app.quote_db = QuoteDatabase::from_quotes(vec![quote("locked away", "HardStockReports Literature"), quote("fn main", "Programming")]);
+    app.quote_db.lock_categories(&AccumulatedStats::new(), &[]);
+    assert_eq!(app.quote_db.locked_in_category(CategoryCycle::Literature), 1);
+    assert!(!app.select_category(CategoryCycle::Literature));
+    assert_eq!(app.notice(), Some("literature quotes are locked"));
+    assert!(app.select_category(CategoryCycle::Programming));
+}
@@ -0,0 +1,89 @@
+use spring_keys::ui::results::{render_results_card, ResultsPause, MIN_CARD_WIDTH};
+use spring_keys::{KeyCode, KeyModifiers, SpringKeys, TypingMetrics};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_results_card_shows_wpm_in_a_box() {
+    let mut metrics = TypingMetrics::new();
+    metrics.wpm = 87.34;
+    metrics.accuracy = 96.0;
+    metrics.key_timings.insert('q', vec![400.0]);
+    metrics.key_timings.insert('a', vec![100.0]);
+
+    let card = render_results_card(&metrics, 50);
+    assert!(card.iter().any(|line| line.contains("87.3")));
+    assert!(card.iter().any(|line| line.contains("Slowest keys q 400ms, a 100ms")));
+    assert!(card.first().unwrap().starts_with('╭'));
+    assert!(card.last().unwrap().starts_with('╰'));
+    assert!(card.iter().all(|line| line.chars().count() == 50));
+
+    // Too narrow a width is widened to fit
+    let narrow = render_results_card(&metrics, 5);
+    assert!(narrow.iter().all(|line| line.chars().count() == MIN_CARD_WIDTH as usize));
+}
+
+#[test]
+fn test_results_card_waits_for_dismissal() {
+    let mut app = SpringKeys::new();
+    app.show_results_card = true;
+    app.start_typing_session(Some("ok".to_string()));
+    app.feed_str("ok");
+    assert!(app.pending_results.is_some());
+
+    app.dismiss_results();
+    assert!(app.pending_results.is_none());
+}
+
+#[test]
+fn test_results_card_reviews_mistakes() {
+    let mut app = SpringKeys::new();
+    app.show_results_card = true;
+    app.start_typing_session(Some("the cat".to_string()));
+    app.feed_str("thx");
+    app.process_input(KeyCode::Backspace, KeyModifiers::NONE);
+    app.feed_str("e cat");
+
+    let card = render_results_card(app.pending_results.as_ref().unwrap(), 50);
+    assert!(card.iter().any(|line| line.contains("typed 'x' for 'e' in \"the\"")));
+}
+
+#[test]
+fn test_results_card_covers_only_the_completed_quote() {
+    let mut app = SpringKeys::new();
+    app.show_results_card = true;
+    app.start_typing_session(Some("first".to_string()));
+    app.feed_str("fxirst");
+    app.dismiss_results();
+
+    app.start_typing_session(Some("ok".to_string()));
+    app.feed_str("ok");
+    let results = app.pending_results.as_ref().unwrap();
+    assert_eq!(results.keystrokes, 2);
+    assert_eq!(results.accuracy, 100.0);
+    assert!(results.errors.is_empty());
+    assert!(results.get_heat_map().keys().all(|key| "ok".contains(*key)));
+    let session = app.typing_session.as_ref().unwrap();
+    assert!(results.start_time > session.metrics.start_time, "Time is counted from this quote's start");
+}
+
+#[test]
+fn test_results_pause_advances_once_elapsed() {
+    let pause = Duration::from_millis(1500);
+    let shown = Instant::now();
+    let mut timer = ResultsPause::default();
+
+    assert!(!timer.tick(true, pause, shown));
+    assert!(!timer.tick(true, pause, shown + Duration::from_millis(1499)));
+    assert!(timer.tick(true, pause, shown + pause));
+
+    // The next card starts its own pause
+    let next = shown + Duration::from_secs(10);
+    assert!(!timer.tick(false, pause, next));
+    assert!(!timer.tick(true, pause, next));
+    assert!(timer.tick(true, pause, next + pause));
+
+    // Without a pause the card waits for a key
+    let mut timer = ResultsPause::default();
+    assert!(!timer.tick(true, Duration::ZERO, shown));
+    assert!(!timer.tick(true, Duration::ZERO, shown + Duration::from_secs(60)));
+}
@@ -0,0 +1,22 @@
+use rand::Rng;
+use spring_keys::rng::{self, SEED_ENV_VAR};
+use spring_keys::QuoteDatabase;
+
+// One test, since the seed env var and the shared generator are process-wide
+#[test]
+fn test_same_seed_replays_quotes_and_shared_rng() {
+    std::env::set_var(SEED_ENV_VAR, "1234");
+    assert_eq!(rng::seed_from_env(), Some(1234));
+
+    let first_quotes = |db: &mut QuoteDatabase| (0..3).map(|_| db.next_random().text).collect::<Vec<_>>();
+    assert_eq!(first_quotes(&mut QuoteDatabase::new()), first_quotes(&mut QuoteDatabase::new()));
+
+    let draws = || -> Vec<u32> { (0..8).map(|_| rng::global_rng().gen_range(0..1000)).collect() };
+    rng::reseed_global(1234);
+    let first = draws();
+    rng::reseed_global(1234);
+    assert_eq!(draws(), first);
+
+    std::env::set_var(SEED_ENV_VAR, "not a number");
+    assert_eq!(rng::seed_from_env(), None);
+}
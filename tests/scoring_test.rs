@@ -0,0 +1,120 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use spring_keys::core::scoring::{combo_multiplier, BACKSPACE_PENALTY_POINTS, COMBO_STEP, MAX_COMBO_MULTIPLIER, POINTS_PER_KEY};
+use spring_keys::{AccumulatedStats, SpringKeys, TypingSession};
+
+#[test]
+fn test_combo_resets_on_error() {
+    let mut session = TypingSession::new("abcd".to_string());
+    session.record_keystroke('a');
+    session.record_keystroke('b');
+    assert_eq!(session.combo, 2);
+    assert_eq!(session.score, 2 * POINTS_PER_KEY);
+
+    session.record_keystroke('x');
+    assert_eq!(session.combo, 0);
+    assert_eq!(session.score, 2 * POINTS_PER_KEY, "Errors don't cost points");
+
+    session.record_keystroke('c');
+    assert_eq!(session.combo, 1);
+}
+
+#[test]
+fn test_retyped_text_earns_no_points() {
+    let mut app = SpringKeys::new_silent();
+    app.start_typing_session(Some("abcd".to_string()));
+    app.feed_str("a");
+    let score = app.typing_session.as_ref().unwrap().score;
+    assert_eq!(score, POINTS_PER_KEY);
+
+    // Without the backspace penalty, erasing and retyping a key must not pay twice
+    for _ in 0..5 {
+        app.process_input(KeyCode::Backspace, KeyModifiers::NONE);
+        app.feed_str("a");
+    }
+    assert_eq!(app.typing_session.as_ref().unwrap().score, score);
+
+    // Nor does starting the quote over
+    let session = app.typing_session.as_mut().unwrap();
+    session.reset();
+    session.record_keystroke('a');
+    assert_eq!(session.score, score);
+    session.record_keystroke('b');
+    assert_eq!(session.score, 2 * POINTS_PER_KEY);
+}
+
+#[test]
+fn test_new_quote_starts_without_a_combo() {
+    let mut session = TypingSession::new("abcd".to_string());
+    session.record_keystroke('a');
+    session.record_keystroke('b');
+    session.load_new_quote("efgh".to_string());
+    assert_eq!(session.combo, 0);
+    session.record_keystroke('e');
+    assert_eq!(session.score, POINTS_PER_KEY);
+}
+
+#[test]
+fn test_combo_multiplier_grows_with_streak_and_caps() {
+    assert_eq!(combo_multiplier(0), 1);
+    assert_eq!(combo_multiplier(COMBO_STEP - 1), 1);
+    assert_eq!(combo_multiplier(COMBO_STEP), 2);
+    assert_eq!(combo_multiplier(3 * COMBO_STEP), 4);
+    assert_eq!(combo_multiplier(1000 * COMBO_STEP), MAX_COMBO_MULTIPLIER);
+
+    // A long streak earns more than the same keys typed with a break in the middle
+    let text = "a".repeat(2 * COMBO_STEP);
+    let mut steady = TypingSession::new(text.clone());
+    let mut broken = TypingSession::new(text);
+    for i in 0..2 * COMBO_STEP {
+        steady.record_keystroke('a');
+        if i == COMBO_STEP {
+            broken.record_keystroke('x');
+        }
+        broken.record_keystroke('a');
+    }
+    assert!(steady.score > broken.score);
+}
+
+#[test]
+fn test_high_scores_are_kept_per_length_bucket() {
+    let mut stats = AccumulatedStats::new();
+    assert!(stats.record_score(20, 100));
+    assert!(!stats.record_score(30, 90));
+    assert!(stats.record_score(200, 50));
+    assert_eq!(stats.high_score(10), Some(100));
+    assert_eq!(stats.high_score(300), Some(50));
+    assert_eq!(stats.high_score(100), None);
+}
+
+#[test]
+fn test_backspace_penalty_counts_errors_and_costs_points() {
+    let mut app = SpringKeys::new();
+    app.config.preferences.backspace_penalty = true;
+    app.start_typing_session(Some("abcdef".to_string()));
+    let errors_before = app.accumulated_stats.total_errors;
+
+    app.feed_str("abx");
+    let score_before = app.typing_session.as_ref().unwrap().score;
+    app.backspace();
+    app.process_input(KeyCode::Backspace, KeyModifiers::NONE);
+
+    let session = app.typing_session.as_ref().unwrap();
+    assert_eq!(session.backspace_penalties, 2);
+    assert_eq!(session.score, score_before.saturating_sub(2 * BACKSPACE_PENALTY_POINTS));
+    // One error for the wrong 'x' and one per backspace
+    assert_eq!(app.accumulated_stats.total_errors, errors_before + 3);
+
+    // Backspacing with nothing left to erase isn't penalized
+    app.backspace();
+    app.backspace();
+    assert_eq!(app.typing_session.as_ref().unwrap().backspace_penalties, 3);
+}
+
+#[test]
+fn test_backspace_is_free_without_penalty() {
+    let mut app = SpringKeys::new();
+    app.start_typing_session(Some("abc".to_string()));
+    app.feed_str("ax");
+    app.backspace();
+    assert_eq!(app.typing_session.as_ref().unwrap().backspace_penalties, 0);
+}
@@ -0,0 +1,57 @@
+use spring_keys::core::find_next_sentence_end;
+use spring_keys::{GameType, SpringKeys, TypingSession};
+
+/// The sentences of `text` as sentence mode serves them, skipping the spaces between
+fn sentences(text: &str) -> Vec<String> {
+    let mut session = TypingSession::new(text.to_string());
+    let mut found = Vec::new();
+    while let Some(sentence) = session.current_sentence() {
+        found.push(sentence.to_string());
+        session.advance_sentence(true);
+    }
+    found
+}
+
+#[test]
+fn test_ellipsis_splits_only_before_a_new_sentence() {
+    assert_eq!(sentences("Wait... what was that? Nothing... Go back to sleep."), [
+        "Wait... what was that?",
+        "Nothing...",
+        "Go back to sleep.",
+    ]);
+    assert_eq!(sentences("And then… Silence."), ["And then…", "Silence."]);
+}
+
+#[test]
+fn test_abbreviations_and_initials_do_not_end_sentences() {
+    assert_eq!(sentences("Mr. Smith met Dr. Jones at 5 p.m. sharp. J. R. R. Tolkien wrote it, e.g. The Hobbit."), [
+        "Mr. Smith met Dr. Jones at 5 p.m. sharp.",
+        "J. R. R. Tolkien wrote it, e.g. The Hobbit.",
+    ]);
+}
+
+#[test]
+fn test_quoted_sentences_keep_their_closing_quote() {
+    assert_eq!(sentences("\"Stop!\" she said. \"Go. Now.\" Then he left."), [
+        "\"Stop!\" she said.",
+        "\"Go. Now.\"",
+        "Then he left.",
+    ]);
+    assert_eq!(sentences("\u{201C}Is it done?\u{201D} Yes."), ["\u{201C}Is it done?\u{201D}", "Yes."]);
+    // Char indices, not bytes, so text before a multi-byte quote mark still lines up
+    assert_eq!(find_next_sentence_end("\u{201C}Hi.\u{201D} Bye.", 0), 5);
+}
+
+#[test]
+fn test_sentence_mode_advances_through_the_quote() {
+    let mut app = SpringKeys::new_silent();
+    app.change_game(GameType::SentenceBySentence);
+    app.start_typing_session(Some("One two. Three four.".to_string()));
+
+    assert_eq!(app.feed_str("One two."), 0);
+    let session = app.typing_session.as_ref().unwrap();
+    assert_eq!(session.current_sentence(), Some("Three four."));
+    assert!(app.input_processor.current_text.is_empty());
+
+    assert_eq!(app.feed_str("Three four."), 1);
+}
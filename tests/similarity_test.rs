@@ -0,0 +1,44 @@
+use spring_keys::core::similarity::{group_by_edit_distance, levenshtein, load_word_groups, SIMILARITY_GROUPS};
+use std::fs;
+
+#[test]
+fn test_levenshtein_distances() {
+    assert_eq!(levenshtein("doggy", "doggy"), 0);
+    assert_eq!(levenshtein("doggy", "foggy"), 1);
+    assert_eq!(levenshtein("kitten", "sitting"), 3);
+    assert_eq!(levenshtein("", "abc"), 3);
+}
+
+#[test]
+fn test_grouping_known_words() {
+    let words: Vec<String> = ["doggy", "foggy", "cat", "bat", "elephant", "boggy"]
+        .iter()
+        .map(|w| w.to_string())
+        .collect();
+
+    let mut groups = group_by_edit_distance(&words, 2);
+    for group in &mut groups {
+        group.sort();
+    }
+    groups.sort();
+
+    assert_eq!(groups, vec![
+        vec!["bat".to_string(), "cat".to_string()],
+        vec!["boggy".to_string(), "doggy".to_string(), "foggy".to_string()],
+        vec!["elephant".to_string()],
+    ]);
+}
+
+#[test]
+fn test_word_list_loading_and_fallback() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path = dir.path().join("words.txt");
+    fs::write(&path, "doggy\nfoggy\n\nelephant\n").unwrap();
+
+    let groups = load_word_groups(&path);
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].len(), 2);
+
+    let fallback = load_word_groups(dir.path().join("missing.txt"));
+    assert_eq!(fallback.len(), SIMILARITY_GROUPS.len());
+}
@@ -0,0 +1,56 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use spring_keys::srs::{self, SrsCard, FIRST_INTERVAL_SECS};
+use spring_keys::{AccumulatedStats, SpringKeys};
+use std::collections::BTreeMap;
+
+#[test]
+fn test_sloppy_quote_is_due_before_clean_one() {
+    let mut cards = BTreeMap::new();
+    let now = 1_700_000_000;
+
+    // Both quotes were learned once already
+    srs::record_review(&mut cards, 0, 60.0, 100.0, now);
+    srs::record_review(&mut cards, 1, 60.0, 100.0, now);
+
+    let later = now + FIRST_INTERVAL_SECS;
+    srs::record_review(&mut cards, 0, 35.0, 70.0, later);
+    srs::record_review(&mut cards, 1, 60.0, 100.0, later);
+
+    assert!(cards[&0].due < cards[&1].due);
+    assert!(cards[&0].ease < cards[&1].ease);
+    assert_eq!(cards[&0].streak, 0);
+
+    // Once it's due, the sloppy quote is served ahead of unseen ones
+    let candidates = [0, 1, 2];
+    assert_eq!(srs::next_quote(&cards, &candidates, later), Some(2));
+    assert_eq!(srs::next_quote(&cards, &candidates, cards[&0].due), Some(0));
+}
+
+#[test]
+fn test_ease_has_a_floor() {
+    let mut card = SrsCard::new(0);
+    for _ in 0..20 {
+        card.review(0, 0);
+    }
+    assert_eq!(card.ease, srs::MIN_EASE);
+    assert_eq!(card.interval_secs, FIRST_INTERVAL_SECS);
+}
+
+#[test]
+fn test_review_is_graded_on_its_own_quote() {
+    let mut app = SpringKeys::new();
+    app.accumulated_stats = AccumulatedStats::new();
+    app.start_typing_session(Some("a clean warm up quote".to_string()));
+    app.feed_str("a clean warm up quote");
+
+    // A sloppy review after it fails, even though the session as a whole is accurate
+    app.start_typing_session(Some("cd".to_string()));
+    app.memorize_index = Some(3);
+    app.feed_str("cx");
+    app.process_input(KeyCode::Backspace, KeyModifiers::NONE);
+    app.feed_str("d");
+
+    let card = &app.accumulated_stats.srs_cards[&3];
+    assert_eq!(card.streak, 0);
+    assert!(card.ease < srs::DEFAULT_EASE);
+}
@@ -0,0 +1,14 @@
+use spring_keys::GameType;
+
+#[test]
+fn test_game_type_names_round_trip() {
+    for game in GameType::ALL {
+        assert_eq!(game.to_string().parse::<GameType>(), Ok(game));
+    }
+    assert_eq!(GameType::WordByWord.to_string(), "words");
+    assert_eq!(GameType::SentenceBySentence.to_string(), "sentences");
+    assert_eq!("Practice".parse::<GameType>(), Ok(GameType::Practice));
+
+    let err = "minesweeper".parse::<GameType>().unwrap_err();
+    assert_eq!(err.to_string(), "unknown game mode 'minesweeper'");
+}
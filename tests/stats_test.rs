@@ -0,0 +1,226 @@
+use spring_keys::{AccumulatedStats, ExtendedStats, TypingSession, STATS_SCHEMA_VERSION};
+use std::fs;
+use std::time::Instant;
+
+#[test]
+fn test_accumulated_heatmap_export_uses_key_averages() {
+    let mut stats = AccumulatedStats::new();
+    let mut key_stats = ExtendedStats::new();
+    key_stats.update(120.0, Instant::now());
+    stats.key_averages.insert('q', key_stats);
+
+    let parsed: serde_json::Value = serde_json::from_str(&stats.heatmap_to_json()).unwrap();
+    assert_eq!(parsed["q"]["avg_ms"], 120.0);
+    assert!(parsed["q"].get("hits").is_none(), "All-time export doesn't track hit counts");
+}
+
+#[test]
+fn test_key_decay_favors_recent_sessions() {
+    let decayed_average = |timings: &[f64]| {
+        let mut stats = AccumulatedStats::new();
+        let mut key_stats = ExtendedStats::new();
+        for (session, ms) in timings.iter().enumerate() {
+            key_stats.update_aged(*ms, Instant::now(), session as u64 + 1, 0.5);
+        }
+        stats.key_averages.insert('k', key_stats);
+        let parsed: serde_json::Value = serde_json::from_str(&stats.heatmap_to_json()).unwrap();
+        parsed["k"]["avg_ms"].as_f64().unwrap()
+    };
+
+    // The same fast session pulls the average down further when it's the latest one
+    let recent_fast = decayed_average(&[200.0, 200.0, 100.0]);
+    let old_fast = decayed_average(&[100.0, 200.0, 200.0]);
+    assert!(recent_fast < old_fast, "{} should be below {}", recent_fast, old_fast);
+    assert!(recent_fast < 200.0 - 100.0 / 3.0, "Recent sessions outweigh the plain mean");
+
+    // Without decay every session counts the same
+    let mut even = ExtendedStats::new();
+    for (session, ms) in [200.0, 200.0, 100.0].into_iter().enumerate() {
+        even.update_aged(ms, Instant::now(), session as u64 + 1, 1.0);
+    }
+    assert!((even.recent_mean - even.mean).abs() < 1e-9);
+
+    // Values from one session don't decay each other, however often the key comes up
+    let mut same_session = ExtendedStats::new();
+    for ms in [200.0, 200.0, 100.0] {
+        same_session.update_aged(ms, Instant::now(), 1, 0.5);
+    }
+    assert!((same_session.recent_mean - same_session.mean).abs() < 1e-9);
+}
+
+#[test]
+fn test_key_timings_decay_by_session_age() {
+    let mut stats = AccumulatedStats::new();
+    stats.key_decay = 0.5;
+    // Two quotes in the first session, then one in the next
+    stats.update_from_session(&typed_session("ab", "ab"));
+    stats.update_from_session(&typed_session("ab", "ab"));
+    assert_eq!(stats.session_count, 1);
+    stats.begin_session();
+    stats.update_from_session(&typed_session("ab", "ab"));
+    assert_eq!(stats.session_count, 2);
+
+    // The first session's two values keep half their weight: 2 * 0.5 + 1
+    let b = &stats.key_averages[&'b'];
+    assert_eq!(b.recent_session, 2);
+    assert!((b.recent_weight - 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_estimated_wpm_from_key_averages() {
+    let mut stats = AccumulatedStats::new();
+    assert_eq!(stats.estimated_wpm_for("abab"), 0.0);
+
+    for (key, ms) in [('a', 100.0), ('b', 200.0)] {
+        let mut key_stats = ExtendedStats::new();
+        key_stats.update(ms, Instant::now());
+        stats.key_averages.insert(key, key_stats);
+    }
+
+    // 4 characters in 600ms: 0.8 words in 0.01 minutes
+    assert!((stats.estimated_wpm_for("abab") - 80.0).abs() < 1e-9);
+    // 'c' has no data and counts at the 150ms average over all keys
+    assert!((stats.estimated_wpm_for("abc") - 80.0).abs() < 1e-9);
+    assert!((stats.estimated_wpm_for("aaaa") - 120.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_stats_schema_version_upgrades_old_files_and_rejects_newer_ones() {
+    let dir = std::env::temp_dir().join(format!("spring-keys-schema-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    // Version 1 files were written before schema_version existed
+    let legacy = dir.join("legacy.json");
+    fs::write(&legacy, r#"{"total_quotes":3,"total_keystrokes":120,"total_errors":4,
+        "session_errors":0,"avg_wpm":42.0,"avg_accuracy":96.5}"#).unwrap();
+    let stats = AccumulatedStats::load_from_file(&legacy).unwrap();
+    assert_eq!(stats.total_quotes, 3);
+    assert_eq!(stats.schema_version, STATS_SCHEMA_VERSION);
+    // Correct keystrokes weren't saved back then, so they are estimated from the accuracy
+    assert_eq!(stats.total_correct_keystrokes, 116);
+    assert_eq!(stats.weighted_wpm_denominator, 120.0);
+    assert_eq!(stats.weighted_wpm_numerator, 42.0 * 120.0);
+
+    let future = dir.join("future.json");
+    let mut newer = serde_json::to_value(AccumulatedStats::new()).unwrap();
+    newer["schema_version"] = (STATS_SCHEMA_VERSION + 1).into();
+    fs::write(&future, newer.to_string()).unwrap();
+    let err = AccumulatedStats::load_from_file(&future).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+fn typed_session(text: &str, typed: &str) -> TypingSession {
+    let mut session = TypingSession::new(text.to_string());
+    for c in typed.chars() {
+        session.record_keystroke(c);
+    }
+    session.calculate_metrics();
+    session
+}
+
+#[test]
+fn test_summary_accuracy_is_weighted_by_keystrokes() {
+    let mut stats = AccumulatedStats::new();
+    // A short perfect quote, then a long one with a wrong key before every right one
+    stats.update_from_session(&typed_session("abcd", "abcd"));
+    stats.update_from_session(&typed_session(&"a".repeat(50), &"xa".repeat(50)));
+
+    let summary = stats.summary();
+    assert_eq!(summary.total_quotes, 2);
+    assert_eq!(summary.total_keystrokes, 104);
+    assert!((summary.overall_accuracy - 54.0 / 104.0 * 100.0).abs() < 1e-9);
+    // The per-quote average barely notices the long, sloppy quote
+    assert!(stats.avg_accuracy > 90.0);
+}
+
+#[test]
+fn test_avg_wpm_is_weighted_by_keystrokes() {
+    let mut stats = AccumulatedStats::new();
+
+    // A short fast quote and a long slow one
+    let mut short = typed_session("abcdefghij", "abcdefghij");
+    short.metrics.wpm = 100.0;
+    stats.update_from_session(&short);
+    let mut long = typed_session(&"a".repeat(90), &"a".repeat(90));
+    long.metrics.wpm = 50.0;
+    stats.update_from_session(&long);
+
+    // (100 * 10 + 50 * 90) / 100, not the plain mean of 75
+    assert!((stats.avg_wpm - 55.0).abs() < 1e-9);
+    assert_eq!(stats.weighted_wpm_denominator, 100.0);
+}
+
+#[test]
+fn test_prune_directory_keeps_newest_sessions() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let max = 3;
+    for timestamp in 0..=max {
+        fs::write(dir.path().join(format!("session_{:010}.json", 1_700_000_000 + timestamp)), "{}").unwrap();
+    }
+    fs::write(dir.path().join("accumulated.json"), "{}").unwrap();
+
+    assert_eq!(AccumulatedStats::prune_directory_at(dir.path(), max).unwrap(), 1);
+    let mut remaining: Vec<String> = fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    remaining.sort();
+    assert_eq!(remaining, vec![
+        "accumulated.json",
+        "session_1700000001.json",
+        "session_1700000002.json",
+        "session_1700000003.json",
+    ]);
+
+    // Already within the limit, nothing else goes
+    assert_eq!(AccumulatedStats::prune_directory_at(dir.path(), max).unwrap(), 0);
+}
+
+#[test]
+fn test_prune_directory_leaves_other_json_files_alone() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let others = ["accumulated.json", "my_notes.json", "session_backup.json", "session_.json", "session_12.json.bak"];
+    for name in others {
+        fs::write(dir.path().join(name), "{}").unwrap();
+    }
+    // Timestamps without zero padding still go oldest first
+    for timestamp in [900, 1_700_000_000] {
+        fs::write(dir.path().join(format!("session_{}.json", timestamp)), "{}").unwrap();
+    }
+
+    assert_eq!(AccumulatedStats::prune_directory_at(dir.path(), 1).unwrap(), 1);
+    assert!(dir.path().join("session_1700000000.json").exists());
+    assert_eq!(AccumulatedStats::prune_directory_at(dir.path(), 0).unwrap(), 1);
+    let mut remaining: Vec<String> = fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    remaining.sort();
+    let mut expected = others.map(String::from).to_vec();
+    expected.sort();
+    assert_eq!(remaining, expected);
+}
+
+#[test]
+fn test_sessions_are_bucketed_by_hour_of_day() {
+    let mut stats = AccumulatedStats::new();
+    let morning = typed_session("abcd", "abcd");
+    let evening = typed_session("abcd", "abxd");
+    stats.update_from_session_at(&morning, 9);
+    stats.update_from_session_at(&evening, 21);
+    stats.update_from_session_at(&evening, 21);
+
+    assert_eq!(stats.hourly_counts[9], 1);
+    assert_eq!(stats.hourly_counts[21], 2);
+    assert_eq!(stats.hourly_counts.iter().sum::<usize>(), 3);
+    assert_eq!(stats.hourly_average(9), Some(morning.get_averages().0));
+    assert_eq!(stats.hourly_average(21), Some(evening.get_averages().0));
+    assert_eq!(stats.hourly_average(10), None);
+
+    let chart = stats.hourly_chart(10);
+    assert_eq!(chart.len(), 2);
+    assert!(chart[0].starts_with("09:00 "));
+    assert!(chart[1].starts_with("21:00 "));
+}
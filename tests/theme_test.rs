@@ -0,0 +1,19 @@
+use spring_keys::ui::theme::Theme;
+
+#[test]
+fn test_high_contrast_theme_has_distinct_cursor_and_text() {
+    let theme = Theme::from_name("high-contrast").expect("high-contrast is built in");
+    assert_ne!(theme.cursor_bg, theme.quote_text);
+    assert_ne!(theme.cursor_bg, theme.cursor_fg);
+    assert_ne!(theme.input, theme.quote_text);
+}
+
+#[test]
+fn test_theme_names_resolve() {
+    for name in Theme::NAMES {
+        assert!(Theme::from_name(name).is_some(), "{} should resolve", name);
+    }
+    assert_eq!(Theme::from_name("CLASSIC"), Some(Theme::classic()));
+    assert_eq!(Theme::from_name("neon"), None);
+    assert_eq!(Theme::default(), Theme::classic());
+}